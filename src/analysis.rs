@@ -1,10 +1,14 @@
+use serde::{Deserialize, Serialize, Serializer};
 use std::collections::HashMap;
 
-use crate::config::ThresholdConfig;
+use crate::client::ClientLimit;
+use crate::config::{AbortConfig, ThresholdConfig};
+use crate::ratelimit::{merge_info, RateLimitInfo};
 use crate::runner::BenchmarkResult;
+use crate::status::{ErrorKind, StatusClass, StatusClasses};
 
 /// Status of a benchmark step
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum StepStatus {
     Ok,
     Warning,
@@ -13,6 +17,8 @@ pub enum StepStatus {
     Blocked,
     Hung,
     Gone,
+    /// Rate limited or blocked while probing past the limiter; the ramp continues
+    Limited,
 }
 
 impl std::fmt::Display for StepStatus {
@@ -25,6 +31,7 @@ impl std::fmt::Display for StepStatus {
             StepStatus::Blocked => write!(f, "BLOCK"),
             StepStatus::Hung => write!(f, "HANG"),
             StepStatus::Gone => write!(f, "GONE"),
+            StepStatus::Limited => write!(f, "LIMIT"),
         }
     }
 }
@@ -33,12 +40,15 @@ impl std::fmt::Display for StepStatus {
 #[derive(Debug, Clone)]
 pub enum BreakReason {
     ErrorRate(f64),
-    RateLimited(f64), // Error rate when rate limited (429)
-    Blocked(f64),     // Error rate when blocked by WAF/security (403)
+    RateLimited(f64), // Error rate when rate limited (429 or a rate-limit status class)
+    Blocked(f64),     // Error rate when blocked by WAF/security (403 or a block status class)
+    ConnectionErrors(ErrorKind, f64), // Error rate when most errors got no response, by kind
     P99Latency(f64),
     ThroughputDegradation(f64), // actual rate vs target rate percentage
     Hung,                       // Server stopped responding
     NoResponses,                // No successful responses received
+    AbortedEarly,               // Thresholds exceeded for the abort window, step cut short
+    ClientLimited(ClientLimit), // The load generator, not the server, ran out of resources
     None,
 }
 
@@ -54,6 +64,9 @@ impl std::fmt::Display for BreakReason {
             BreakReason::Blocked(rate) => {
                 write!(f, "Blocked by WAF/security ({:.1}% errors)", rate)
             }
+            BreakReason::ConnectionErrors(kind, rate) => {
+                write!(f, "{} ({:.1}% errors)", kind.break_description(), rate)
+            }
             BreakReason::P99Latency(ms) => {
                 write!(f, "p99 latency exceeded threshold ({:.0}ms)", ms)
             }
@@ -62,11 +75,25 @@ impl std::fmt::Display for BreakReason {
             }
             BreakReason::Hung => write!(f, "Server stopped responding"),
             BreakReason::NoResponses => write!(f, "No successful responses received"),
+            BreakReason::AbortedEarly => write!(f, "Thresholds exceeded, step aborted early"),
+            BreakReason::ClientLimited(limit) => {
+                write!(f, "Load generator saturated ({})", limit)
+            }
             BreakReason::None => write!(f, ""),
         }
     }
 }
 
+/// Serialized as its description (null for none)
+impl Serialize for BreakReason {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            BreakReason::None => serializer.serialize_none(),
+            _ => serializer.collect_str(self),
+        }
+    }
+}
+
 /// Analysis of benchmark results
 #[derive(Debug)]
 pub struct AnalysisResult {
@@ -75,7 +102,7 @@ pub struct AnalysisResult {
 }
 
 /// Summary of all benchmark results
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct BenchmarkSummary {
     pub breaking_point_rate: Option<u32>,
     pub break_reason: BreakReason,
@@ -85,21 +112,53 @@ pub struct BenchmarkSummary {
     pub total_duration_seconds: u64,
     pub was_rate_limited: bool,
     pub was_blocked: bool,
+    /// The breaking step was aborted before its full duration
+    pub aborted_early: bool,
+    /// First rate limited or blocked rate (the policy limit)
+    pub policy_limit_rate: Option<u32>,
+    /// Why the policy limit step was limited (rate limited or blocked, with its share)
+    pub policy_limit_reason: BreakReason,
+    /// First rate the backend itself broke at (the capacity limit)
+    pub capacity_limit_rate: Option<u32>,
+    /// The ramp continued past the policy limit (`--probe-past-limit`)
+    pub probed_past_limit: bool,
+    /// Requests/s the server still accepted in the rate-limited step
+    pub rate_limited_accepted_rate: Option<f64>,
+    /// Rate-limit policy advertised in sampled response headers across all steps
+    pub rate_limit: Option<RateLimitInfo>,
+    /// First rate at which the load generator was saturated, with the resource
+    pub client_limited_rate: Option<u32>,
+    pub client_limit: Option<ClientLimit>,
+    /// Rate at which the safety cutoff stopped the run
+    pub safety_cutoff_rate: Option<u32>,
     /// Aggregated HTTP error status codes across all results, sorted by count (descending)
     pub aggregated_error_codes: Vec<(u32, u64)>,
+    /// Aggregated failures without a response by kind, sorted by count (descending)
+    pub aggregated_error_kinds: Vec<(ErrorKind, u64)>,
+    /// Responses whose status code is classed as ignored
+    pub ignored: u64,
+    /// Sampled responses checked against the response assertions
+    pub validated: u64,
+    /// Failed assertions by name with their sampled counts, sorted by count (descending)
+    pub assertion_failures: Vec<(String, u64)>,
 }
 
 /// Generate a summary from all benchmark results
 pub fn generate_summary(
     results: &[BenchmarkResult],
     analyses: &[AnalysisResult],
-    duration_per_step: u32,
 ) -> BenchmarkSummary {
     let mut breaking_point_rate: Option<u32> = None;
     let mut break_reason = BreakReason::None;
     let mut last_stable_rate: Option<u32> = None;
     let mut was_rate_limited = false;
     let mut was_blocked = false;
+    let mut aborted_early = false;
+    let mut rate_limited_accepted_rate = None;
+    let mut policy_limit_rate = None;
+    let mut policy_limit_reason = BreakReason::None;
+    let mut capacity_limit_rate = None;
+    let mut probed_past_limit = false;
 
     for (i, analysis) in analyses.iter().enumerate() {
         let is_terminal = matches!(
@@ -114,12 +173,39 @@ pub fn generate_summary(
         if is_terminal {
             breaking_point_rate = Some(results[i].target_rate);
             break_reason = analysis.break_reason.clone();
-            was_rate_limited = analysis.status == StepStatus::RateLimited;
-            was_blocked = analysis.status == StepStatus::Blocked;
+            aborted_early = results[i].aborted_early;
+            match analysis.status {
+                StepStatus::RateLimited | StepStatus::Blocked => {
+                    was_rate_limited = analysis.status == StepStatus::RateLimited;
+                    was_blocked = analysis.status == StepStatus::Blocked;
+                    policy_limit_rate = Some(results[i].target_rate);
+                    policy_limit_reason = analysis.break_reason.clone();
+                    if was_rate_limited {
+                        rate_limited_accepted_rate = Some(accepted_rate(&results[i]));
+                    }
+                }
+                // A saturated load generator says nothing about the backend's capacity
+                _ if matches!(analysis.break_reason, BreakReason::ClientLimited(_)) => {}
+                _ => capacity_limit_rate = Some(results[i].target_rate),
+            }
             if i > 0 {
                 last_stable_rate = Some(results[i - 1].target_rate);
             }
             break;
+        } else if analysis.status == StepStatus::Limited {
+            // Probing past the limiter: the first limited step is the policy limit, and the
+            // step still counts as stable for the backend behind it
+            probed_past_limit = true;
+            if policy_limit_rate.is_none() {
+                policy_limit_rate = Some(results[i].target_rate);
+                policy_limit_reason = analysis.break_reason.clone();
+                rate_limited_accepted_rate = Some(accepted_rate(&results[i]));
+            }
+            match analysis.break_reason {
+                BreakReason::Blocked(_) => was_blocked = true,
+                _ => was_rate_limited = true,
+            }
+            last_stable_rate = Some(results[i].target_rate);
         } else if analysis.status == StepStatus::Ok {
             last_stable_rate = Some(results[i].target_rate);
         }
@@ -134,8 +220,9 @@ pub fn generate_summary(
     let recommended_rate = last_stable_rate.map(|r| (r as f64 * 0.8) as u32);
 
     // Total requests and duration
-    let total_requests: u64 = results.iter().map(|r| r.total_requests).sum();
-    let total_duration_seconds = (results.len() as u64) * (duration_per_step as u64);
+    // Limited responses are kept out of a step's requests when probing, but were still sent
+    let total_requests: u64 = results.iter().map(|r| r.total_requests + r.limited).sum();
+    let total_duration_seconds: u64 = results.iter().map(|r| r.duration_seconds as u64).sum();
 
     // Aggregate error codes across all results
     let mut error_code_counts: HashMap<u32, u64> = HashMap::new();
@@ -146,7 +233,39 @@ pub fn generate_summary(
     }
     // Convert to sorted vec (by count descending)
     let mut aggregated_error_codes: Vec<(u32, u64)> = error_code_counts.into_iter().collect();
-    aggregated_error_codes.sort_by(|a, b| b.1.cmp(&a.1));
+    aggregated_error_codes.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+
+    let mut error_kind_counts: HashMap<ErrorKind, u64> = HashMap::new();
+    for result in results {
+        for (kind, count) in &result.error_kinds {
+            *error_kind_counts.entry(*kind).or_insert(0) += count;
+        }
+    }
+    let mut aggregated_error_kinds: Vec<(ErrorKind, u64)> = error_kind_counts.into_iter().collect();
+    aggregated_error_kinds.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+
+    // Aggregate failed assertions the same way
+    let mut assertion_counts: HashMap<&str, u64> = HashMap::new();
+    for result in results {
+        for (name, count) in &result.assertion_failures {
+            *assertion_counts.entry(name).or_insert(0) += count;
+        }
+    }
+    let mut assertion_failures: Vec<(String, u64)> = assertion_counts
+        .into_iter()
+        .map(|(name, count)| (name.to_string(), count))
+        .collect();
+    assertion_failures.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut rate_limit = None;
+    for result in results {
+        merge_info(&mut rate_limit, result.rate_limit.as_ref());
+    }
+
+    let client_limit = results.iter().find_map(|r| {
+        let limit = r.client.as_ref()?.saturation()?;
+        Some((r.target_rate, limit))
+    });
 
     BenchmarkSummary {
         breaking_point_rate,
@@ -157,7 +276,45 @@ pub fn generate_summary(
         total_duration_seconds,
         was_rate_limited,
         was_blocked,
+        aborted_early,
+        policy_limit_rate,
+        policy_limit_reason,
+        capacity_limit_rate,
+        probed_past_limit,
+        rate_limited_accepted_rate,
+        rate_limit,
+        client_limited_rate: client_limit.as_ref().map(|(rate, _)| *rate),
+        client_limit: client_limit.map(|(_, limit)| limit),
+        safety_cutoff_rate: results
+            .iter()
+            .find(|r| r.safety_cutoff)
+            .map(|r| r.target_rate),
         aggregated_error_codes,
+        aggregated_error_kinds,
+        ignored: results.iter().map(|r| r.ignored).sum(),
+        validated: results.iter().map(|r| r.validated).sum(),
+        assertion_failures,
+    }
+}
+
+/// Requests/s that got a non-error response (limited responses count as rejected)
+fn accepted_rate(result: &BenchmarkResult) -> f64 {
+    let sent = result.total_requests + result.limited;
+    if result.limited > 0 && sent > 0 {
+        let accepted = result.total_requests.saturating_sub(result.errors);
+        result.actual_rate * accepted as f64 / sent as f64
+    } else {
+        result.actual_rate * (1.0 - result.error_rate / 100.0).max(0.0)
+    }
+}
+
+/// Share (percent) of the requests sent that were rate limited or blocked
+pub fn limited_share(result: &BenchmarkResult) -> f64 {
+    let sent = result.total_requests + result.limited;
+    if sent == 0 {
+        0.0
+    } else {
+        result.limited as f64 / sent as f64 * 100.0
     }
 }
 
@@ -168,8 +325,38 @@ fn get_dominant_error_status(result: &BenchmarkResult) -> Option<u32> {
     result.error_status_codes.first().map(|(code, _)| *code)
 }
 
+/// Returns the most common kind of failure for requests without a response, if any
+fn get_dominant_error_kind(result: &BenchmarkResult) -> Option<(ErrorKind, u64)> {
+    // error_kinds is sorted by count descending as well
+    result.error_kinds.first().copied()
+}
+
 /// Analyze a single benchmark result against thresholds
-pub fn analyze_result(result: &BenchmarkResult, thresholds: &ThresholdConfig) -> AnalysisResult {
+/// A step that broke while the load generator was saturated is flagged as client-limited
+/// rather than blamed on the server
+pub fn analyze_result(
+    result: &BenchmarkResult,
+    thresholds: &ThresholdConfig,
+    classes: &StatusClasses,
+) -> AnalysisResult {
+    let analysis = analyze_server(result, thresholds, classes);
+    let limit = result.client.as_ref().and_then(|c| c.saturation());
+    match (analysis.status, limit) {
+        (StepStatus::Break | StepStatus::Gone, Some(limit)) => AnalysisResult {
+            status: StepStatus::Break,
+            break_reason: BreakReason::ClientLimited(limit),
+        },
+        _ => analysis,
+    }
+}
+
+/// Analyze a step as the server's result
+/// The class of the most common error status decides between rate limited, blocked and break
+fn analyze_server(
+    result: &BenchmarkResult,
+    thresholds: &ThresholdConfig,
+    classes: &StatusClasses,
+) -> AnalysisResult {
     // Check if benchmark hung (timed out)
     if result.hung {
         return AnalysisResult {
@@ -180,24 +367,38 @@ pub fn analyze_result(result: &BenchmarkResult, thresholds: &ThresholdConfig) ->
 
     // Check if no successful responses were received (all latency values are 0)
     // This indicates complete failure - server didn't respond to any requests
-    if result.p99_latency_ms == 0.0 && result.avg_latency_ms == 0.0 && result.actual_rate > 0.0 {
+    if no_responses(result) {
         return AnalysisResult {
             status: StepStatus::Gone,
-            break_reason: BreakReason::NoResponses,
+            break_reason: match get_dominant_error_kind(result) {
+                Some((kind, _)) => BreakReason::ConnectionErrors(kind, result.error_rate),
+                None => BreakReason::NoResponses,
+            },
         };
     }
 
     // Check error rate
     if result.error_rate > thresholds.max_error_rate {
+        // Requests without a response outnumbering the most common error status decide first
+        let top_status_count = result.error_status_codes.first().map_or(0, |(_, c)| *c);
+        if let Some((kind, count)) = get_dominant_error_kind(result) {
+            if count > top_status_count {
+                return AnalysisResult {
+                    status: StepStatus::Break,
+                    break_reason: BreakReason::ConnectionErrors(kind, result.error_rate),
+                };
+            }
+        }
+
         // Use plurality: whichever error code is most common determines the status
-        match get_dominant_error_status(result) {
-            Some(429) => {
+        match get_dominant_error_status(result).map(|code| classes.classify(code)) {
+            Some(StatusClass::RateLimit) => {
                 return AnalysisResult {
                     status: StepStatus::RateLimited,
                     break_reason: BreakReason::RateLimited(result.error_rate),
                 };
             }
-            Some(403) => {
+            Some(StatusClass::Block) => {
                 return AnalysisResult {
                     status: StepStatus::Blocked,
                     break_reason: BreakReason::Blocked(result.error_rate),
@@ -230,6 +431,29 @@ pub fn analyze_result(result: &BenchmarkResult, thresholds: &ThresholdConfig) ->
         };
     }
 
+    // A step cut short by early abort or the safety cutoff was failing even if the whole-step
+    // figures, diluted by its healthy start, stay under the thresholds
+    if result.aborted_early || result.safety_cutoff {
+        return AnalysisResult {
+            status: StepStatus::Break,
+            break_reason: BreakReason::AbortedEarly,
+        };
+    }
+
+    // Rate limiting and blocking measured separately while probing past the limiter: over the
+    // error threshold the step is limited, but the ramp goes on
+    let share = limited_share(result);
+    if share > thresholds.max_error_rate {
+        let dominant = result.limited_status_codes.first().map(|(code, _)| *code);
+        return AnalysisResult {
+            status: StepStatus::Limited,
+            break_reason: match dominant.map(|code| classes.classify(code)) {
+                Some(StatusClass::Block) => BreakReason::Blocked(share),
+                _ => BreakReason::RateLimited(share),
+            },
+        };
+    }
+
     // Check for warnings (approaching thresholds)
     let error_warning = result.error_rate > thresholds.max_error_rate * 0.5;
     let latency_warning = result.p99_latency_ms > thresholds.max_p99_ms as f64 * 0.7;
@@ -246,3 +470,38 @@ pub fn analyze_result(result: &BenchmarkResult, thresholds: &ThresholdConfig) ->
         break_reason: BreakReason::None,
     }
 }
+
+/// Whether no request got a response (all latency values are 0 while requests were sent)
+fn no_responses(result: &BenchmarkResult) -> bool {
+    result.p99_latency_ms == 0.0 && result.avg_latency_ms == 0.0 && result.actual_rate > 0.0
+}
+
+/// Error rate of an in-step slice, counting a hung slice or one without responses as 100%
+fn slice_error_rate(result: &BenchmarkResult) -> f64 {
+    if result.hung || no_responses(result) {
+        100.0
+    } else {
+        result.error_rate
+    }
+}
+
+/// Whether an in-step slice exceeds the error rate or p99 threshold by `margin_percent`
+pub fn exceeds_thresholds(
+    result: &BenchmarkResult,
+    thresholds: &ThresholdConfig,
+    margin_percent: f64,
+) -> bool {
+    let factor = 1.0 + margin_percent / 100.0;
+    slice_error_rate(result) > thresholds.max_error_rate * factor
+        || result.p99_latency_ms > thresholds.max_p99_ms as f64 * factor
+}
+
+/// Whether an in-step slice reached the safety cutoff that stops the whole run
+pub fn hits_cutoff(result: &BenchmarkResult, abort: &AbortConfig) -> bool {
+    abort
+        .cutoff_error_rate
+        .is_some_and(|rate| slice_error_rate(result) >= rate)
+        || abort
+            .cutoff_p99_ms
+            .is_some_and(|ms| result.p99_latency_ms >= ms as f64)
+}
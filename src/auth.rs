@@ -1,7 +1,20 @@
+use anyhow::{bail, Context, Result};
 use base64::{engine::general_purpose::STANDARD, Engine};
+use std::time::{Duration, Instant};
 
 use crate::cli::AuthType;
 use crate::config::AuthConfig;
+use crate::signing::{self, SigningRequest};
+use crate::tls::{self, TlsConfig};
+
+/// Token lifetime assumed when the token endpoint doesn't return `expires_in`
+const DEFAULT_TOKEN_LIFETIME_SECONDS: u64 = 3600;
+
+/// Refresh tokens this long before they would expire during a step
+const TOKEN_REFRESH_MARGIN_SECONDS: u64 = 30;
+
+/// Timeout for token endpoint requests
+const TOKEN_REQUEST_TIMEOUT_SECONDS: u64 = 30;
 
 /// Generate the Authorization header value from auth config
 pub fn generate_auth_header(config: &AuthConfig) -> Option<String> {
@@ -19,12 +32,166 @@ pub fn generate_auth_header(config: &AuthConfig) -> Option<String> {
             Some(format!("Authorization: Bearer {}", token))
         }
         AuthType::Header => config.custom_header.clone(),
+        // Dynamic: headers are produced per step by AuthSession
+        AuthType::OAuth2 | AuthType::Hmac | AuthType::SigV4 | AuthType::Jwt => None,
+    }
+}
+
+/// An access token and when it expires
+#[derive(Debug, Clone)]
+struct AccessToken {
+    value: String,
+    expires_at: Instant,
+}
+
+/// Per-run auth state for auth types whose header changes during a run
+#[derive(Debug)]
+pub struct AuthSession {
+    config: AuthConfig,
+    token: Option<AccessToken>,
+    /// HTTP agent for token requests (honours the TLS options)
+    agent: ureq::Agent,
+}
+
+impl AuthSession {
+    /// Create the session, fetching the initial token for token-based auth types
+    pub fn new(config: &AuthConfig, tls: &TlsConfig) -> Result<Self> {
+        let mut session = Self {
+            config: config.clone(),
+            token: None,
+            agent: tls::agent_builder(tls)?.build(),
+        };
+
+        if config.auth_type == AuthType::OAuth2 {
+            session.token = Some(fetch_oauth2_token(&session.agent, config)?);
+        }
+
+        Ok(session)
+    }
+
+    /// Whether the auth headers cover the request URL and body (and so need a fixed URL)
+    pub fn signs_request(&self) -> bool {
+        matches!(self.config.auth_type, AuthType::Hmac | AuthType::SigV4)
+    }
+
+    /// Longest stretch one set of headers may be used for, if the auth type limits it
+    /// Steps longer than this are run in several parts so each part is signed afresh
+    pub fn max_segment_seconds(&self) -> Option<u32> {
+        match self.config.auth_type {
+            AuthType::Hmac | AuthType::SigV4 | AuthType::Jwt => Some(self.config.signature_ttl),
+            _ => None,
+        }
     }
+
+    /// Get the auth headers for `request`, sent for the next `seconds`
+    /// Tokens that would expire in that time are refreshed and signatures are computed now
+    pub fn headers_for(&mut self, request: &SigningRequest, seconds: u32) -> Result<Vec<String>> {
+        let now = chrono::Utc::now();
+        match self.config.auth_type {
+            AuthType::OAuth2 => Ok(vec![self.oauth2_header(seconds)?]),
+            AuthType::Hmac => signing::hmac_headers(&self.config, request, now),
+            AuthType::SigV4 => signing::sigv4_headers(&self.config, request, now),
+            AuthType::Jwt => {
+                let jwt = signing::mint_jwt(&self.config, now, self.config.signature_ttl)?;
+                crate::secret::register(&jwt);
+                Ok(vec![format!("Authorization: Bearer {}", jwt)])
+            }
+            _ => Ok(generate_auth_header(&self.config).into_iter().collect()),
+        }
+    }
+
+    /// Bearer header from the current OAuth2 token, refreshed if it expires within `seconds`
+    fn oauth2_header(&mut self, seconds: u32) -> Result<String> {
+        let needed = Duration::from_secs(seconds as u64 + TOKEN_REFRESH_MARGIN_SECONDS);
+        let expiring = self
+            .token
+            .as_ref()
+            .is_none_or(|t| t.expires_at < Instant::now() + needed);
+
+        if expiring {
+            let token = fetch_oauth2_token(&self.agent, &self.config)?;
+            if token.expires_at < Instant::now() + Duration::from_secs(seconds as u64) {
+                bail!(
+                    "OAuth2 tokens expire sooner than a {}s step; use a shorter --duration",
+                    seconds
+                );
+            }
+            self.token = Some(token);
+        }
+
+        let token = self.token.as_ref().map(|t| t.value.as_str()).unwrap_or("");
+        Ok(format!("Authorization: Bearer {}", token))
+    }
+}
+
+/// Request an access token with the OAuth2 client-credentials grant
+/// Client credentials are sent with HTTP Basic auth (client_secret_basic)
+fn fetch_oauth2_token(agent: &ureq::Agent, config: &AuthConfig) -> Result<AccessToken> {
+    let Some(ref token_url) = config.token_url else {
+        bail!("OAuth2 auth requires --token-url");
+    };
+    let client_id = config.client_id.as_deref().unwrap_or("");
+    let client_secret = config.client_secret.as_deref().unwrap_or("");
+    let credentials = STANDARD.encode(format!("{}:{}", client_id, client_secret));
+
+    let mut form = vec![("grant_type", "client_credentials")];
+    if let Some(ref scope) = config.scope {
+        form.push(("scope", scope));
+    }
+
+    let requested_at = Instant::now();
+    let response = agent
+        .post(token_url)
+        .timeout(Duration::from_secs(TOKEN_REQUEST_TIMEOUT_SECONDS))
+        .set("Authorization", &format!("Basic {}", credentials))
+        .set("Accept", "application/json")
+        .send_form(&form);
+
+    let body = match response {
+        Ok(r) => r.into_string()?,
+        Err(ureq::Error::Status(code, r)) => {
+            let body = r.into_string().unwrap_or_default();
+            bail!(
+                "Token request to {} failed with HTTP {}: {}",
+                token_url,
+                code,
+                body.trim()
+            );
+        }
+        Err(e) => return Err(e).context("OAuth2 token request failed"),
+    };
+
+    let json: serde_json::Value = serde_json::from_str(&body)
+        .with_context(|| format!("Token endpoint {} returned invalid JSON", token_url))?;
+    let value = json
+        .get("access_token")
+        .and_then(|v| v.as_str())
+        .with_context(|| format!("Token endpoint {} returned no access_token", token_url))?;
+    let lifetime = json
+        .get("expires_in")
+        .and_then(|v| v.as_u64().or_else(|| v.as_str()?.parse().ok()))
+        .unwrap_or(DEFAULT_TOKEN_LIFETIME_SECONDS);
+
+    crate::secret::register(value);
+
+    Ok(AccessToken {
+        value: value.to_string(),
+        expires_at: requested_at + Duration::from_secs(lifetime),
+    })
 }
 
 /// Get auth type options for menu display
 pub fn get_auth_type_names() -> Vec<&'static str> {
-    vec!["None", "Basic Auth", "Bearer Token", "API Key Header"]
+    vec![
+        "None",
+        "Basic Auth",
+        "Bearer Token",
+        "API Key Header",
+        "OAuth2 Client Credentials",
+        "HMAC Signature",
+        "AWS SigV4",
+        "JWT (minted locally)",
+    ]
 }
 
 /// Convert menu index to AuthType
@@ -34,6 +201,10 @@ pub fn index_to_auth_type(index: usize) -> AuthType {
         1 => AuthType::Basic,
         2 => AuthType::Bearer,
         3 => AuthType::Header,
+        4 => AuthType::OAuth2,
+        5 => AuthType::Hmac,
+        6 => AuthType::SigV4,
+        7 => AuthType::Jwt,
         _ => AuthType::None,
     }
 }
@@ -1,22 +1,54 @@
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use crate::metrics::MetricSeries;
+use crate::notify::NotifyEvent;
+use crate::status::StatusRule;
 
 /// HTTP load testing tool with automatic breaking point detection using oha
 #[derive(Parser, Debug)]
 #[command(name = "ohabench")]
 #[command(version, about, long_about = None)]
 pub struct Args {
-    /// Target URL(s) to benchmark (can specify multiple)
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Target URL(s) to benchmark (can specify multiple); may contain {{...}} template variables
     #[arg(short, long, action = clap::ArgAction::Append)]
     pub url: Vec<String>,
 
+    /// File of target URLs, one per line, benchmarked together as one target (repeatable)
+    #[arg(long, action = clap::ArgAction::Append)]
+    pub targets_file: Vec<String>,
+
+    /// Number of URLs generated per step from a URL template
+    #[arg(long, default_value = "1000")]
+    pub url_variants: u32,
+
     /// HTTP method to use
     #[arg(short, long, value_enum, default_value = "get")]
     pub method: HttpMethod,
 
-    /// Request body (for POST, PUT, PATCH)
+    /// Request body (for POST, PUT, PATCH); may contain {{...}} template variables
     #[arg(short, long)]
     pub body: Option<String>,
 
+    /// Load the request body from a file (binary files are sent as-is)
+    #[arg(long, conflicts_with = "body")]
+    pub body_file: Option<String>,
+
+    /// Content-Type for the body (inferred from --body-file extension, else application/json)
+    #[arg(long)]
+    pub content_type: Option<String>,
+
+    /// CSV (with header row) or JSONL file providing values for {{data.COLUMN}} body variables
+    #[arg(long)]
+    pub data_file: Option<String>,
+
+    /// Number of distinct bodies rendered per step from a templated body
+    #[arg(long, default_value = "16")]
+    pub body_variants: u32,
+
     /// User-Agent preset or custom string
     #[arg(long, default_value = "ohabench")]
     pub user_agent: String,
@@ -29,22 +61,243 @@ pub struct Args {
     #[arg(long)]
     pub auth_user: Option<String>,
 
-    /// Password for basic auth
-    #[arg(long)]
+    /// Password for basic auth (also env:NAME, file:PATH or - for stdin)
+    #[arg(long, env = "OHABENCH_AUTH_PASS", hide_env_values = true)]
     pub auth_pass: Option<String>,
 
-    /// Bearer token for token auth
-    #[arg(long)]
+    /// Bearer token for token auth (also env:NAME, file:PATH or - for stdin)
+    #[arg(long, env = "OHABENCH_AUTH_TOKEN", hide_env_values = true)]
     pub auth_token: Option<String>,
 
-    /// Custom auth header (e.g., "X-API-Key: secret")
-    #[arg(long)]
+    /// Custom auth header (e.g., "X-API-Key: secret"; also env:NAME, file:PATH or -)
+    #[arg(long, env = "OHABENCH_AUTH_HEADER", hide_env_values = true)]
     pub auth_header: Option<String>,
 
+    /// OAuth2 token endpoint for client-credentials auth
+    #[arg(long)]
+    pub token_url: Option<String>,
+
+    /// OAuth2 client ID
+    #[arg(long)]
+    pub client_id: Option<String>,
+
+    /// OAuth2 client secret (also env:NAME, file:PATH or - for stdin)
+    #[arg(long, env = "OHABENCH_CLIENT_SECRET", hide_env_values = true)]
+    pub client_secret: Option<String>,
+
+    /// OAuth2 scope(s) to request (space-separated)
+    #[arg(long)]
+    pub oauth_scope: Option<String>,
+
+    /// Key ID for signing auth: HMAC key id, AWS access key ID or JWT `kid`
+    #[arg(long)]
+    pub key_id: Option<String>,
+
+    /// Signing key: HMAC secret, AWS secret access key or JWT key (PEM via file:PATH)
+    #[arg(long, env = "OHABENCH_SIGNING_KEY", hide_env_values = true)]
+    pub signing_key: Option<String>,
+
+    /// AWS session token for temporary credentials
+    #[arg(long, env = "OHABENCH_AWS_SESSION_TOKEN", hide_env_values = true)]
+    pub aws_session_token: Option<String>,
+
+    /// AWS region for SigV4 (e.g. us-east-1)
+    #[arg(long)]
+    pub aws_region: Option<String>,
+
+    /// AWS service name for SigV4 (e.g. execute-api, s3)
+    #[arg(long)]
+    pub aws_service: Option<String>,
+
+    /// JWT signing algorithm (HS256, RS256, ES256, ...)
+    #[arg(long, default_value = "HS256")]
+    pub jwt_algorithm: String,
+
+    /// Extra JWT claims as a JSON object (iat, nbf and exp are set automatically)
+    #[arg(long, value_name = "JSON")]
+    pub jwt_claims: Option<String>,
+
+    /// Header carrying the HMAC signature
+    #[arg(long, default_value = "X-Signature")]
+    pub signature_header: String,
+
+    /// Longest time (seconds) a signature or JWT is reused; longer steps are split to re-sign
+    #[arg(long, default_value = "300")]
+    pub signature_ttl: u32,
+
+    /// Import the request from a curl command line (e.g. copied from browser dev tools)
+    #[arg(long, value_name = "COMMAND")]
+    pub import_curl: Option<String>,
+
+    /// Import requests from a browser HAR export
+    #[arg(long, value_name = "FILE")]
+    pub import_har: Option<String>,
+
+    /// Import operations from an OpenAPI 3 / Swagger 2 spec (JSON or YAML)
+    #[arg(long, value_name = "FILE")]
+    pub import_openapi: Option<String>,
+
+    /// Only import requests whose name or URL matches this regex
+    #[arg(long, value_name = "REGEX")]
+    pub import_filter: Option<String>,
+
+    /// Log in by POSTing --login-body to this URL before benchmarking (cookie or token session)
+    #[arg(long)]
+    pub login_url: Option<String>,
+
+    /// Login request body, e.g. '{"user":"u","password":"p"}' (also env:NAME, file:PATH or -)
+    #[arg(long, env = "OHABENCH_LOGIN_BODY", hide_env_values = true)]
+    pub login_body: Option<String>,
+
+    /// Content-Type of the login body (JSON if it starts with '{', else form-encoded)
+    #[arg(long)]
+    pub login_content_type: Option<String>,
+
+    /// Dot path of a token in the JSON login response (e.g. data.access_token); cookies otherwise
+    #[arg(long)]
+    pub login_token_path: Option<String>,
+
+    /// Header template for a login token
+    #[arg(long, default_value = crate::login::DEFAULT_LOGIN_HEADER)]
+    pub login_header: String,
+
+    /// Extra CA bundle (PEM) to trust for the target (repeatable)
+    #[arg(long, value_name = "FILE", action = clap::ArgAction::Append)]
+    pub cacert: Vec<String>,
+
+    /// Client certificate (PEM) for mutual TLS (requires --key)
+    #[arg(long, value_name = "FILE", requires = "key")]
+    pub cert: Option<String>,
+
+    /// Private key (PEM) for --cert
+    #[arg(long, value_name = "FILE", requires = "cert")]
+    pub key: Option<String>,
+
+    /// Server name for the TLS handshake and Host header, still connecting to the URL's host
+    #[arg(long, value_name = "HOST")]
+    pub sni: Option<String>,
+
+    /// Skip TLS certificate verification (self-signed or mismatched certificates)
+    #[arg(long)]
+    pub insecure: bool,
+
     /// Additional headers (repeatable)
     #[arg(short = 'H', long = "header", action = clap::ArgAction::Append)]
     pub headers: Vec<String>,
 
+    /// Accepted response status codes, e.g. 200,201 (checked on sampled responses)
+    #[arg(long, value_name = "CODES", value_delimiter = ',')]
+    pub expect_status: Vec<u16>,
+
+    /// Text every sampled response body must contain (repeatable)
+    #[arg(long, value_name = "TEXT", action = clap::ArgAction::Append)]
+    pub expect_body_contains: Vec<String>,
+
+    /// Regex every sampled response body must match (repeatable)
+    #[arg(long, value_name = "REGEX", action = clap::ArgAction::Append)]
+    pub expect_body_regex: Vec<String>,
+
+    /// Value expected at a dot path of the JSON body, e.g. status=ok (repeatable)
+    #[arg(long, value_name = "PATH=VALUE", value_parser = crate::validate::parse_json_assertion, action = clap::ArgAction::Append)]
+    pub expect_json: Vec<(String, String)>,
+
+    /// JSON Schema file every sampled response body must match
+    #[arg(long, value_name = "FILE")]
+    pub expect_schema: Option<String>,
+
+    /// Largest accepted response body in bytes
+    #[arg(long, value_name = "BYTES")]
+    pub expect_max_size: Option<u64>,
+
+    /// Classify status codes: CODES=success|error|rate-limit|block|ignore, CODES being 404,
+    /// 300-399 or 3xx (repeatable, later rules win; e.g. 404=success, 503=rate-limit)
+    #[arg(long, value_name = "CODES=CLASS", value_parser = crate::status::parse_status_rule, action = clap::ArgAction::Append)]
+    pub status_class: Vec<StatusRule>,
+
+    /// Responses sampled per second for the --expect-* assertions and rate-limit headers
+    #[arg(long, default_value = "2", value_name = "N")]
+    pub sample_rate: u32,
+
+    /// Client identity header, e.g. "X-Api-Key: key2" (repeatable); the rate is split evenly
+    /// across identities so each stays under a per-client limit
+    #[arg(long, value_name = "HEADER", action = clap::ArgAction::Append)]
+    pub identity: Vec<String>,
+
+    /// Keep ramping when rate limited or blocked, measuring 429/403 responses as a separate
+    /// series, to find the capacity behind the limiter as well as the policy limit
+    #[arg(long)]
+    pub probe_past_limit: bool,
+
+    /// Don't sample responses for rate-limit headers (Retry-After, RateLimit-*, X-RateLimit-*)
+    #[arg(long)]
+    pub no_rate_limit_headers: bool,
+
+    /// Worker to generate load from, started with `ohabench worker` (repeatable); each step's
+    /// rate is split evenly across the workers
+    #[arg(long, value_name = "HOST:PORT", action = clap::ArgAction::Append)]
+    pub worker: Vec<String>,
+
+    /// Token the workers require (supports env:, file:, -)
+    #[arg(long, env = "OHABENCH_WORKER_TOKEN", hide_env_values = true)]
+    pub worker_token: Option<String>,
+
+    /// Prometheus metrics endpoint scraped at the start and end of each step
+    #[arg(long, value_name = "URL")]
+    pub metrics_url: Option<String>,
+
+    /// Command printing Prometheus-format metrics, run at the start and end of each step
+    #[arg(long, value_name = "COMMAND")]
+    pub metrics_command: Option<String>,
+
+    /// Series to record from the metrics, e.g. db_pool=hikaricp_connections_active (repeatable;
+    /// alternatives separated by |; defaults to cpu, memory, gc and db_pool)
+    #[arg(long, value_name = "LABEL=METRIC", value_parser = crate::metrics::parse_metric_series, action = clap::ArgAction::Append)]
+    pub metric: Vec<MetricSeries>,
+
+    /// Command run once before the benchmark starts; the run is aborted if it fails
+    #[arg(long, value_name = "COMMAND")]
+    pub hook_before_run: Option<String>,
+
+    /// Command run before each step (step info in OHABENCH_* variables and JSON on stdin)
+    #[arg(long, value_name = "COMMAND")]
+    pub hook_before_step: Option<String>,
+
+    /// Command run after each step with its results
+    #[arg(long, value_name = "COMMAND")]
+    pub hook_after_step: Option<String>,
+
+    /// Command run when a URL reaches its breaking point
+    #[arg(long, value_name = "COMMAND")]
+    pub hook_on_break: Option<String>,
+
+    /// Command run once after the benchmark with every URL's summary and the saved files
+    #[arg(long, value_name = "COMMAND")]
+    pub hook_after_run: Option<String>,
+
+    /// Stop the ramp when the after-step hook exits with a nonzero code
+    #[arg(long, requires = "hook_after_step")]
+    pub hook_veto: bool,
+
+    /// Webhook to POST notifications to as JSON (repeatable; supports env:, file:)
+    #[arg(long, value_name = "URL", action = clap::ArgAction::Append)]
+    pub notify_webhook: Vec<String>,
+
+    /// Slack incoming webhook to notify (repeatable; supports env:, file:)
+    #[arg(long, value_name = "URL", action = clap::ArgAction::Append)]
+    pub notify_slack: Vec<String>,
+
+    /// Microsoft Teams incoming webhook to notify (repeatable; supports env:, file:)
+    #[arg(long, value_name = "URL", action = clap::ArgAction::Append)]
+    pub notify_teams: Vec<String>,
+
+    /// Command run with each notification as JSON on stdin (repeatable)
+    #[arg(long, value_name = "COMMAND", action = clap::ArgAction::Append)]
+    pub notify_command: Vec<String>,
+
+    /// Events to notify on, e.g. break,failure (defaults to complete, break and failure)
+    #[arg(long, value_enum, value_name = "EVENTS", value_delimiter = ',')]
+    pub notify_on: Vec<NotifyEvent>,
+
     /// Ramping mode
     #[arg(long, value_enum, default_value = "linear")]
     pub mode: RampingMode,
@@ -65,6 +318,22 @@ pub struct Args {
     #[arg(short, long, default_value = "30")]
     pub duration: u32,
 
+    /// Extend each step until p50, p99 and throughput stabilize (replaces --duration)
+    #[arg(long)]
+    pub adaptive: bool,
+
+    /// Shortest adaptive step in seconds
+    #[arg(long, default_value = "10", value_name = "SECONDS")]
+    pub adaptive_min: u32,
+
+    /// Longest adaptive step in seconds
+    #[arg(long, default_value = "120", value_name = "SECONDS")]
+    pub adaptive_max: u32,
+
+    /// Change (%) in p50, p99 and throughput still considered stable
+    #[arg(long, default_value = "5.0", value_name = "PERCENT")]
+    pub adaptive_tolerance: f64,
+
     /// Number of threads
     #[arg(short, long, default_value = "4")]
     pub threads: u32,
@@ -81,6 +350,22 @@ pub struct Args {
     #[arg(long, default_value = "5000")]
     pub max_p99: u32,
 
+    /// Abort a step early once the break thresholds are exceeded by this many percent
+    #[arg(long, value_name = "PERCENT")]
+    pub abort_margin: Option<f64>,
+
+    /// Seconds the thresholds must stay exceeded before aborting (also the check interval)
+    #[arg(long, default_value = "5", value_name = "SECONDS")]
+    pub abort_window: u32,
+
+    /// Safety cutoff: stop the whole run once the error rate reaches this (%)
+    #[arg(long, value_name = "PERCENT")]
+    pub cutoff_error_rate: Option<f64>,
+
+    /// Safety cutoff: stop the whole run once p99 latency reaches this (ms)
+    #[arg(long, value_name = "MS")]
+    pub cutoff_p99: Option<u32>,
+
     /// Warmup duration in seconds (0 to disable)
     #[arg(long, default_value = "0")]
     pub warmup: u32,
@@ -89,6 +374,14 @@ pub struct Args {
     #[arg(long, default_value = "0")]
     pub cooldown: u32,
 
+    /// Show a live dashboard during each step (keys: s skip step, q stop ramp, + extend)
+    #[arg(long)]
+    pub live: bool,
+
+    /// Seconds per oha run in live mode; the dashboard updates after each one
+    #[arg(long, default_value = "5", value_name = "SECONDS")]
+    pub live_interval: u32,
+
     /// Directory to save report files
     #[arg(short = 'o', long = "output-dir")]
     pub report_dir: Option<String>,
@@ -97,12 +390,96 @@ pub struct Args {
     #[arg(short = 'n', long = "name")]
     pub report_name: Option<String>,
 
-    /// Run in non-interactive mode (requires --url)
+    /// Run in non-interactive mode (requires --url, --targets-file or an --import-* option)
     #[arg(long)]
     pub non_interactive: bool,
+
+    /// Scenario name used to group runs in the history (defaults to --name, then the URL)
+    #[arg(long)]
+    pub scenario: Option<String>,
+
+    /// Directory for the run history store
+    #[arg(long, global = true)]
+    pub history_dir: Option<String>,
+
+    /// Don't record this run in the history store
+    #[arg(long)]
+    pub no_history: bool,
+
+    /// Fail (exit 3) unless every URL is stable at this rate (req/s)
+    #[arg(long, value_name = "N")]
+    pub require_rate: Option<u32>,
+
+    /// Fail (exit 4) if p99 at RATE req/s exceeds MS milliseconds (repeatable, e.g. 500:250)
+    #[arg(long, value_name = "RATE:MS", value_parser = crate::gate::parse_p99_gate, action = clap::ArgAction::Append)]
+    pub max_p99_at: Vec<(u32, u32)>,
+
+    /// Fail if the run ends in this state: rate-limited (exit 5), blocked (6), hung (7) (repeatable)
+    #[arg(long, value_enum, action = clap::ArgAction::Append)]
+    pub fail_on: Vec<FailOn>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+impl Args {
+    /// Whether any benchmark target was given on the command line
+    pub fn has_targets(&self) -> bool {
+        !self.url.is_empty()
+            || !self.targets_file.is_empty()
+            || self.import_curl.is_some()
+            || self.import_har.is_some()
+            || self.import_openapi.is_some()
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// List recorded benchmark runs
+    History {
+        /// Only show runs for this scenario
+        #[arg(long)]
+        scenario: Option<String>,
+
+        /// Only show runs for this URL
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Maximum number of runs to show (most recent)
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+
+    /// Show breaking point, recommended rate and p99 trends across recorded runs
+    Trend {
+        /// Only include runs for this scenario
+        #[arg(long)]
+        scenario: Option<String>,
+
+        /// Only include runs for this URL
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Rate (req/s) at which to compare p99 latency (defaults to the highest rate tested in every run)
+        #[arg(long)]
+        at_rate: Option<u32>,
+
+        /// Save a trend graph PNG to this path
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Run oha for a coordinator (`ohabench --worker HOST:PORT ...`) on this machine
+    Worker {
+        /// Address to listen on (other than loopback only with a token)
+        #[arg(long, default_value = crate::worker::DEFAULT_LISTEN)]
+        listen: String,
+
+        /// Token coordinators must send (supports env:, file:, -)
+        #[arg(long, env = "OHABENCH_WORKER_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum HttpMethod {
     Get,
     Post,
@@ -131,6 +508,12 @@ pub enum AuthType {
     Basic,
     Bearer,
     Header,
+    #[value(name = "oauth2")]
+    OAuth2,
+    Hmac,
+    #[value(name = "sigv4")]
+    SigV4,
+    Jwt,
 }
 
 impl std::fmt::Display for AuthType {
@@ -140,11 +523,33 @@ impl std::fmt::Display for AuthType {
             AuthType::Basic => write!(f, "Basic Auth"),
             AuthType::Bearer => write!(f, "Bearer Token"),
             AuthType::Header => write!(f, "Custom Header"),
+            AuthType::OAuth2 => write!(f, "OAuth2 Client Credentials"),
+            AuthType::Hmac => write!(f, "HMAC Signature"),
+            AuthType::SigV4 => write!(f, "AWS SigV4"),
+            AuthType::Jwt => write!(f, "JWT"),
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FailOn {
+    RateLimited,
+    Blocked,
+    Hung,
+}
+
+impl std::fmt::Display for FailOn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FailOn::RateLimited => write!(f, "rate-limited"),
+            FailOn::Blocked => write!(f, "blocked"),
+            FailOn::Hung => write!(f, "hung"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum RampingMode {
     Linear,
     Exponential,
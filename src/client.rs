@@ -0,0 +1,243 @@
+use serde::{Deserialize, Serialize};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// How often the load generator's resources are sampled while a step runs
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Extra time the monitor keeps sampling after the step's duration (oha waits for in-flight
+/// requests with `-w`)
+const GRACE_SECONDS: u64 = 5;
+
+/// Machine CPU use at or above which the load generator is saturated
+const CPU_LIMIT_PERCENT: f64 = 90.0;
+
+/// Memory use at or above which the load generator is saturated
+const MEMORY_LIMIT_PERCENT: f64 = 95.0;
+
+/// Share of the ephemeral port range in use at or above which new connections may fail locally
+const PORTS_LIMIT_PERCENT: f64 = 90.0;
+
+/// Resources of the machine running oha during a step (peak values, Linux only)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClientUsage {
+    /// Machine-wide CPU use (% of all cores)
+    pub cpu_percent: f64,
+    /// CPU used by the oha processes (% of one core, so 250 means two and a half cores)
+    pub oha_cpu_percent: f64,
+    /// Memory in use (% of total, excluding reclaimable cache)
+    pub memory_percent: f64,
+    /// Open TCP sockets, including those in TIME_WAIT
+    pub sockets: u64,
+    /// Size of the ephemeral port range
+    pub port_range: u64,
+    /// CPU cores available
+    pub cores: u64,
+}
+
+/// Why the load generator rather than the server limited a step
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClientLimit {
+    /// Machine CPU use (%)
+    Cpu(f64),
+    /// Memory use (%)
+    Memory(f64),
+    /// Open sockets against the ephemeral port range
+    Ports(u64, u64),
+}
+
+impl std::fmt::Display for ClientLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientLimit::Cpu(percent) => write!(f, "client CPU at {:.0}%", percent),
+            ClientLimit::Memory(percent) => write!(f, "client memory at {:.0}%", percent),
+            ClientLimit::Ports(sockets, range) => {
+                write!(f, "{} sockets open of {} ephemeral ports", sockets, range)
+            }
+        }
+    }
+}
+
+/// Serialized as its description
+impl Serialize for ClientLimit {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl ClientUsage {
+    /// The resource that saturated the load generator, if any
+    pub fn saturation(&self) -> Option<ClientLimit> {
+        if self.cpu_percent >= CPU_LIMIT_PERCENT {
+            Some(ClientLimit::Cpu(self.cpu_percent))
+        } else if self.memory_percent >= MEMORY_LIMIT_PERCENT {
+            Some(ClientLimit::Memory(self.memory_percent))
+        } else if self.port_range > 0
+            && self.sockets as f64 >= self.port_range as f64 * PORTS_LIMIT_PERCENT / 100.0
+        {
+            Some(ClientLimit::Ports(self.sockets, self.port_range))
+        } else {
+            None
+        }
+    }
+
+    /// Fold another sample or part in, keeping the peaks
+    pub fn merge(&mut self, other: &ClientUsage) {
+        self.cpu_percent = self.cpu_percent.max(other.cpu_percent);
+        self.oha_cpu_percent = self.oha_cpu_percent.max(other.oha_cpu_percent);
+        self.memory_percent = self.memory_percent.max(other.memory_percent);
+        self.sockets = self.sockets.max(other.sockets);
+        self.port_range = self.port_range.max(other.port_range);
+        self.cores = self.cores.max(other.cores);
+    }
+}
+
+/// Fold an optional usage sample into another
+pub fn merge_usage(target: &mut Option<ClientUsage>, other: Option<&ClientUsage>) {
+    if let Some(other) = other {
+        match target {
+            Some(usage) => usage.merge(other),
+            None => *target = Some(other.clone()),
+        }
+    }
+}
+
+/// Sample the machine and the oha processes `pids` on a background thread until they exit
+/// (or `seconds` plus a grace period pass)
+/// Returns None where `/proc` isn't available
+pub fn start_monitor(pids: Vec<u32>, seconds: u32) -> Option<JoinHandle<Option<ClientUsage>>> {
+    let first = Snapshot::take(&pids)?;
+    Some(std::thread::spawn(move || monitor(first, &pids, seconds)))
+}
+
+fn monitor(mut previous: Snapshot, pids: &[u32], seconds: u32) -> Option<ClientUsage> {
+    let deadline = Instant::now() + Duration::from_secs(seconds as u64 + GRACE_SECONDS);
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get()) as u64;
+    let port_range = read_port_range();
+    let mut usage: Option<ClientUsage> = None;
+
+    while Instant::now() < deadline && pids.iter().any(|pid| is_running(*pid)) {
+        std::thread::sleep(SAMPLE_INTERVAL);
+        let Some(current) = Snapshot::take(pids) else {
+            break;
+        };
+        let total_ticks = current.total_ticks.saturating_sub(previous.total_ticks);
+        if total_ticks > 0 {
+            let idle_ticks = current.idle_ticks.saturating_sub(previous.idle_ticks);
+            let oha_ticks = current.oha_ticks.saturating_sub(previous.oha_ticks);
+            // /proc/stat counts every core's ticks; one core's share is total / cores
+            let sample = ClientUsage {
+                cpu_percent: (total_ticks - idle_ticks.min(total_ticks)) as f64
+                    / total_ticks as f64
+                    * 100.0,
+                oha_cpu_percent: oha_ticks as f64 / total_ticks as f64 * cores as f64 * 100.0,
+                memory_percent: current.memory_percent,
+                sockets: current.sockets,
+                port_range,
+                cores,
+            };
+            merge_usage(&mut usage, Some(&sample));
+        }
+        previous = current;
+    }
+    usage
+}
+
+/// One reading of the counters needed for a sample
+struct Snapshot {
+    /// All CPU ticks since boot, across cores
+    total_ticks: u64,
+    /// Idle and I/O wait ticks since boot
+    idle_ticks: u64,
+    /// User and system ticks of the oha processes
+    oha_ticks: u64,
+    memory_percent: f64,
+    sockets: u64,
+}
+
+impl Snapshot {
+    fn take(pids: &[u32]) -> Option<Self> {
+        let stat = std::fs::read_to_string("/proc/stat").ok()?;
+        let cpu: Vec<u64> = stat
+            .lines()
+            .next()?
+            .split_whitespace()
+            .skip(1)
+            .filter_map(|n| n.parse().ok())
+            .collect();
+        // user nice system idle iowait irq softirq steal (guest time is already in user)
+        let total_ticks = cpu.iter().take(8).sum();
+        let idle_ticks = cpu.get(3).copied().unwrap_or(0) + cpu.get(4).copied().unwrap_or(0);
+
+        Some(Self {
+            total_ticks,
+            idle_ticks,
+            oha_ticks: pids.iter().filter_map(|pid| process_ticks(*pid)).sum(),
+            memory_percent: read_memory_percent().unwrap_or(0.0),
+            sockets: count_sockets(),
+        })
+    }
+}
+
+/// User plus system ticks of a process
+fn process_ticks(pid: u32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // Fields after the parenthesized command name, which may itself contain spaces
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(utime + stime)
+}
+
+/// Whether a process is still running (exited children stay as zombies until waited for)
+fn is_running(pid: u32) -> bool {
+    std::fs::read_to_string(format!("/proc/{}/stat", pid))
+        .ok()
+        .and_then(|stat| {
+            let state = stat
+                .rsplit_once(')')?
+                .1
+                .split_whitespace()
+                .next()?
+                .to_string();
+            Some(state != "Z" && state != "X")
+        })
+        .unwrap_or(false)
+}
+
+fn read_memory_percent() -> Option<f64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let field = |name: &str| -> Option<f64> {
+        meminfo
+            .lines()
+            .find(|line| line.starts_with(name))?
+            .split_whitespace()
+            .nth(1)?
+            .parse()
+            .ok()
+    };
+    let total = field("MemTotal:")?;
+    let available = field("MemAvailable:")?;
+    (total > 0.0).then(|| (total - available) / total * 100.0)
+}
+
+/// TCP sockets in any state, IPv4 and IPv6
+fn count_sockets() -> u64 {
+    ["/proc/net/tcp", "/proc/net/tcp6"]
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .map(|table| table.lines().skip(1).count() as u64)
+        .sum()
+}
+
+fn read_port_range() -> u64 {
+    std::fs::read_to_string("/proc/sys/net/ipv4/ip_local_port_range")
+        .ok()
+        .and_then(|range| {
+            let mut bounds = range.split_whitespace().map(|n| n.parse::<u64>().ok());
+            let (low, high) = (bounds.next()??, bounds.next()??);
+            Some(high.saturating_sub(low) + 1)
+        })
+        .unwrap_or(0)
+}
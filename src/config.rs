@@ -1,4 +1,13 @@
-use crate::cli::{AuthType, HttpMethod, RampingMode};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::{AuthType, FailOn, HttpMethod, RampingMode};
+use crate::hooks::HookConfig;
+use crate::login::LoginConfig;
+use crate::metrics::MetricsConfig;
+use crate::notify::NotifyConfig;
+use crate::status::StatusClasses;
+use crate::tls::TlsConfig;
+use crate::validate::ValidationConfig;
 
 /// Complete benchmark configuration
 #[derive(Debug, Clone)]
@@ -6,15 +15,46 @@ pub struct BenchmarkConfig {
     pub urls: Vec<String>,
     pub method: HttpMethod,
     pub body: Option<String>,
+    pub body_file: Option<String>,
+    pub content_type: Option<String>,
+    pub data_file: Option<String>,
+    pub body_variants: u32,
+    pub url_variants: u32,
     pub user_agent: String,
     pub auth: AuthConfig,
+    pub login: Option<LoginConfig>,
+    pub tls: TlsConfig,
     pub headers: Vec<String>,
+    pub validation: ValidationConfig,
+    /// Which status codes count as success, error, rate limiting, blocking or are ignored
+    pub status_classes: StatusClasses,
+    /// Sample responses for the server's advertised rate-limit policy
+    pub rate_limit_headers: bool,
+    /// Headers of separate client identities; the rate is split across them
+    pub identities: Vec<String>,
+    /// Keep ramping past rate limiting and blocking, measuring it as a separate series
+    pub probe_past_limit: bool,
+    /// Workers generating the load (`ohabench worker`), splitting each step's rate
+    pub workers: Vec<String>,
+    pub worker_token: Option<String>,
+    /// Server metrics scraped at the start and end of each step
+    pub metrics: MetricsConfig,
+    /// Commands run before and after the run and each step
+    pub hooks: HookConfig,
+    /// Webhook, chat and command notifications on completion, break and failure
+    pub notify: NotifyConfig,
     pub ramping: RampingConfig,
     pub thresholds: ThresholdConfig,
+    pub abort: AbortConfig,
     pub warmup_seconds: u32,
     pub cooldown_seconds: u32,
+    /// Show the live dashboard, running each step as slices of `live_interval` seconds
+    pub live: bool,
+    pub live_interval: u32,
     pub report_dir: Option<String>,
     pub report_name: Option<String>,
+    pub scenario: Option<String>,
+    pub gates: GateConfig,
 }
 
 /// Get the default downloads directory for the current OS
@@ -111,6 +151,21 @@ pub struct AuthConfig {
     pub password: Option<String>,
     pub token: Option<String>,
     pub custom_header: Option<String>,
+    /// OAuth2 client-credentials settings
+    pub token_url: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub scope: Option<String>,
+    /// Request signing settings (HMAC, SigV4, JWT)
+    pub key_id: Option<String>,
+    pub signing_key: Option<String>,
+    pub session_token: Option<String>,
+    pub region: Option<String>,
+    pub service: Option<String>,
+    pub algorithm: Option<String>,
+    pub claims: Option<String>,
+    pub signature_header: Option<String>,
+    pub signature_ttl: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -120,31 +175,129 @@ pub struct RampingConfig {
     pub max_rate: u32,
     pub step: u32,
     pub duration_seconds: u32,
+    /// Run each step until its figures settle instead of for `duration_seconds`
+    pub adaptive: Option<AdaptiveDuration>,
     pub threads: u32,
     pub connections: u32,
 }
 
+/// Bounds and tolerance for adaptive step durations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdaptiveDuration {
+    pub min_seconds: u32,
+    pub max_seconds: u32,
+    /// Maximum change (percent) in p50, p99 and throughput between the two latest windows
+    pub tolerance_percent: f64,
+}
+
+impl Default for AdaptiveDuration {
+    fn default() -> Self {
+        Self {
+            min_seconds: 10,
+            max_seconds: 120,
+            tolerance_percent: 5.0,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ThresholdConfig {
     pub max_error_rate: f64,
     pub max_p99_ms: u32,
 }
 
+/// In-step monitoring that ends a clearly failing step, or the whole run, before its time is up
+/// Steps are checked in slices of `window_seconds`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AbortConfig {
+    /// Abort a step once a slice exceeds the break thresholds by this many percent
+    pub margin_percent: Option<f64>,
+    /// Seconds the thresholds must stay exceeded before a step is aborted
+    pub window_seconds: u32,
+    /// Stop the whole run once a slice reaches this error rate (percent)
+    pub cutoff_error_rate: Option<f64>,
+    /// Stop the whole run once a slice reaches this p99 latency (ms)
+    pub cutoff_p99_ms: Option<u32>,
+}
+
+impl AbortConfig {
+    /// Whether steps need to be checked while they run
+    pub fn is_enabled(&self) -> bool {
+        self.margin_percent.is_some()
+            || self.cutoff_error_rate.is_some()
+            || self.cutoff_p99_ms.is_some()
+    }
+}
+
+impl Default for AbortConfig {
+    fn default() -> Self {
+        Self {
+            margin_percent: None,
+            window_seconds: 5,
+            cutoff_error_rate: None,
+            cutoff_p99_ms: None,
+        }
+    }
+}
+
+/// Pass/fail gates evaluated after a non-interactive run
+#[derive(Debug, Clone, Default)]
+pub struct GateConfig {
+    pub require_rate: Option<u32>,
+    /// (rate req/s, max p99 ms) pairs
+    pub max_p99_at: Vec<(u32, u32)>,
+    pub fail_on: Vec<FailOn>,
+}
+
+impl GateConfig {
+    /// Whether any gate is configured
+    pub fn is_empty(&self) -> bool {
+        self.require_rate.is_none() && self.max_p99_at.is_empty() && self.fail_on.is_empty()
+    }
+}
+
 impl Default for BenchmarkConfig {
     fn default() -> Self {
         Self {
             urls: Vec::new(),
             method: HttpMethod::Get,
             body: None,
+            body_file: None,
+            content_type: None,
+            data_file: None,
+            body_variants: 16,
+            url_variants: 1000,
             user_agent: "ohabench/0.1.0".to_string(),
             auth: AuthConfig::default(),
+            login: None,
+            tls: TlsConfig::default(),
             headers: Vec::new(),
+            validation: ValidationConfig {
+                sample_rate: 2,
+                ..ValidationConfig::default()
+            },
+            status_classes: StatusClasses::default(),
+            rate_limit_headers: true,
+            identities: Vec::new(),
+            probe_past_limit: false,
+            workers: Vec::new(),
+            worker_token: None,
+            metrics: MetricsConfig::default(),
+            hooks: HookConfig::default(),
+            notify: NotifyConfig::default(),
             ramping: RampingConfig::default(),
             thresholds: ThresholdConfig::default(),
+            abort: AbortConfig::default(),
             warmup_seconds: 0,
             cooldown_seconds: 0,
+            live: false,
+            live_interval: 5,
             report_dir: None,
             report_name: None,
+            scenario: None,
+            gates: GateConfig::default(),
         }
     }
 }
@@ -157,6 +310,19 @@ impl Default for AuthConfig {
             password: None,
             token: None,
             custom_header: None,
+            token_url: None,
+            client_id: None,
+            client_secret: None,
+            scope: None,
+            key_id: None,
+            signing_key: None,
+            session_token: None,
+            region: None,
+            service: None,
+            algorithm: None,
+            claims: None,
+            signature_header: None,
+            signature_ttl: 300,
         }
     }
 }
@@ -169,6 +335,7 @@ impl Default for RampingConfig {
             max_rate: 5000,
             step: 50,
             duration_seconds: 30,
+            adaptive: None,
             threads: 4,
             connections: 100,
         }
@@ -185,6 +352,13 @@ impl Default for ThresholdConfig {
 }
 
 impl RampingConfig {
+    /// Longest a step can run: the fixed duration, or the adaptive upper bound
+    pub fn max_step_seconds(&self) -> u32 {
+        self.adaptive
+            .as_ref()
+            .map_or(self.duration_seconds, |a| a.max_seconds)
+    }
+
     /// Generate the sequence of rates to test
     pub fn generate_rates(&self) -> Vec<u32> {
         let mut rates = Vec::new();
@@ -0,0 +1,332 @@
+use console::{style, Term};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use std::time::{Duration, Instant};
+
+use crate::output::{format_latency, format_number};
+use crate::runner::BenchmarkResult;
+
+/// Seconds added to the running step by the extend key
+pub const EXTEND_SECONDS: u32 = 30;
+
+/// Slices merged for the rolling throughput and latency figures
+pub const ROLLING_SLICES: usize = 3;
+
+/// Prior steps shown in the sparklines
+const SPARKLINE_WIDTH: usize = 40;
+
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// What the user asked for from the dashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiveAction {
+    Continue,
+    /// End the current step after the running slice, keeping its results
+    SkipStep,
+    /// End the current step after the running slice, then stop the ramp
+    StopRamp,
+}
+
+/// Metrics for the step so far, updated after each slice
+struct StepProgress {
+    rolling: BenchmarkResult,
+    so_far: BenchmarkResult,
+}
+
+/// Live view of the running step, redrawn in place on stderr
+/// Steps run as short consecutive oha slices; the figures update as each slice finishes
+pub struct Dashboard {
+    term: Term,
+    drawn: usize,
+    total_steps: usize,
+    step: usize,
+    rate: u32,
+    planned_seconds: u32,
+    started: Instant,
+    progress: Option<StepProgress>,
+    prior_p99: Vec<f64>,
+    prior_error_rate: Vec<f64>,
+    action: LiveAction,
+    extension: u32,
+    notice: Option<String>,
+}
+
+impl Dashboard {
+    /// Create the dashboard, or None when stderr isn't an interactive terminal
+    pub fn new(total_steps: usize) -> Option<Self> {
+        let term = Term::stderr();
+        if !term.is_term() {
+            return None;
+        }
+        Some(Self {
+            term,
+            drawn: 0,
+            total_steps,
+            step: 0,
+            rate: 0,
+            planned_seconds: 0,
+            started: Instant::now(),
+            progress: None,
+            prior_p99: Vec::new(),
+            prior_error_rate: Vec::new(),
+            action: LiveAction::Continue,
+            extension: 0,
+            notice: None,
+        })
+    }
+
+    /// Start showing a new step
+    pub fn begin_step(&mut self, step: usize, rate: u32, seconds: u32) {
+        self.step = step;
+        self.rate = rate;
+        self.planned_seconds = seconds;
+        self.started = Instant::now();
+        self.progress = None;
+        self.action = LiveAction::Continue;
+        self.extension = 0;
+        self.notice = None;
+        self.draw();
+    }
+
+    /// Record a finished slice: `rolling` covers the last few slices, `so_far` the whole step
+    pub fn slice_done(&mut self, rolling: BenchmarkResult, so_far: BenchmarkResult) {
+        self.progress = Some(StepProgress { rolling, so_far });
+        self.draw();
+    }
+
+    /// Redraw and handle key presses for up to `timeout`
+    pub fn wait(&mut self, timeout: Duration) {
+        self.draw();
+
+        let deadline = Instant::now() + timeout;
+        if terminal::enable_raw_mode().is_err() {
+            std::thread::sleep(timeout);
+            return;
+        }
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            match event::poll(remaining) {
+                Ok(true) => {
+                    if let Ok(Event::Key(key)) = event::read() {
+                        self.handle_key(key);
+                    }
+                }
+                _ => break,
+            }
+        }
+        terminal::disable_raw_mode().ok();
+    }
+
+    /// Seconds the user added to the step since the last call
+    pub fn take_extension(&mut self) -> u32 {
+        let seconds = self.extension;
+        self.planned_seconds += seconds;
+        self.extension = 0;
+        seconds
+    }
+
+    /// Whether the current step should end early
+    pub fn ends_step(&self) -> bool {
+        self.action != LiveAction::Continue
+    }
+
+    /// Whether the user asked to stop the ramp
+    pub fn stop_requested(&self) -> bool {
+        self.action == LiveAction::StopRamp
+    }
+
+    /// Clear the dashboard and remember the step for the sparklines
+    pub fn end_step(&mut self, result: &BenchmarkResult) {
+        self.clear();
+        self.prior_p99.push(result.p99_latency_ms);
+        self.prior_error_rate.push(result.error_rate);
+    }
+
+    /// Remove the dashboard from the screen
+    pub fn clear(&mut self) {
+        if self.drawn > 0 {
+            self.term.clear_last_lines(self.drawn).ok();
+            self.drawn = 0;
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        match key.code {
+            // Raw mode swallows the interrupt signal, so Ctrl+C exits here
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                terminal::disable_raw_mode().ok();
+                self.clear();
+                std::process::exit(130);
+            }
+            KeyCode::Char('s') | KeyCode::Char('n') => {
+                self.action = LiveAction::SkipStep;
+                self.notice = Some("Skipping to the next step after this slice...".to_string());
+            }
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.action = LiveAction::StopRamp;
+                self.notice = Some("Stopping the ramp after this slice...".to_string());
+            }
+            KeyCode::Char('+') | KeyCode::Char('e') => {
+                self.extension += EXTEND_SECONDS;
+                self.notice = Some(format!(
+                    "Step extended to {}s",
+                    self.planned_seconds + self.extension
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self) {
+        let lines = self.render();
+        self.clear();
+        for line in &lines {
+            self.term.write_line(line).ok();
+        }
+        self.drawn = lines.len();
+    }
+
+    fn render(&self) -> Vec<String> {
+        let planned = self.planned_seconds + self.extension;
+        let elapsed = (self.started.elapsed().as_secs() as u32).min(planned);
+        let label = |text: &str| format!("  {:<13}", style(text).cyan());
+
+        let mut lines = vec![format!(
+            "  {} [{}] {}s/{}s @ {} req/s",
+            style(format!("Step {}/{}", self.step, self.total_steps)).bold(),
+            progress_bar(elapsed, planned, 20),
+            elapsed,
+            planned,
+            self.rate
+        )];
+
+        match self.progress {
+            Some(ref p) => {
+                lines.push(format!(
+                    "{}{:.1} req/s now, {:.1} avg ({} requests)",
+                    label("Throughput"),
+                    p.rolling.actual_rate,
+                    p.so_far.actual_rate,
+                    format_number(p.so_far.total_requests)
+                ));
+                lines.push(format!(
+                    "{}p50 {}  p99 {}  (recent)",
+                    label("Latency"),
+                    format_latency(p.rolling.p50_latency_ms),
+                    format_latency(p.rolling.p99_latency_ms)
+                ));
+                lines.push(format!("{}{}", label("Errors"), describe_errors(&p.so_far)));
+            }
+            None => lines.push(format!(
+                "{}{}",
+                label("Throughput"),
+                style("waiting for the first slice...").dim()
+            )),
+        }
+
+        if !self.prior_p99.is_empty() {
+            lines.push(format!(
+                "{}{}  {}",
+                label("p99 by step"),
+                sparkline(&self.prior_p99),
+                style(format!(
+                    "last {}",
+                    format_latency(*self.prior_p99.last().unwrap())
+                ))
+                .dim()
+            ));
+            lines.push(format!(
+                "{}{}  {}",
+                label("Err by step"),
+                sparkline(&self.prior_error_rate),
+                style(format!(
+                    "last {:.1}%",
+                    self.prior_error_rate.last().unwrap()
+                ))
+                .dim()
+            ));
+        }
+
+        lines.push(match self.notice {
+            Some(ref notice) => format!("  {}", style(notice).yellow()),
+            None => format!(
+                "  {}",
+                style(format!(
+                    "[s] skip step  [q] stop ramp  [+] extend {}s",
+                    EXTEND_SECONDS
+                ))
+                .dim()
+            ),
+        });
+        lines
+    }
+}
+
+/// Error count with a breakdown by status code and kind of connection error
+fn describe_errors(result: &BenchmarkResult) -> String {
+    if result.errors == 0 {
+        return "none".to_string();
+    }
+    let mut text = format!(
+        "{} ({:.1}%)",
+        format_number(result.errors),
+        result.error_rate
+    );
+    let mut counted = 0;
+    let breakdown = result
+        .error_status_codes
+        .iter()
+        .map(|(code, count)| (code.to_string(), *count))
+        .chain(
+            result
+                .error_kinds
+                .iter()
+                .map(|(kind, count)| (kind.to_string(), *count)),
+        );
+    for (label, count) in breakdown.take(4) {
+        text.push_str(&format!("  {} ×{}", label, format_number(count)));
+        counted += count;
+    }
+    let other = result.errors.saturating_sub(counted);
+    if other > 0 && counted > 0 {
+        text.push_str(&format!("  other ×{}", format_number(other)));
+    }
+    text
+}
+
+fn progress_bar(done: u32, total: u32, width: usize) -> String {
+    let filled = if total == 0 {
+        width
+    } else {
+        (done as usize * width / total as usize).min(width)
+    };
+    format!(
+        "{}{}",
+        style("━".repeat(filled)).cyan(),
+        style("─".repeat(width - filled)).dim()
+    )
+}
+
+/// Unicode sparkline of the most recent values, scaled between their min and max
+fn sparkline(values: &[f64]) -> String {
+    let values = &values[values.len().saturating_sub(SPARKLINE_WIDTH)..];
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|&v| {
+            let level = if max > min {
+                ((v - min) / (max - min) * (SPARK_CHARS.len() - 1) as f64).round() as usize
+            } else {
+                0
+            };
+            SPARK_CHARS[level.min(SPARK_CHARS.len() - 1)]
+        })
+        .collect()
+}
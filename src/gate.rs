@@ -0,0 +1,205 @@
+use serde::Serialize;
+
+use crate::analysis::StepStatus;
+use crate::cli::FailOn;
+use crate::config::GateConfig;
+use crate::output::UrlBenchmarkResults;
+
+/// Exit code when every gate passed
+pub const EXIT_PASS: i32 = 0;
+/// Exit code when a URL was not stable at `--require-rate`
+pub const EXIT_REQUIRE_RATE: i32 = 3;
+/// Exit code when a `--max-p99-at` latency gate failed
+pub const EXIT_MAX_P99: i32 = 4;
+/// Exit code for `--fail-on rate-limited`
+pub const EXIT_RATE_LIMITED: i32 = 5;
+/// Exit code for `--fail-on blocked`
+pub const EXIT_BLOCKED: i32 = 6;
+/// Exit code for `--fail-on hung`
+pub const EXIT_HUNG: i32 = 7;
+/// Exit code when a URL produced no results at all (e.g. warmup or the first step failed)
+pub const EXIT_INCOMPLETE: i32 = 8;
+
+/// A single failed gate
+#[derive(Debug, Clone, Serialize)]
+pub struct GateFailure {
+    pub gate: String,
+    pub url: String,
+    pub message: String,
+    #[serde(skip)]
+    pub exit_code: i32,
+}
+
+/// Per-URL outcome included in the verdict line
+#[derive(Debug, Clone, Serialize)]
+pub struct UrlVerdict {
+    pub url: String,
+    pub breaking_point: Option<u32>,
+    pub last_stable: Option<u32>,
+    pub recommended: Option<u32>,
+    /// First rate limited or blocked rate
+    pub policy_limit: Option<u32>,
+    /// First rate the backend itself broke at
+    pub capacity_limit: Option<u32>,
+    /// First rate the load generator was saturated at
+    pub client_limited: Option<u32>,
+}
+
+/// Result of evaluating all gates for a run
+#[derive(Debug, Clone, Serialize)]
+pub struct Verdict {
+    pub result: &'static str,
+    pub exit_code: i32,
+    pub failures: Vec<GateFailure>,
+    pub urls: Vec<UrlVerdict>,
+}
+
+impl Verdict {
+    /// Render the single machine-readable verdict line
+    pub fn to_line(&self) -> String {
+        format!(
+            "ohabench-verdict: {}",
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+}
+
+/// Evaluate the configured gates against the results of a run
+/// When several gates fail, the exit code of the lowest-numbered failing gate is used
+pub fn evaluate_gates(
+    gates: &GateConfig,
+    urls: &[String],
+    url_results: &[UrlBenchmarkResults],
+) -> Verdict {
+    let mut failures = Vec::new();
+
+    // Every requested URL must have produced at least one measured step
+    for url in urls {
+        let measured = url_results
+            .iter()
+            .any(|u| &u.url == url && !u.results.is_empty());
+        if !measured {
+            failures.push(GateFailure {
+                gate: "incomplete".to_string(),
+                url: url.clone(),
+                message: "no results were collected".to_string(),
+                exit_code: EXIT_INCOMPLETE,
+            });
+        }
+    }
+
+    for url_result in url_results {
+        let url = &url_result.url;
+        let summary = &url_result.summary;
+
+        // Stable at the required rate: the ramp must have reached it without breaking
+        if let Some(required) = gates.require_rate {
+            let stable = summary.last_stable_rate.is_some_and(|r| r >= required);
+            if !stable {
+                let message = match summary.last_stable_rate {
+                    Some(r) => {
+                        format!("last stable rate {} req/s < required {} req/s", r, required)
+                    }
+                    None => format!("no stable rate (required {} req/s)", required),
+                };
+                failures.push(GateFailure {
+                    gate: "require-rate".to_string(),
+                    url: url.clone(),
+                    message,
+                    exit_code: EXIT_REQUIRE_RATE,
+                });
+            }
+        }
+
+        // p99 at a rate is read from the first step at or above that rate
+        for &(rate, max_ms) in &gates.max_p99_at {
+            let step = url_result.results.iter().find(|r| r.target_rate >= rate);
+            let message = match step {
+                Some(r) if r.p99_latency_ms > max_ms as f64 => Some(format!(
+                    "p99 {:.0}ms at {} req/s > {}ms",
+                    r.p99_latency_ms, r.target_rate, max_ms
+                )),
+                Some(_) => None,
+                None => Some(format!("{} req/s was never tested", rate)),
+            };
+            if let Some(message) = message {
+                failures.push(GateFailure {
+                    gate: format!("max-p99-at {}:{}", rate, max_ms),
+                    url: url.clone(),
+                    message,
+                    exit_code: EXIT_MAX_P99,
+                });
+            }
+        }
+
+        let hung = url_result
+            .analyses
+            .last()
+            .is_some_and(|a| a.status == StepStatus::Hung);
+
+        for fail_on in &gates.fail_on {
+            let (triggered, exit_code) = match fail_on {
+                FailOn::RateLimited => (summary.was_rate_limited, EXIT_RATE_LIMITED),
+                FailOn::Blocked => (summary.was_blocked, EXIT_BLOCKED),
+                FailOn::Hung => (hung, EXIT_HUNG),
+            };
+            if triggered {
+                failures.push(GateFailure {
+                    gate: format!("fail-on {}", fail_on),
+                    url: url.clone(),
+                    message: format!(
+                        "{} at {} req/s",
+                        fail_on,
+                        match fail_on {
+                            FailOn::Hung => summary.breaking_point_rate,
+                            _ => summary.policy_limit_rate,
+                        }
+                        .unwrap_or(0)
+                    ),
+                    exit_code,
+                });
+            }
+        }
+    }
+
+    let exit_code = failures
+        .iter()
+        .map(|f| f.exit_code)
+        .min()
+        .unwrap_or(EXIT_PASS);
+
+    Verdict {
+        result: if failures.is_empty() { "pass" } else { "fail" },
+        exit_code,
+        failures,
+        urls: url_results
+            .iter()
+            .map(|u| UrlVerdict {
+                url: u.url.clone(),
+                breaking_point: u.summary.breaking_point_rate,
+                last_stable: u.summary.last_stable_rate,
+                recommended: u.summary.recommended_rate,
+                policy_limit: u.summary.policy_limit_rate,
+                capacity_limit: u.summary.capacity_limit_rate,
+                client_limited: u.summary.client_limited_rate,
+            })
+            .collect(),
+    }
+}
+
+/// Parse a `RATE:MS` pair for `--max-p99-at`
+pub fn parse_p99_gate(value: &str) -> Result<(u32, u32), String> {
+    let (rate, ms) = value
+        .split_once(':')
+        .ok_or_else(|| format!("expected RATE:MS, got '{}'", value))?;
+    let rate = rate
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid rate '{}'", rate))?;
+    let ms = ms
+        .trim()
+        .trim_end_matches("ms")
+        .parse()
+        .map_err(|_| format!("invalid latency '{}'", ms))?;
+    Ok((rate, ms))
+}
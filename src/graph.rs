@@ -2,16 +2,36 @@ use anyhow::Result;
 use plotters::prelude::*;
 use plotters::style::text_anchor::{HPos, Pos, VPos};
 
-use crate::analysis::{BreakReason, StepStatus};
+use crate::analysis::{limited_share, BreakReason, StepStatus};
 use crate::config::ThresholdConfig;
+use crate::history::{common_tested_rate, group_runs, RunRecord};
+use crate::metrics::format_metric_value;
 use crate::output::UrlBenchmarkResults;
 
 /// Error rate line color (red)
 const ERROR_COLOR: RGBColor = RGBColor(239, 68, 68);
 
+/// Limited (rate limited/blocked) share line color when probing past the limiter (amber)
+const LIMITED_COLOR: RGBColor = RGBColor(245, 158, 11);
+
 /// P99 latency line color (blue)
 const P99_COLOR: RGBColor = RGBColor(59, 130, 246);
 
+/// Recommended rate line color (green)
+const RECOMMENDED_COLOR: RGBColor = RGBColor(34, 197, 94);
+
+/// Server metrics series colors, cycled per series
+const METRIC_COLORS: &[RGBColor] = &[
+    RGBColor(139, 92, 246),
+    RGBColor(20, 184, 166),
+    RGBColor(236, 72, 153),
+    RGBColor(132, 204, 22),
+    RGBColor(100, 116, 139),
+];
+
+/// Height of the server metrics panel under a URL panel that has metrics
+const METRICS_PANEL_HEIGHT: u32 = 300;
+
 /// Very light grid/outline color
 const LIGHT_GRID: RGBColor = RGBColor(230, 230, 230);
 
@@ -65,7 +85,12 @@ pub fn generate_error_rate_graph(
     let panel_height = 480u32; // Increased to fit per-plot labels + business scale labels
     let header_height = 140u32;
     let footer_height = 100u32; // Just "Requests/Second" label + legend
-    let height = header_height + (panel_height * num_urls as u32) + footer_height;
+                                // URLs with server metrics get an extra panel below their own
+    let metrics_panels = url_results.iter().filter(|u| has_server_metrics(u)).count() as u32;
+    let height = header_height
+        + (panel_height * num_urls as u32)
+        + (METRICS_PANEL_HEIGHT * metrics_panels)
+        + footer_height;
 
     let root = BitMapBackend::new(output_path, (width, height)).into_drawing_area();
     root.fill(&WHITE)?;
@@ -99,19 +124,24 @@ pub fn generate_error_rate_graph(
     ))?;
 
     // Draw each URL panel with shared y-axis ranges for comparison
-    for (i, url_result) in url_results.iter().enumerate() {
+    let mut y_offset = header_height as i32;
+    for url_result in url_results {
         draw_url_panel(
             &root,
-            i,
-            num_urls,
+            y_offset,
             panel_height,
-            header_height,
             width,
             url_result,
             &x_range,
             &error_y_range,
             &p99_y_range,
         )?;
+        y_offset += panel_height as i32;
+
+        if has_server_metrics(url_result) {
+            draw_metrics_panel(&root, y_offset, width, url_result, &x_range)?;
+            y_offset += METRICS_PANEL_HEIGHT as i32;
+        }
     }
 
     // Draw shared x-axis label at bottom
@@ -125,7 +155,10 @@ pub fn generate_error_rate_graph(
     ))?;
 
     // Draw legend at the bottom
-    draw_legend(&root, width, height)?;
+    let show_limited = url_results
+        .iter()
+        .any(|u| u.results.iter().any(|r| r.limited > 0));
+    draw_legend(&root, width, height, show_limited)?;
 
     root.present()?;
 
@@ -133,20 +166,17 @@ pub fn generate_error_rate_graph(
 }
 
 /// Draw a single URL panel with dual y-axes
+#[allow(clippy::too_many_arguments)]
 fn draw_url_panel(
     root: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
-    panel_index: usize,
-    _total_panels: usize,
+    y_offset: i32,
     panel_height: u32,
-    header_height: u32,
     total_width: u32,
     url_result: &UrlBenchmarkResults,
     x_range: &std::ops::Range<f64>,
     error_y_range: &std::ops::Range<f64>,
     p99_y_range: &std::ops::Range<f64>,
 ) -> Result<()> {
-    let y_offset = header_height as i32 + (panel_index as u32 * panel_height) as i32;
-
     // Panel margins (2x)
     let left_margin = 140i32; // Space for error % y-axis
     let right_margin = 140i32; // Space for p99 y-axis
@@ -221,7 +251,7 @@ fn draw_url_panel(
         chart_right,
         chart_top,
         chart_bottom,
-        &error_y_range,
+        error_y_range,
     )?;
 
     // Draw p99 latency threshold line (3 seconds)
@@ -231,7 +261,7 @@ fn draw_url_panel(
         chart_right,
         chart_top,
         chart_bottom,
-        &p99_y_range,
+        p99_y_range,
     )?;
 
     // Collect data points using target_rate for x-axis, excluding the last point if it's a terminal (failure) status
@@ -273,8 +303,36 @@ fn draw_url_panel(
         chart_right,
         chart_top,
         chart_bottom,
+        ERROR_COLOR,
     )?;
 
+    // Rate limited/blocked share as its own series when probing past the limiter, pinned to
+    // the top of the axis once it's past the error threshold
+    let limited: Vec<(f64, f64)> = url_result
+        .results
+        .iter()
+        .take(data.len())
+        .map(|r| {
+            (
+                r.target_rate as f64,
+                limited_share(r).min(error_y_range.end),
+            )
+        })
+        .collect();
+    if limited.iter().any(|(_, share)| *share > 0.0) {
+        draw_error_data_line(
+            root,
+            &limited,
+            x_range,
+            error_y_range,
+            chart_left,
+            chart_right,
+            chart_top,
+            chart_bottom,
+            LIMITED_COLOR,
+        )?;
+    }
+
     // Draw p99 latency line and points (right y-axis, blue/purple based on threshold)
     draw_p99_data_line(
         root,
@@ -296,7 +354,7 @@ fn draw_url_panel(
         chart_left,
         chart_top,
         chart_bottom,
-        &p99_y_range,
+        p99_y_range,
         P99_COLOR,
         side_padding,
     )?;
@@ -307,7 +365,7 @@ fn draw_url_panel(
         chart_right,
         chart_top,
         chart_bottom,
-        &error_y_range,
+        error_y_range,
         ERROR_COLOR,
         total_width as i32 - side_padding,
     )?;
@@ -323,6 +381,118 @@ fn draw_url_panel(
     Ok(())
 }
 
+fn has_server_metrics(url_result: &UrlBenchmarkResults) -> bool {
+    url_result
+        .results
+        .iter()
+        .any(|r| !r.server_metrics.is_empty())
+}
+
+/// Draw the server metrics of a URL, each series scaled to its own maximum
+fn draw_metrics_panel(
+    root: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
+    y_offset: i32,
+    total_width: u32,
+    url_result: &UrlBenchmarkResults,
+    x_range: &std::ops::Range<f64>,
+) -> Result<()> {
+    // Same horizontal layout as the URL panel so the rates line up
+    let left_margin = 140i32;
+    let right_margin = 140i32;
+    let top_margin = 50i32; // Space for the series legend
+    let bottom_margin = 30i32;
+    let side_padding = 40i32;
+
+    let chart_left = side_padding + left_margin;
+    let chart_right = total_width as i32 - side_padding - right_margin;
+    let chart_top = y_offset + top_margin;
+    let chart_bottom = y_offset + METRICS_PANEL_HEIGHT as i32 - bottom_margin;
+
+    draw_grid_lines(root, chart_left, chart_right, chart_top, chart_bottom)?;
+
+    // Series in the order they were first recorded
+    let mut labels: Vec<&str> = Vec::new();
+    for (label, _) in url_result.results.iter().flat_map(|r| &r.server_metrics) {
+        if !labels.contains(&label.as_str()) {
+            labels.push(label);
+        }
+    }
+
+    let chart_width = (chart_right - chart_left) as f64;
+    let chart_height = (chart_bottom - chart_top) as f64;
+    let x_size = x_range.end - x_range.start;
+    let mut legend_x = chart_left + 10;
+
+    for (i, label) in labels.iter().enumerate() {
+        let color = METRIC_COLORS[i % METRIC_COLORS.len()];
+        let values: Vec<(f64, f64)> = url_result
+            .results
+            .iter()
+            .filter_map(|r| {
+                r.server_metrics
+                    .iter()
+                    .find(|(l, _)| l == label)
+                    .map(|(_, v)| (r.target_rate as f64, *v))
+            })
+            .collect();
+        let max = values.iter().map(|(_, v)| *v).fold(0.0, f64::max);
+
+        let points: Vec<(i32, i32)> = values
+            .iter()
+            .map(|(x, v)| {
+                let ratio = if max > 0.0 { v / max } else { 0.0 };
+                let px = chart_left + (((x - x_range.start) / x_size) * chart_width) as i32;
+                let py = chart_bottom - (ratio * chart_height) as i32;
+                (px, py)
+            })
+            .collect();
+
+        root.draw(&PathElement::new(points.clone(), color.stroke_width(3)))?;
+        for point in points {
+            root.draw(&Circle::new(point, 4, color.filled()))?;
+        }
+
+        // Legend entry: series name with its maximum, which the top of the panel stands for
+        let text = format!("{} (max {})", label, format_metric_value(max));
+        let text_style = TextStyle::from(("sans-serif", 22).into_font())
+            .color(&color)
+            .pos(Pos::new(HPos::Left, VPos::Center));
+        root.draw(&PathElement::new(
+            vec![(legend_x, y_offset + 25), (legend_x + 30, y_offset + 25)],
+            color.stroke_width(4),
+        ))?;
+        root.draw(&Text::new(
+            text.clone(),
+            (legend_x + 40, y_offset + 25),
+            text_style,
+        ))?;
+        legend_x += 40 + (text.len() as i32) * 11 + 40;
+    }
+
+    // Left axis: share of each series' maximum
+    let axis_style = TextStyle::from(("sans-serif", 22).into_font())
+        .color(&SCALE_COLOR)
+        .pos(Pos::new(HPos::Right, VPos::Center));
+    for i in 0..=2 {
+        let y = chart_bottom - (chart_bottom - chart_top) * i / 2;
+        root.draw(&Text::new(
+            format!("{}%", i * 50),
+            (chart_left - 10, y),
+            axis_style.clone(),
+        ))?;
+    }
+    let title_style = TextStyle::from(("sans-serif", 24).into_font())
+        .color(&SCALE_COLOR)
+        .pos(Pos::new(HPos::Center, VPos::Center));
+    root.draw(&Text::new(
+        "server",
+        (side_padding + 10, (chart_top + chart_bottom) / 2),
+        title_style,
+    ))?;
+
+    Ok(())
+}
+
 /// Draw grid lines (horizontal only - vertical lines are drawn by scale dividers)
 fn draw_grid_lines(
     root: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
@@ -340,10 +510,7 @@ fn draw_grid_lines(
     // Horizontal grid lines only (5 lines)
     for i in 0..=4 {
         let y = top + (bottom - top) * i / 4;
-        root.draw(&PathElement::new(
-            vec![(left, y), (right, y)],
-            grid_style.clone(),
-        ))?;
+        root.draw(&PathElement::new(vec![(left, y), (right, y)], grid_style))?;
     }
 
     Ok(())
@@ -367,10 +534,12 @@ fn draw_scale_dividers(
         if min_rate > x_range.start && min_rate < x_range.end {
             boundaries.push(min_rate);
         }
-        if max_rate != f64::MAX && max_rate > x_range.start && max_rate < x_range.end {
-            if !boundaries.contains(&max_rate) {
-                boundaries.push(max_rate);
-            }
+        if max_rate != f64::MAX
+            && max_rate > x_range.start
+            && max_rate < x_range.end
+            && !boundaries.contains(&max_rate)
+        {
+            boundaries.push(max_rate);
         }
     }
 
@@ -385,10 +554,7 @@ fn draw_scale_dividers(
         let x = left + (((boundary - x_range.start) / x_size) * chart_width) as i32;
 
         // Draw solid vertical line
-        root.draw(&PathElement::new(
-            vec![(x, top), (x, bottom)],
-            line_style.clone(),
-        ))?;
+        root.draw(&PathElement::new(vec![(x, top), (x, bottom)], line_style))?;
     }
 
     Ok(())
@@ -425,10 +591,7 @@ fn draw_threshold_lines(
             let mut x = left;
             while x < right {
                 let x_end = (x + dash_len).min(right);
-                root.draw(&PathElement::new(
-                    vec![(x, y), (x_end, y)],
-                    dash_style.clone(),
-                ))?;
+                root.draw(&PathElement::new(vec![(x, y), (x_end, y)], dash_style))?;
                 x += dash_len + gap_len;
             }
         }
@@ -467,10 +630,7 @@ fn draw_p99_threshold_line(
         let mut x = left;
         while x < right {
             let x_end = (x + dash_len).min(right);
-            root.draw(&PathElement::new(
-                vec![(x, y), (x_end, y)],
-                dash_style.clone(),
-            ))?;
+            root.draw(&PathElement::new(vec![(x, y), (x_end, y)], dash_style))?;
             x += dash_len + gap_len;
         }
     }
@@ -480,6 +640,7 @@ fn draw_p99_threshold_line(
 
 /// Draw p99 latency line with threshold-based coloring
 /// Points and segments turn purple when above the 3s threshold
+#[allow(clippy::too_many_arguments)]
 fn draw_p99_data_line(
     root: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
     data: &[(f64, f64)], // (x, p99_ms)
@@ -534,7 +695,7 @@ fn draw_p99_data_line(
     for i in 0..points.len().saturating_sub(1) {
         let (pt1, _) = points[i];
         let (pt2, _) = points[i + 1];
-        root.draw(&PathElement::new(vec![pt1, pt2], line_style.clone()))?;
+        root.draw(&PathElement::new(vec![pt1, pt2], line_style))?;
     }
 
     // Draw points (always blue)
@@ -545,16 +706,19 @@ fn draw_p99_data_line(
     Ok(())
 }
 
-/// Draw error rate line, only showing segments adjacent to non-zero points
+/// Draw an error-rate-style percentage line in `color`, only showing segments adjacent to
+/// non-zero points
+#[allow(clippy::too_many_arguments)]
 fn draw_error_data_line(
     root: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
-    data: &[(f64, f64)], // (x, error_rate)
+    data: &[(f64, f64)], // (x, percent)
     x_range: &std::ops::Range<f64>,
     y_range: &std::ops::Range<f64>,
     left: i32,
     right: i32,
     top: i32,
     bottom: i32,
+    color: RGBColor,
 ) -> Result<()> {
     if data.is_empty() {
         return Ok(());
@@ -592,7 +756,7 @@ fn draw_error_data_line(
 
     // Draw translucent area fill only for visible segments
     if points.len() >= 2 {
-        let fill_color = ERROR_COLOR.mix(0.15);
+        let fill_color = color.mix(0.15);
 
         // Find contiguous visible segments and draw area for each
         let mut i = 0;
@@ -608,9 +772,7 @@ fn draw_error_data_line(
                 if end - start >= 2 {
                     let mut area_points: Vec<(i32, i32)> = Vec::new();
                     area_points.push((points[start].0 .0, bottom));
-                    for j in start..end {
-                        area_points.push(points[j].0);
-                    }
+                    area_points.extend(points[start..end].iter().map(|p| p.0));
                     area_points.push((points[end - 1].0 .0, bottom));
                     area_points.push((points[start].0 .0, bottom));
 
@@ -624,7 +786,7 @@ fn draw_error_data_line(
 
     // Draw line segments only where both endpoints are visible
     let line_style = ShapeStyle {
-        color: ERROR_COLOR.to_rgba(),
+        color: color.to_rgba(),
         filled: false,
         stroke_width: 4,
     };
@@ -633,7 +795,7 @@ fn draw_error_data_line(
         if visible[i] && visible[i + 1] {
             root.draw(&PathElement::new(
                 vec![points[i].0, points[i + 1].0],
-                line_style.clone(),
+                line_style,
             ))?;
         }
     }
@@ -642,7 +804,7 @@ fn draw_error_data_line(
     for i in 0..points.len() {
         if visible[i] {
             let (px, py) = points[i].0;
-            root.draw(&Circle::new((px, py), 4, ERROR_COLOR.filled()))?;
+            root.draw(&Circle::new((px, py), 4, color.filled()))?;
         }
     }
 
@@ -871,6 +1033,7 @@ fn draw_legend(
     root: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
     width: u32,
     height: u32,
+    show_limited: bool,
 ) -> Result<()> {
     let legend_y = (height - 40) as i32;
     let left_x = 60i32;
@@ -904,14 +1067,14 @@ fn draw_legend(
             (p99_threshold_start, legend_y),
             (p99_threshold_start + 8, legend_y),
         ],
-        dash_style.clone(),
+        dash_style,
     ))?;
     root.draw(&PathElement::new(
         vec![
             (p99_threshold_start + 16, legend_y),
             (p99_threshold_start + 24, legend_y),
         ],
-        dash_style.clone(),
+        dash_style,
     ))?;
     root.draw(&PathElement::new(
         vec![
@@ -930,6 +1093,23 @@ fn draw_legend(
         threshold_text_style,
     ))?;
 
+    // Rate limited/blocked share, only drawn when probing past the limiter
+    if show_limited {
+        let limited_start = left_x + 480;
+        root.draw(&PathElement::new(
+            vec![(limited_start, legend_y), (limited_start + 30, legend_y)],
+            LIMITED_COLOR.stroke_width(4),
+        ))?;
+        let limited_style = TextStyle::from(("sans-serif", 20).into_font())
+            .color(&LIMITED_COLOR)
+            .pos(Pos::new(HPos::Left, VPos::Center));
+        root.draw(&Text::new(
+            "Rate Limited/Blocked",
+            (limited_start + 40, legend_y),
+            limited_style,
+        ))?;
+    }
+
     // Right side: Error Rate (solid) + Threshold lines with their descriptions
     // Layout: [--- Error Rate] [--- 0.1% Payment] [--- 0.5% Core] [--- 1% APIs] [--- 2% Non-critical]
     let right_x = (width - 60) as i32;
@@ -974,11 +1154,11 @@ fn draw_legend(
         };
         root.draw(&PathElement::new(
             vec![(line_start, legend_y), (line_start + 8, legend_y)],
-            dash_style.clone(),
+            dash_style,
         ))?;
         root.draw(&PathElement::new(
             vec![(line_start + 12, legend_y), (line_start + 20, legend_y)],
-            dash_style.clone(),
+            dash_style,
         ))?;
         root.draw(&PathElement::new(
             vec![(line_start + 24, legend_y), (line_end, legend_y)],
@@ -1106,18 +1286,12 @@ fn format_rate_short(rate: f64) -> String {
         let k = rate / 1000.0;
         if k >= 100.0 {
             format!("{:.0}k", k)
-        } else if k >= 10.0 {
-            let s = format!("{:.1}", k);
-            format!("{}k", s.trim_end_matches('0').trim_end_matches('.'))
         } else {
             let s = format!("{:.1}", k);
             format!("{}k", s.trim_end_matches('0').trim_end_matches('.'))
         }
     } else if rate >= 100.0 {
         format!("{:.0}", rate)
-    } else if rate >= 10.0 {
-        let s = format!("{:.1}", rate);
-        s.trim_end_matches('0').trim_end_matches('.').to_string()
     } else {
         let s = format!("{:.1}", rate);
         s.trim_end_matches('0').trim_end_matches('.').to_string()
@@ -1133,12 +1307,19 @@ fn format_termination_status(url_result: &UrlBenchmarkResults) -> Option<String>
         StepStatus::Break => {
             let reason = match &last_analysis.break_reason {
                 BreakReason::ErrorRate(rate) => format!("Error Rate ({:.1}%)", rate),
+                BreakReason::ConnectionErrors(kind, rate) => {
+                    format!("{} ({:.1}%)", kind.break_description(), rate)
+                }
                 BreakReason::P99Latency(ms) => format!("P99 Latency ({:.0}ms)", ms),
                 BreakReason::ThroughputDegradation(pct) => {
                     format!("Throughput Degradation ({:.0}%)", pct)
                 }
                 BreakReason::Hung => "Server Hung".to_string(),
                 BreakReason::NoResponses => "No Responses".to_string(),
+                BreakReason::AbortedEarly => "Aborted Early".to_string(),
+                BreakReason::ClientLimited(_) => {
+                    return Some("CLIENT LIMITED: load generator saturated".to_string());
+                }
                 _ => "Threshold Exceeded".to_string(),
             };
             Some(format!("BREAK: {}", reason))
@@ -1147,7 +1328,7 @@ fn format_termination_status(url_result: &UrlBenchmarkResults) -> Option<String>
         StepStatus::Blocked => Some("BLOCKED".to_string()),
         StepStatus::Hung => Some("CONNECTION HUNG".to_string()),
         StepStatus::Gone => Some("NO RESPONSE".to_string()),
-        StepStatus::Ok | StepStatus::Warning => None,
+        StepStatus::Ok | StepStatus::Warning | StepStatus::Limited => None,
     }
 }
 
@@ -1167,9 +1348,10 @@ fn format_short_number(n: f64) -> String {
 /// - did_break: true if test terminated early, false if completed all steps
 fn calculate_dau_estimate(url_result: &UrlBenchmarkResults) -> (String, bool) {
     // Check if test ended with a break/failure status
-    let did_break = url_result.analyses.last().map_or(false, |a| {
-        !matches!(a.status, StepStatus::Ok | StepStatus::Warning)
-    });
+    let did_break = url_result
+        .analyses
+        .last()
+        .is_some_and(|a| !matches!(a.status, StepStatus::Ok | StepStatus::Warning));
 
     // Find max rate where status is Ok/Warning AND p99 < threshold
     let max_qualifying_rate: Option<u32> = url_result
@@ -1221,3 +1403,178 @@ fn format_business_scale_subtitle() -> String {
 
     format!("Classed by {}", parts.join(" | "))
 }
+
+/// Generate a PNG graph of trends across recorded runs
+/// One row per (scenario, URL) group: breaking point and recommended rate on the left,
+/// p99 latency at a fixed rate on the right
+pub fn generate_trend_graph(
+    runs: &[RunRecord],
+    output_path: &str,
+    at_rate: Option<u32>,
+) -> Result<()> {
+    let groups = group_runs(runs);
+    if groups.is_empty() {
+        return Ok(());
+    }
+
+    // Create parent directories if they don't exist
+    if let Some(parent) = std::path::Path::new(output_path).parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+
+    let width = 2400u32;
+    let panel_height = 600u32;
+    let header_height = 100u32;
+    let height = header_height + panel_height * groups.len() as u32;
+
+    let root = BitMapBackend::new(output_path, (width, height)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let title_style = TextStyle::from(("sans-serif", 48).into_font())
+        .color(&BLACK)
+        .pos(Pos::new(HPos::Center, VPos::Top));
+    root.draw(&Text::new(
+        "Benchmark Trends Across Runs",
+        ((width / 2) as i32, 24),
+        title_style,
+    ))?;
+
+    let (_, body) = root.split_vertically(header_height);
+    let rows = body.split_evenly((groups.len(), 1));
+
+    for (row, ((scenario, url), group)) in rows.iter().zip(groups.iter()) {
+        let rate = at_rate.or_else(|| common_tested_rate(group));
+        let (left, right) = row.split_horizontally(width / 2);
+
+        let labels: Vec<String> = group
+            .iter()
+            .map(|r| r.formatted_time().chars().skip(5).collect())
+            .collect();
+        let x_range = -0.5f64..(group.len() as f64 - 0.5);
+
+        // Left: breaking point and recommended rate
+        let breaks: Vec<(f64, f64)> = group
+            .iter()
+            .enumerate()
+            .filter_map(|(i, r)| r.breaking_point_rate.map(|b| (i as f64, b as f64)))
+            .collect();
+        let recommended: Vec<(f64, f64)> = group
+            .iter()
+            .enumerate()
+            .filter_map(|(i, r)| r.recommended_rate.map(|b| (i as f64, b as f64)))
+            .collect();
+        let rate_max = breaks
+            .iter()
+            .chain(recommended.iter())
+            .map(|(_, y)| *y)
+            .fold(1.0f64, f64::max);
+
+        let mut rate_chart = ChartBuilder::on(&left)
+            .caption(
+                format!("{} - {}", scenario, shorten_url(url)),
+                ("sans-serif", 28),
+            )
+            .margin(30)
+            .x_label_area_size(60)
+            .y_label_area_size(120)
+            .build_cartesian_2d(x_range.clone(), 0.0..(rate_max * 1.15))?;
+
+        rate_chart
+            .configure_mesh()
+            .light_line_style(LIGHT_GRID)
+            .x_labels(labels.len().min(12))
+            .x_label_formatter(&|x| trend_label(&labels, *x))
+            .y_desc("Requests/Second")
+            .y_label_formatter(&|y| format_rate_short(*y))
+            .label_style(("sans-serif", 20))
+            .axis_desc_style(("sans-serif", 24))
+            .draw()?;
+
+        rate_chart
+            .draw_series(LineSeries::new(breaks.clone(), ERROR_COLOR.stroke_width(4)))?
+            .label("Breaking point")
+            .legend(|(x, y)| {
+                PathElement::new(vec![(x, y), (x + 30, y)], ERROR_COLOR.stroke_width(4))
+            });
+        rate_chart.draw_series(
+            breaks
+                .iter()
+                .map(|&(x, y)| Circle::new((x, y), 7, ERROR_COLOR.filled())),
+        )?;
+        rate_chart
+            .draw_series(LineSeries::new(
+                recommended.clone(),
+                RECOMMENDED_COLOR.stroke_width(4),
+            ))?
+            .label("Recommended rate")
+            .legend(|(x, y)| {
+                PathElement::new(vec![(x, y), (x + 30, y)], RECOMMENDED_COLOR.stroke_width(4))
+            });
+        rate_chart.draw_series(
+            recommended
+                .iter()
+                .map(|&(x, y)| Circle::new((x, y), 7, RECOMMENDED_COLOR.filled())),
+        )?;
+        rate_chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(LIGHT_GRID)
+            .label_font(("sans-serif", 20))
+            .position(SeriesLabelPosition::LowerRight)
+            .draw()?;
+
+        // Right: p99 latency at the comparison rate
+        let p99s: Vec<(f64, f64)> = match rate {
+            Some(r) => group
+                .iter()
+                .enumerate()
+                .filter_map(|(i, run)| run.p99_at_rate(r).map(|p| (i as f64, p)))
+                .collect(),
+            None => Vec::new(),
+        };
+        let p99_max = p99s.iter().map(|(_, y)| *y).fold(1.0f64, f64::max);
+        let p99_caption = match rate {
+            Some(r) => format!("p99 latency @ {} req/s", r),
+            None => "p99 latency (no rate tested in every run)".to_string(),
+        };
+
+        let mut p99_chart = ChartBuilder::on(&right)
+            .caption(p99_caption, ("sans-serif", 28))
+            .margin(30)
+            .x_label_area_size(60)
+            .y_label_area_size(120)
+            .build_cartesian_2d(x_range, 0.0..(p99_max * 1.15))?;
+
+        p99_chart
+            .configure_mesh()
+            .light_line_style(LIGHT_GRID)
+            .x_labels(labels.len().min(12))
+            .x_label_formatter(&|x| trend_label(&labels, *x))
+            .y_desc("p99 Latency")
+            .y_label_formatter(&|y| format_latency_short(*y))
+            .label_style(("sans-serif", 20))
+            .axis_desc_style(("sans-serif", 24))
+            .draw()?;
+
+        p99_chart.draw_series(LineSeries::new(p99s.clone(), P99_COLOR.stroke_width(4)))?;
+        p99_chart.draw_series(
+            p99s.iter()
+                .map(|&(x, y)| Circle::new((x, y), 7, P99_COLOR.filled())),
+        )?;
+    }
+
+    root.present()?;
+
+    Ok(())
+}
+
+/// X-axis label for a trend chart: the run date, only at whole run indices
+fn trend_label(labels: &[String], x: f64) -> String {
+    let idx = x.round();
+    if (x - idx).abs() > 0.01 || idx < 0.0 {
+        return String::new();
+    }
+    labels.get(idx as usize).cloned().unwrap_or_default()
+}
@@ -0,0 +1,342 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::analysis::StepStatus;
+use crate::config::BenchmarkConfig;
+use crate::menu::url_to_safe_name;
+use crate::output::UrlBenchmarkResults;
+use crate::runner::BenchmarkResult;
+
+/// A single ramp step as stored in the history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepRecord {
+    #[serde(flatten)]
+    pub result: BenchmarkResult,
+    pub status: StepStatus,
+}
+
+/// One URL's benchmark run as stored in the history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// Unix timestamp (seconds) of when the run finished
+    pub timestamp: i64,
+    pub scenario: String,
+    pub url: String,
+    pub method: String,
+    pub duration_seconds: u32,
+    pub breaking_point_rate: Option<u32>,
+    pub break_reason: String,
+    pub last_stable_rate: Option<u32>,
+    pub recommended_rate: Option<u32>,
+    pub total_requests: u64,
+    pub total_duration_seconds: u64,
+    pub steps: Vec<StepRecord>,
+}
+
+impl RunRecord {
+    /// p99 latency of the step that ran at exactly `rate`, if that step was tested
+    pub fn p99_at_rate(&self, rate: u32) -> Option<f64> {
+        self.steps
+            .iter()
+            .find(|s| s.result.target_rate == rate)
+            .map(|s| s.result.p99_latency_ms)
+    }
+
+    /// Format the run timestamp in local time
+    pub fn formatted_time(&self) -> String {
+        use chrono::{Local, TimeZone};
+
+        match Local.timestamp_opt(self.timestamp, 0).single() {
+            Some(dt) => dt.format("%Y-%m-%d %H:%M").to_string(),
+            None => self.timestamp.to_string(),
+        }
+    }
+}
+
+/// Get the default history directory (platform data dir, e.g. ~/.local/share/ohabench/history)
+pub fn default_history_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("ohabench")
+        .join("history")
+}
+
+/// Resolve the history directory from an optional override
+pub fn resolve_history_dir(custom: Option<&str>) -> PathBuf {
+    custom
+        .map(PathBuf::from)
+        .unwrap_or_else(default_history_dir)
+}
+
+/// Scenario name used to key a run: explicit scenario, then report name, then the URL
+pub fn scenario_name(config: &BenchmarkConfig, url: &str) -> String {
+    config
+        .scenario
+        .clone()
+        .or_else(|| config.report_name.clone())
+        .unwrap_or_else(|| url_to_safe_name(url))
+}
+
+/// Append one record per URL to the history directory
+/// Returns the paths of the files written
+pub fn record_runs(
+    dir: &Path,
+    config: &BenchmarkConfig,
+    url_results: &[UrlBenchmarkResults],
+) -> Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create history directory {}", dir.display()))?;
+
+    let now = chrono::Local::now();
+    let mut written = Vec::new();
+
+    for url_result in url_results {
+        let scenario = scenario_name(config, &url_result.url);
+        let record = RunRecord {
+            timestamp: now.timestamp(),
+            scenario: scenario.clone(),
+            url: url_result.url.clone(),
+            method: config.method.to_string(),
+            duration_seconds: config.ramping.duration_seconds,
+            breaking_point_rate: url_result.summary.breaking_point_rate,
+            break_reason: url_result.summary.break_reason.to_string(),
+            last_stable_rate: url_result.summary.last_stable_rate,
+            recommended_rate: url_result.summary.recommended_rate,
+            total_requests: url_result.summary.total_requests,
+            total_duration_seconds: url_result.summary.total_duration_seconds,
+            steps: url_result
+                .results
+                .iter()
+                .zip(url_result.analyses.iter())
+                .map(|(result, analysis)| StepRecord {
+                    result: result.clone(),
+                    status: analysis.status,
+                })
+                .collect(),
+        };
+
+        let base = format!(
+            "{}_{}",
+            now.format("%Y%m%d-%H%M%S"),
+            url_to_safe_name(&scenario)
+        );
+        let mut path = dir.join(format!("{}.json", base));
+        let mut counter = 2;
+        while path.exists() {
+            path = dir.join(format!("{}.{}.json", base, counter));
+            counter += 1;
+        }
+
+        let json = serde_json::to_string_pretty(&record)?;
+        std::fs::write(&path, json)
+            .with_context(|| format!("Failed to write history record {}", path.display()))?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+/// Load all run records from the history directory, oldest first
+/// Optionally filtered by scenario name and/or URL
+pub fn load_runs(dir: &Path, scenario: Option<&str>, url: Option<&str>) -> Result<Vec<RunRecord>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut runs = Vec::new();
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read history directory {}", dir.display()))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+
+        // Skip unreadable or foreign files rather than failing the whole listing
+        let Ok(contents) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let Ok(record) = serde_json::from_str::<RunRecord>(&contents) else {
+            continue;
+        };
+
+        if scenario.is_some_and(|s| record.scenario != s) {
+            continue;
+        }
+        if url.is_some_and(|u| record.url != u) {
+            continue;
+        }
+
+        runs.push(record);
+    }
+
+    runs.sort_by_key(|r| r.timestamp);
+    Ok(runs)
+}
+
+/// Group runs by (scenario, URL), preserving chronological order within each group
+pub fn group_runs(runs: &[RunRecord]) -> Vec<((String, String), Vec<&RunRecord>)> {
+    let mut groups: Vec<((String, String), Vec<&RunRecord>)> = Vec::new();
+
+    for run in runs {
+        let key = (run.scenario.clone(), run.url.clone());
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, group)) => group.push(run),
+            None => groups.push((key, vec![run])),
+        }
+    }
+
+    groups
+}
+
+/// Find the highest target rate that completed without breaking in every given run
+/// Used as the default comparison rate for p99 trends
+pub fn common_tested_rate(runs: &[&RunRecord]) -> Option<u32> {
+    let is_stable_at = |run: &RunRecord, rate: u32| {
+        run.steps.iter().any(|s| {
+            s.result.target_rate == rate && matches!(s.status, StepStatus::Ok | StepStatus::Warning)
+        })
+    };
+
+    let first = runs.first()?;
+    first
+        .steps
+        .iter()
+        .map(|s| s.result.target_rate)
+        .filter(|rate| runs.iter().all(|r| is_stable_at(r, *rate)))
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{analyze_result, generate_summary};
+    use crate::status::StatusClasses;
+
+    /// A run where each step is (target rate, status)
+    fn run(timestamp: i64, steps: &[(u32, StepStatus)]) -> RunRecord {
+        RunRecord {
+            timestamp,
+            scenario: "api".to_string(),
+            url: "https://api.example.com".to_string(),
+            method: "GET".to_string(),
+            duration_seconds: 10,
+            breaking_point_rate: None,
+            break_reason: String::new(),
+            last_stable_rate: None,
+            recommended_rate: None,
+            total_requests: 0,
+            total_duration_seconds: 0,
+            steps: steps
+                .iter()
+                .map(|&(rate, status)| StepRecord {
+                    result: BenchmarkResult {
+                        target_rate: rate,
+                        p99_latency_ms: rate as f64 / 10.0,
+                        ..Default::default()
+                    },
+                    status,
+                })
+                .collect(),
+        }
+    }
+
+    fn url_results(url: &str, rates: &[u32]) -> UrlBenchmarkResults {
+        let config = BenchmarkConfig::default();
+        let results: Vec<BenchmarkResult> = rates
+            .iter()
+            .map(|&rate| BenchmarkResult {
+                target_rate: rate,
+                actual_rate: rate as f64,
+                total_requests: rate as u64 * 10,
+                ..Default::default()
+            })
+            .collect();
+        let analyses: Vec<_> = results
+            .iter()
+            .map(|r| analyze_result(r, &config.thresholds, &StatusClasses::default()))
+            .collect();
+        UrlBenchmarkResults {
+            url: url.to_string(),
+            summary: generate_summary(&results, &analyses),
+            results,
+            analyses,
+        }
+    }
+
+    #[test]
+    fn records_and_loads_runs() {
+        let dir = std::env::temp_dir().join(format!("ohabench-history-{}", std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        let config = BenchmarkConfig {
+            scenario: Some("checkout".to_string()),
+            ..Default::default()
+        };
+        let a = url_results("https://a.example.com", &[10, 20]);
+        let b = url_results("https://b.example.com", &[10]);
+
+        let first = record_runs(&dir, &config, &[a, b]).unwrap();
+        // Recording again in the same second doesn't overwrite the earlier file
+        let again = url_results("https://a.example.com", &[10, 20]);
+        let second = record_runs(&dir, &config, &[again]).unwrap();
+        assert_eq!(first.len(), 2);
+        assert!(second.iter().all(|p| !first.contains(p)));
+        std::fs::write(dir.join("notes.json"), "not a run").unwrap();
+
+        let runs = load_runs(&dir, Some("checkout"), None).unwrap();
+        assert_eq!(runs.len(), 3);
+        assert_eq!(runs[0].steps.len(), 2);
+        assert_eq!(runs[0].steps[1].result.target_rate, 20);
+        assert_eq!(runs[0].steps[1].status, StepStatus::Ok);
+
+        let only_b = load_runs(&dir, None, Some("https://b.example.com")).unwrap();
+        assert_eq!(only_b.len(), 1);
+        assert!(load_runs(&dir, Some("other"), None).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn groups_runs_by_scenario_and_url() {
+        let mut other = run(2, &[]);
+        other.url = "https://other.example.com".to_string();
+        let runs = vec![run(1, &[]), other, run(3, &[])];
+
+        let groups = group_runs(&runs);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0 .1, "https://api.example.com");
+        let timestamps: Vec<i64> = groups[0].1.iter().map(|r| r.timestamp).collect();
+        assert_eq!(timestamps, vec![1, 3]);
+        assert_eq!(groups[1].1.len(), 1);
+    }
+
+    #[test]
+    fn finds_the_highest_rate_stable_in_every_run() {
+        let a = run(
+            1,
+            &[
+                (100, StepStatus::Ok),
+                (200, StepStatus::Warning),
+                (300, StepStatus::Break),
+            ],
+        );
+        let b = run(
+            2,
+            &[
+                (100, StepStatus::Ok),
+                (200, StepStatus::Ok),
+                (300, StepStatus::Ok),
+            ],
+        );
+        let c = run(3, &[(100, StepStatus::Ok), (200, StepStatus::RateLimited)]);
+
+        assert_eq!(common_tested_rate(&[&a, &b]), Some(200));
+        assert_eq!(common_tested_rate(&[&b]), Some(300));
+        assert_eq!(common_tested_rate(&[&a, &b, &c]), Some(100));
+        assert_eq!(common_tested_rate(&[]), None);
+        assert_eq!(b.p99_at_rate(300), Some(30.0));
+    }
+}
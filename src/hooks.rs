@@ -0,0 +1,233 @@
+use anyhow::{Context, Result};
+use console::style;
+use serde::Serialize;
+use std::io::Write;
+use std::process::Stdio;
+
+use crate::analysis::{BenchmarkSummary, BreakReason, StepStatus};
+use crate::runner::{shell_command, BenchmarkResult};
+use crate::secret;
+
+/// Commands run at fixed points of a run, e.g. to reset caches or scale a deployment
+#[derive(Debug, Clone, Default)]
+pub struct HookConfig {
+    pub before_run: Option<String>,
+    pub before_step: Option<String>,
+    pub after_step: Option<String>,
+    pub on_break: Option<String>,
+    pub after_run: Option<String>,
+    /// A failing after-step hook stops the ramp
+    pub veto: bool,
+}
+
+impl HookConfig {
+    /// Whether any hook is configured
+    pub fn is_empty(&self) -> bool {
+        self.command(Hook::BeforeRun).is_none()
+            && self.command(Hook::BeforeStep).is_none()
+            && self.command(Hook::AfterStep).is_none()
+            && self.command(Hook::OnBreak).is_none()
+            && self.command(Hook::AfterRun).is_none()
+    }
+
+    fn command(&self, hook: Hook) -> Option<&str> {
+        match hook {
+            Hook::BeforeRun => self.before_run.as_deref(),
+            Hook::BeforeStep => self.before_step.as_deref(),
+            Hook::AfterStep => self.after_step.as_deref(),
+            Hook::OnBreak => self.on_break.as_deref(),
+            Hook::AfterRun => self.after_run.as_deref(),
+        }
+    }
+
+    /// Configured hooks by name, for summaries
+    pub fn names(&self) -> Vec<&'static str> {
+        Hook::ALL
+            .iter()
+            .filter(|hook| self.command(**hook).is_some())
+            .map(|hook| hook.name())
+            .collect()
+    }
+}
+
+/// Points of a run a hook can run at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Hook {
+    BeforeRun,
+    BeforeStep,
+    AfterStep,
+    OnBreak,
+    AfterRun,
+}
+
+impl Hook {
+    const ALL: [Hook; 5] = [
+        Hook::BeforeRun,
+        Hook::BeforeStep,
+        Hook::AfterStep,
+        Hook::OnBreak,
+        Hook::AfterRun,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Hook::BeforeRun => "before-run",
+            Hook::BeforeStep => "before-step",
+            Hook::AfterStep => "after-step",
+            Hook::OnBreak => "on-break",
+            Hook::AfterRun => "after-run",
+        }
+    }
+}
+
+/// What a hook is told, as JSON on stdin (the main fields are also environment variables)
+#[derive(Debug, Serialize)]
+pub struct HookEvent<'a> {
+    pub event: Hook,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<&'a str>,
+    /// 1-based step number and the number of steps in the ramp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub steps: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<StepStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub break_reason: Option<&'a BreakReason>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<&'a BenchmarkResult>,
+    /// Per-URL summaries (after-run)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub summaries: Vec<UrlSummary<'a>>,
+    /// Saved report and graph (after-run)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UrlSummary<'a> {
+    pub url: &'a str,
+    pub summary: &'a BenchmarkSummary,
+}
+
+impl<'a> HookEvent<'a> {
+    pub fn new(event: Hook) -> Self {
+        Self {
+            event,
+            url: None,
+            step: None,
+            steps: None,
+            rate: None,
+            status: None,
+            break_reason: None,
+            result: None,
+            summaries: Vec::new(),
+            report: None,
+            graph: None,
+        }
+    }
+
+    /// Environment variables for the hook command
+    fn env(&self) -> Vec<(&'static str, String)> {
+        let mut env = vec![("OHABENCH_EVENT", self.event.name().to_string())];
+        if let Some(url) = self.url {
+            env.push(("OHABENCH_URL", url.to_string()));
+        }
+        if let Some(step) = self.step {
+            env.push(("OHABENCH_STEP", step.to_string()));
+        }
+        if let Some(steps) = self.steps {
+            env.push(("OHABENCH_STEPS", steps.to_string()));
+        }
+        if let Some(rate) = self.rate {
+            env.push(("OHABENCH_RATE", rate.to_string()));
+        }
+        if let Some(status) = self.status {
+            env.push(("OHABENCH_STATUS", status.to_string()));
+        }
+        if let Some(reason) = self.break_reason {
+            env.push(("OHABENCH_BREAK_REASON", reason.to_string()));
+        }
+        if let Some(result) = self.result {
+            env.push(("OHABENCH_ACTUAL_RATE", format!("{:.1}", result.actual_rate)));
+            env.push(("OHABENCH_ERROR_RATE", format!("{:.2}", result.error_rate)));
+            env.push(("OHABENCH_P99_MS", format!("{:.1}", result.p99_latency_ms)));
+        }
+        if let Some(report) = self.report {
+            env.push(("OHABENCH_REPORT", report.to_string()));
+        }
+        if let Some(graph) = self.graph {
+            env.push(("OHABENCH_GRAPH", graph.to_string()));
+        }
+        env
+    }
+}
+
+/// Run the hook for an event, if one is configured
+/// Returns false when the hook failed (it's reported); true when it succeeded or isn't set
+pub fn run(hooks: &HookConfig, event: &HookEvent) -> bool {
+    let Some(command) = hooks.command(event.event) else {
+        return true;
+    };
+    match execute(command, event) {
+        Ok(None) => true,
+        Ok(Some(failure)) => {
+            eprintln!(
+                "{} {} hook {}",
+                style("✗").red(),
+                event.event.name(),
+                secret::redact(&failure)
+            );
+            false
+        }
+        Err(e) => {
+            eprintln!(
+                "{} {} hook: {}",
+                style("✗").red(),
+                event.event.name(),
+                secret::redact(&format!("{:#}", e))
+            );
+            false
+        }
+    }
+}
+
+/// Run a hook command, returning a description of the failure if it exited unsuccessfully
+/// Output is captured so it doesn't break up the results table
+fn execute(command: &str, event: &HookEvent) -> Result<Option<String>> {
+    let mut cmd = shell_command(command);
+    cmd.envs(event.env());
+    cmd.stdin(Stdio::piped());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+
+    let mut child = cmd
+        .spawn()
+        .with_context(|| format!("Failed to run '{}'", command))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that doesn't read stdin closes it early; that's fine
+        let json = serde_json::to_string(event)?;
+        stdin.write_all(json.as_bytes()).ok();
+    }
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        return Ok(None);
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let last_line = stderr.lines().rev().find(|l| !l.trim().is_empty());
+    let status = match output.status.code() {
+        Some(code) => format!("exited with {}", code),
+        None => "was terminated".to_string(),
+    };
+    Ok(Some(match last_line {
+        Some(line) => format!("{}: {}", status, line.trim()),
+        None => status,
+    }))
+}
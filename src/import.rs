@@ -0,0 +1,567 @@
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use regex::Regex;
+use serde_json::Value;
+
+use crate::cli::{Args, AuthType, HttpMethod};
+use crate::config::{AuthConfig, BenchmarkConfig};
+
+/// Headers that describe the connection rather than the request and are never imported
+const SKIPPED_HEADERS: &[&str] = &[
+    "host",
+    "content-length",
+    "connection",
+    "accept-encoding",
+    "user-agent",
+];
+
+/// A request definition produced by an importer
+#[derive(Debug, Clone)]
+pub struct ImportedRequest {
+    /// Short label for menus ("POST https://...", or the OpenAPI operationId)
+    pub name: String,
+    pub url: String,
+    pub method: HttpMethod,
+    pub headers: Vec<String>,
+    pub body: Option<String>,
+    pub user_agent: Option<String>,
+    pub auth: AuthConfig,
+}
+
+impl ImportedRequest {
+    fn new(method: HttpMethod, url: String) -> Self {
+        Self {
+            name: format!("{} {}", method, url),
+            url,
+            method,
+            headers: Vec::new(),
+            body: None,
+            user_agent: None,
+            auth: AuthConfig::default(),
+        }
+    }
+
+    /// Add a header, lifting Authorization and User-Agent into their dedicated settings
+    fn add_header(&mut self, name: &str, value: &str) {
+        let lower = name.trim().to_lowercase();
+        if lower.starts_with(':') {
+            // HTTP/2 pseudo-headers from browser exports
+            return;
+        }
+        if lower == "user-agent" {
+            self.user_agent = Some(value.trim().to_string());
+            return;
+        }
+        if SKIPPED_HEADERS.contains(&lower.as_str()) {
+            return;
+        }
+        if lower == "authorization" {
+            if let Some(auth) = parse_authorization(value.trim()) {
+                self.auth = auth;
+                return;
+            }
+        }
+        self.headers
+            .push(format!("{}: {}", name.trim(), value.trim()));
+    }
+
+    /// Key used to group requests that can share one BenchmarkConfig
+    fn group_key(&self) -> String {
+        format!(
+            "{}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}",
+            self.method,
+            self.headers,
+            self.body,
+            self.user_agent,
+            self.auth.auth_type,
+            self.auth.username,
+            self.auth.token,
+            self.auth.custom_header
+        )
+    }
+}
+
+/// Parse an HTTP method name
+pub fn parse_method(method: &str) -> Option<HttpMethod> {
+    match method.to_uppercase().as_str() {
+        "GET" => Some(HttpMethod::Get),
+        "POST" => Some(HttpMethod::Post),
+        "PUT" => Some(HttpMethod::Put),
+        "PATCH" => Some(HttpMethod::Patch),
+        "DELETE" => Some(HttpMethod::Delete),
+        "HEAD" => Some(HttpMethod::Head),
+        _ => None,
+    }
+}
+
+/// Map an Authorization header value onto an AuthConfig where possible
+fn parse_authorization(value: &str) -> Option<AuthConfig> {
+    let (scheme, credentials) = value.split_once(' ')?;
+    match scheme.to_lowercase().as_str() {
+        "bearer" => Some(AuthConfig {
+            auth_type: AuthType::Bearer,
+            token: Some(credentials.trim().to_string()),
+            ..Default::default()
+        }),
+        "basic" => {
+            let decoded = STANDARD.decode(credentials.trim()).ok()?;
+            let decoded = String::from_utf8(decoded).ok()?;
+            let (user, pass) = decoded.split_once(':')?;
+            Some(AuthConfig {
+                auth_type: AuthType::Basic,
+                username: Some(user.to_string()),
+                password: Some(pass.to_string()),
+                ..Default::default()
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Import a request from a curl command line
+pub fn import_curl(command: &str) -> Result<Vec<ImportedRequest>> {
+    let args = split_shell_words(command)?;
+    let mut args = args.into_iter().peekable();
+
+    // Allow the command with or without the leading "curl"
+    if args.peek().is_some_and(|a| a == "curl") {
+        args.next();
+    }
+
+    let mut url: Option<String> = None;
+    let mut method: Option<String> = None;
+    let mut headers: Vec<(String, String)> = Vec::new();
+    let mut data: Vec<String> = Vec::new();
+    let mut user: Option<String> = None;
+    let mut user_agent: Option<String> = None;
+    let mut head = false;
+
+    while let Some(arg) = args.next() {
+        // Support --opt=value as well as --opt value
+        let (flag, inline) = match arg.split_once('=') {
+            Some((f, v)) if f.starts_with("--") => (f.to_string(), Some(v.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| -> Result<String> {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .with_context(|| format!("curl option {} is missing a value", name))
+        };
+
+        match flag.as_str() {
+            "-X" | "--request" => method = Some(value(&flag)?),
+            "-H" | "--header" => {
+                let header = value(&flag)?;
+                if let Some((name, val)) = header.split_once(':') {
+                    headers.push((name.to_string(), val.to_string()));
+                }
+            }
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii"
+            | "--data-urlencode" => data.push(value(&flag)?),
+            "--json" => {
+                data.push(value(&flag)?);
+                headers.push(("Content-Type".into(), "application/json".into()));
+                headers.push(("Accept".into(), "application/json".into()));
+            }
+            "-u" | "--user" => user = Some(value(&flag)?),
+            "-A" | "--user-agent" => user_agent = Some(value(&flag)?),
+            "-b" | "--cookie" => headers.push(("Cookie".into(), value(&flag)?)),
+            "-e" | "--referer" => headers.push(("Referer".into(), value(&flag)?)),
+            "--url" => url = Some(value(&flag)?),
+            "-I" | "--head" => head = true,
+            // Options with a value that don't affect the request definition
+            "-o" | "--output" | "-m" | "--max-time" | "--connect-timeout" | "-w"
+            | "--write-out" | "-x" | "--proxy" | "--retry" => {
+                value(&flag)?;
+            }
+            f if f.starts_with('-') => {
+                // Flags like -s, -L, -k, --compressed carry no request data
+            }
+            _ => {
+                if url.is_none() {
+                    url = Some(arg);
+                }
+            }
+        }
+    }
+
+    let url = url.context("No URL found in curl command")?;
+    let method_name = match (method, head, data.is_empty()) {
+        (Some(m), _, _) => m,
+        (None, true, _) => "HEAD".to_string(),
+        (None, false, false) => "POST".to_string(),
+        (None, false, true) => "GET".to_string(),
+    };
+    let method = parse_method(&method_name)
+        .with_context(|| format!("Unsupported HTTP method {}", method_name))?;
+
+    let mut request = ImportedRequest::new(method, url);
+    for (name, value) in &headers {
+        request.add_header(name, value);
+    }
+    if let Some(ua) = user_agent {
+        request.user_agent = Some(ua);
+    }
+    if let Some(user) = user {
+        let (username, password) = user.split_once(':').unwrap_or((&user, ""));
+        request.auth = AuthConfig {
+            auth_type: AuthType::Basic,
+            username: Some(username.to_string()),
+            password: Some(password.to_string()),
+            ..Default::default()
+        };
+    }
+    if !data.is_empty() {
+        request.body = Some(data.join("&"));
+        // curl sends form encoding for -d unless a Content-Type was given
+        if !request
+            .headers
+            .iter()
+            .any(|h| h.to_lowercase().starts_with("content-type:"))
+        {
+            request
+                .headers
+                .push("Content-Type: application/x-www-form-urlencoded".to_string());
+        }
+    }
+
+    Ok(vec![request])
+}
+
+/// Split a command line into words, honoring quotes and backslash escapes
+fn split_shell_words(input: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(ch) => current.push(ch),
+                        None => bail!("Unterminated single quote in curl command"),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(ch @ ('"' | '\\' | '$' | '`')) => current.push(ch),
+                            Some('\n') => {}
+                            Some(ch) => {
+                                current.push('\\');
+                                current.push(ch);
+                            }
+                            None => bail!("Unterminated double quote in curl command"),
+                        },
+                        Some(ch) => current.push(ch),
+                        None => bail!("Unterminated double quote in curl command"),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                // Line continuation
+                Some('\n') | Some('\r') => {}
+                Some(ch) => {
+                    in_word = true;
+                    current.push(ch);
+                }
+                None => {}
+            },
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+
+    if in_word {
+        words.push(current);
+    }
+
+    Ok(words)
+}
+
+/// Import requests from a browser HAR export
+pub fn import_har(path: &str) -> Result<Vec<ImportedRequest>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read HAR file {}", path))?;
+    let har: Value =
+        serde_json::from_str(&contents).with_context(|| format!("Invalid HAR file {}", path))?;
+
+    let entries = har
+        .pointer("/log/entries")
+        .and_then(Value::as_array)
+        .context("HAR file has no log.entries")?;
+
+    let mut requests: Vec<ImportedRequest> = Vec::new();
+    for entry in entries {
+        let Some(req) = entry.get("request") else {
+            continue;
+        };
+        let method_name = req.get("method").and_then(Value::as_str).unwrap_or("GET");
+        let Some(method) = parse_method(method_name) else {
+            continue;
+        };
+        let Some(url) = req.get("url").and_then(Value::as_str) else {
+            continue;
+        };
+
+        let mut request = ImportedRequest::new(method, url.to_string());
+        for header in req
+            .get("headers")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            if let (Some(name), Some(value)) = (
+                header.get("name").and_then(Value::as_str),
+                header.get("value").and_then(Value::as_str),
+            ) {
+                request.add_header(name, value);
+            }
+        }
+
+        if let Some(text) = req.pointer("/postData/text").and_then(Value::as_str) {
+            if !text.is_empty() {
+                request.body = Some(text.to_string());
+            }
+        }
+
+        // Browsers record duplicates (retries, polling); keep the first of each
+        if !requests
+            .iter()
+            .any(|r| r.name == request.name && r.body == request.body)
+        {
+            requests.push(request);
+        }
+    }
+
+    Ok(requests)
+}
+
+/// Import operations from an OpenAPI 3 / Swagger 2 spec (JSON or YAML)
+/// Path parameters use the spec's example/default, else a template that varies per request
+pub fn import_openapi(path: &str) -> Result<Vec<ImportedRequest>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read OpenAPI spec {}", path))?;
+    let spec: Value = if path.ends_with(".json") {
+        serde_json::from_str(&contents).with_context(|| format!("Invalid OpenAPI JSON {}", path))?
+    } else {
+        serde_yaml::from_str(&contents).with_context(|| format!("Invalid OpenAPI spec {}", path))?
+    };
+
+    let base_url = openapi_base_url(&spec);
+    let paths = spec
+        .get("paths")
+        .and_then(Value::as_object)
+        .context("OpenAPI spec has no paths")?;
+
+    let mut requests = Vec::new();
+    for (route, item) in paths {
+        let Some(item) = item.as_object() else {
+            continue;
+        };
+        let shared_params = item.get("parameters");
+
+        for (method_name, operation) in item {
+            let Some(method) = parse_method(method_name) else {
+                continue;
+            };
+
+            let params: Vec<&Value> = shared_params
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .chain(
+                    operation
+                        .get("parameters")
+                        .and_then(Value::as_array)
+                        .into_iter()
+                        .flatten(),
+                )
+                .collect();
+
+            let mut url_path = route.clone();
+            for param in &params {
+                if param.get("in").and_then(Value::as_str) != Some("path") {
+                    continue;
+                }
+                let Some(name) = param.get("name").and_then(Value::as_str) else {
+                    continue;
+                };
+                url_path = url_path.replace(&format!("{{{}}}", name), &path_param_value(param));
+            }
+
+            let mut request = ImportedRequest::new(method, format!("{}{}", base_url, url_path));
+            if let Some(op_id) = operation.get("operationId").and_then(Value::as_str) {
+                request.name = format!("{} ({} {})", op_id, method, route);
+            }
+
+            if let Some(example) = openapi_json_example(&spec, operation) {
+                request.body = Some(example);
+                request
+                    .headers
+                    .push("Content-Type: application/json".to_string());
+            }
+
+            requests.push(request);
+        }
+    }
+
+    Ok(requests)
+}
+
+/// Base URL from `servers[0].url` (OpenAPI 3) or scheme/host/basePath (Swagger 2)
+fn openapi_base_url(spec: &Value) -> String {
+    if let Some(url) = spec.pointer("/servers/0/url").and_then(Value::as_str) {
+        return url.trim_end_matches('/').to_string();
+    }
+    let host = spec
+        .get("host")
+        .and_then(Value::as_str)
+        .unwrap_or("localhost");
+    let scheme = spec
+        .pointer("/schemes/0")
+        .and_then(Value::as_str)
+        .unwrap_or("https");
+    let base_path = spec.get("basePath").and_then(Value::as_str).unwrap_or("");
+    format!("{}://{}{}", scheme, host, base_path.trim_end_matches('/'))
+}
+
+/// Value for a path parameter: example, default, or a template for the parameter type
+fn path_param_value(param: &Value) -> String {
+    let schema = param.get("schema").unwrap_or(param);
+    let fixed = param
+        .get("example")
+        .or_else(|| schema.get("example"))
+        .or_else(|| schema.get("default"));
+    if let Some(value) = fixed {
+        return match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+    }
+
+    match schema.get("type").and_then(Value::as_str) {
+        Some("integer") | Some("number") => "{{range:1:1000}}".to_string(),
+        _ if schema.get("format").and_then(Value::as_str) == Some("uuid") => "{{uuid}}".to_string(),
+        _ => "{{random_string:8}}".to_string(),
+    }
+}
+
+/// JSON request body example for an operation, if the spec provides one
+fn openapi_json_example(spec: &Value, operation: &Value) -> Option<String> {
+    let media = operation.pointer("/requestBody/content/application~1json")?;
+    if let Some(example) = media.get("example") {
+        return Some(example.to_string());
+    }
+    if let Some(example) = media
+        .get("examples")
+        .and_then(Value::as_object)
+        .and_then(|e| e.values().next())
+        .and_then(|e| e.get("value"))
+    {
+        return Some(example.to_string());
+    }
+
+    // Follow a local $ref to a schema example
+    let mut schema = media.get("schema")?;
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        schema = spec.pointer(reference.strip_prefix('#')?)?;
+    }
+    schema.get("example").map(Value::to_string)
+}
+
+/// Load every request named by the `--import-*` options, after `--import-filter`
+pub fn import_from_args(args: &Args) -> Result<Vec<ImportedRequest>> {
+    let mut requests = Vec::new();
+    if let Some(ref command) = args.import_curl {
+        requests.extend(import_curl(command)?);
+    }
+    if let Some(ref path) = args.import_har {
+        requests.extend(import_har(path)?);
+    }
+    if let Some(ref path) = args.import_openapi {
+        requests.extend(import_openapi(path)?);
+    }
+
+    let requests = filter_requests(requests, args.import_filter.as_deref())?;
+    if requests.is_empty() {
+        bail!("No requests were imported (check the file or --import-filter)");
+    }
+    Ok(requests)
+}
+
+/// Keep only requests whose name or URL matches the filter regex
+pub fn filter_requests(
+    requests: Vec<ImportedRequest>,
+    filter: Option<&str>,
+) -> Result<Vec<ImportedRequest>> {
+    let Some(filter) = filter else {
+        return Ok(requests);
+    };
+    let re = Regex::new(filter).with_context(|| format!("Invalid --import-filter {}", filter))?;
+    Ok(requests
+        .into_iter()
+        .filter(|r| re.is_match(&r.name) || re.is_match(&r.url))
+        .collect())
+}
+
+/// Apply a single imported request to a config
+pub fn apply_request(config: &mut BenchmarkConfig, request: &ImportedRequest) {
+    config.urls = vec![request.url.clone()];
+    config.method = request.method;
+    config.headers = request.headers.clone();
+    config.body = request.body.clone();
+    config.body_file = None;
+    if let Some(ref ua) = request.user_agent {
+        config.user_agent = ua.clone();
+    }
+    if request.auth.auth_type != AuthType::None {
+        config.auth = request.auth.clone();
+    }
+}
+
+/// Build one config per group of imported requests that share method, headers, body and auth
+/// Requests in the same group are benchmarked as multiple URLs of one config
+pub fn configs_from_imports(
+    base: &BenchmarkConfig,
+    requests: &[ImportedRequest],
+) -> Vec<BenchmarkConfig> {
+    let mut groups: Vec<(String, BenchmarkConfig)> = Vec::new();
+
+    for request in requests {
+        let key = request.group_key();
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, config)) => {
+                if !config.urls.contains(&request.url) {
+                    config.urls.push(request.url.clone());
+                }
+            }
+            None => {
+                let mut config = base.clone();
+                apply_request(&mut config, request);
+                // Keep headers given on the command line in addition to imported ones
+                config.headers.extend(base.headers.iter().cloned());
+                groups.push((key, config));
+            }
+        }
+    }
+
+    groups.into_iter().map(|(_, c)| c).collect()
+}
@@ -0,0 +1,148 @@
+use anyhow::{bail, Context, Result};
+use std::time::Duration;
+
+use crate::runner::BenchmarkResult;
+use crate::tls::{self, TlsConfig};
+
+/// Timeout for the login request
+const LOGIN_TIMEOUT_SECONDS: u64 = 30;
+
+/// Default header template for a token captured from the login response
+pub const DEFAULT_LOGIN_HEADER: &str = "Authorization: Bearer {token}";
+
+/// Login pre-step run before benchmarking (and again when the session expires)
+#[derive(Debug, Clone)]
+pub struct LoginConfig {
+    pub url: String,
+    /// Credentials sent as the login request body
+    pub body: String,
+    /// Content-Type of the body (JSON if the body looks like JSON, else form-encoded)
+    pub content_type: Option<String>,
+    /// Dot path of a token in the JSON response (e.g. `data.access_token`); cookies otherwise
+    pub token_path: Option<String>,
+    /// Header template for the token, with `{token}` replaced
+    pub header: String,
+}
+
+/// Session captured by logging in: the headers to send with every request
+#[derive(Debug)]
+pub struct LoginSession {
+    config: LoginConfig,
+    headers: Vec<String>,
+    /// HTTP agent for the login request (honours the TLS options)
+    agent: ureq::Agent,
+}
+
+impl LoginSession {
+    /// Log in and capture the session
+    pub fn new(config: &LoginConfig, tls: &TlsConfig) -> Result<Self> {
+        // Redirects aren't followed so cookies set on a 302 after login are captured
+        let agent = tls::agent_builder(tls)?
+            .redirects(0)
+            .timeout(Duration::from_secs(LOGIN_TIMEOUT_SECONDS))
+            .build();
+        let headers = login(&agent, config)?;
+        Ok(Self {
+            config: config.clone(),
+            headers,
+            agent,
+        })
+    }
+
+    /// Headers carrying the session (Cookie, or the token header)
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    /// Log in again, replacing the session
+    pub fn relogin(&mut self) -> Result<()> {
+        self.headers = login(&self.agent, &self.config)?;
+        Ok(())
+    }
+
+    /// Whether a step's responses suggest the session has expired
+    /// 401s mean the session was rejected; redirects (oha doesn't follow them while a login
+    /// is configured) usually mean a bounce to the login page
+    pub fn expired(result: &BenchmarkResult) -> bool {
+        result.redirects > 0
+            || result
+                .error_status_codes
+                .iter()
+                .any(|&(code, _)| code == 401)
+    }
+}
+
+/// POST the credentials and turn the response into session headers
+fn login(agent: &ureq::Agent, config: &LoginConfig) -> Result<Vec<String>> {
+    let content_type = config.content_type.clone().unwrap_or_else(|| {
+        if config.body.trim_start().starts_with('{') {
+            "application/json".to_string()
+        } else {
+            "application/x-www-form-urlencoded".to_string()
+        }
+    });
+
+    let response = match agent
+        .post(&config.url)
+        .set("Content-Type", &content_type)
+        .send_string(&config.body)
+    {
+        Ok(r) => r,
+        Err(ureq::Error::Status(code, r)) if (300..400).contains(&code) => r,
+        Err(ureq::Error::Status(code, _)) => {
+            bail!("Login to {} failed with HTTP {}", config.url, code)
+        }
+        Err(e) => return Err(e).context("Login request failed"),
+    };
+
+    let headers = match config.token_path {
+        Some(ref path) => {
+            let body = response.into_string()?;
+            let json: serde_json::Value = serde_json::from_str(&body)
+                .with_context(|| format!("Login response from {} is not JSON", config.url))?;
+            let token = json_path(&json, path)
+                .with_context(|| format!("Login response has no value at '{}'", path))?;
+            crate::secret::register(&token);
+            vec![config.header.replace("{token}", &token)]
+        }
+        None => {
+            let cookies: Vec<String> = response
+                .all("set-cookie")
+                .iter()
+                .filter_map(|c| c.split(';').next())
+                .map(|c| c.trim().to_string())
+                .filter(|c| !c.is_empty())
+                .collect();
+            if cookies.is_empty() {
+                bail!(
+                    "Login to {} set no cookies (use --login-token-path for token logins)",
+                    config.url
+                );
+            }
+            for cookie in &cookies {
+                if let Some((_, value)) = cookie.split_once('=') {
+                    crate::secret::register(value);
+                }
+            }
+            vec![format!("Cookie: {}", cookies.join("; "))]
+        }
+    };
+
+    Ok(headers)
+}
+
+/// Look up a dot-separated path (`data.token`, `items.0.id`) in a JSON value
+pub fn json_path(value: &serde_json::Value, path: &str) -> Option<String> {
+    let mut current = value;
+    for key in path.split('.').filter(|k| !k.is_empty()) {
+        current = match current {
+            serde_json::Value::Array(items) => items.get(key.parse::<usize>().ok()?)?,
+            other => other.get(key)?,
+        };
+    }
+    match current {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
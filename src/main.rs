@@ -1,65 +1,135 @@
 mod analysis;
 mod auth;
 mod cli;
+mod client;
 mod config;
+mod dashboard;
+mod gate;
 mod graph;
+mod history;
+mod hooks;
+mod import;
+mod login;
 mod menu;
+mod metrics;
+mod notify;
 mod output;
+mod ratelimit;
+mod request;
 mod runner;
+mod secret;
+mod settings;
+mod signing;
+mod status;
+mod template;
+mod tls;
 mod user_agent;
+mod validate;
+mod worker;
 
 use anyhow::{bail, Result};
 use clap::Parser;
 use console::style;
 use dialoguer::Select;
 use indicatif::{ProgressBar, ProgressStyle};
+use std::path::Path;
 use std::time::Duration;
 
 use analysis::{analyze_result, generate_summary, AnalysisResult, StepStatus};
-use cli::Args;
+use cli::{Args, Command};
 use config::{get_unique_report_paths, BenchmarkConfig};
-use menu::{config_from_args, run_interactive_menu, SessionState};
+use dashboard::Dashboard;
+use hooks::{Hook, HookEvent};
+use menu::{configs_from_args, run_interactive_menu, SessionState};
+use notify::Notification;
 use output::{
-    generate_report_text, print_config_summary, print_header, print_legend, print_result_row,
-    print_summary, print_table_header, print_url_header, save_report, UrlBenchmarkResults,
+    generate_report_text, print_config_summary, print_header, print_history, print_legend,
+    print_result_row, print_summary, print_table_header, print_trend, print_url_header,
+    print_verdict, save_report, UrlBenchmarkResults,
 };
+use request::RequestPlan;
 use runner::{check_oha_installed, run_benchmark, run_warmup, BenchmarkResult};
 
 fn main() {
-    if let Err(e) = run() {
-        eprintln!("{} {:#}", style("Error:").red().bold(), e);
-        std::process::exit(1);
+    match run() {
+        Ok(gate::EXIT_PASS) => {}
+        Ok(code) => std::process::exit(code),
+        Err(e) => {
+            eprintln!(
+                "{} {}",
+                style("Error:").red().bold(),
+                secret::redact(&format!("{:#}", e))
+            );
+            std::process::exit(1);
+        }
     }
 }
 
-fn run() -> Result<()> {
-    // Check if oha is installed
-    check_oha_installed()?;
-
+/// Run the application and return the process exit code
+fn run() -> Result<i32> {
     // Parse CLI args
     let args = Args::parse();
 
-    // Non-interactive mode: run once and exit
-    if args.non_interactive {
-        if args.url.is_empty() {
-            bail!("--url is required when using --non-interactive");
+    match args.command {
+        // A worker runs oha for a coordinator until stopped
+        Some(Command::Worker {
+            ref listen,
+            ref token,
+        }) => {
+            check_oha_installed()?;
+            let token = secret::resolve_optional(token.as_deref())?;
+            worker::serve(listen, token.as_deref())?;
+            return Ok(gate::EXIT_PASS);
         }
-        let config = config_from_args(&args);
-        let mut state = SessionState::default();
-        run_benchmark_suite(&config, &mut state)?;
-        return Ok(());
+        // History subcommands only read the store and don't need oha
+        Some(ref command) => {
+            run_history_command(command, &args)?;
+            return Ok(gate::EXIT_PASS);
+        }
+        None => {}
+    }
+
+    // Check if oha is installed (with workers, it only runs on them)
+    if args.worker.is_empty() {
+        check_oha_installed()?;
     }
 
-    // CLI mode with URL provided: run once and exit
-    if !args.url.is_empty() {
-        let config = config_from_args(&args);
+    // Runs are recorded in the history store unless --no-history is given
+    let history_dir =
+        (!args.no_history).then(|| history::resolve_history_dir(args.history_dir.as_deref()));
+
+    // Non-interactive mode: run once and exit
+    if args.non_interactive && !args.has_targets() {
+        bail!("--url is required when using --non-interactive");
+    }
+
+    // CLI mode with targets provided: run once and exit
+    if args.has_targets() {
+        let configs = configs_from_args(&args)?;
         let mut state = SessionState::default();
-        run_benchmark_suite(&config, &mut state)?;
-        return Ok(());
+        let mut urls = Vec::new();
+        let mut url_results = Vec::new();
+        for config in &configs {
+            url_results.extend(run_benchmark_suite(
+                config,
+                &mut state,
+                history_dir.as_deref(),
+            )?);
+            urls.extend(config.urls.iter().cloned());
+        }
+
+        // Non-interactive runs always end with a verdict so CI can parse the outcome
+        let gates = &configs[0].gates;
+        if !args.non_interactive && gates.is_empty() {
+            return Ok(gate::EXIT_PASS);
+        }
+        let verdict = gate::evaluate_gates(gates, &urls, &url_results);
+        print_verdict(&verdict);
+        return Ok(verdict.exit_code);
     }
 
-    // Interactive mode: loop until user quits
-    let mut state = SessionState::default();
+    // Interactive mode: loop until user quits, starting from the last launch's settings
+    let mut state = settings::load_session();
 
     loop {
         // Run interactive menu
@@ -67,7 +137,11 @@ fn run() -> Result<()> {
             Ok(c) => c,
             Err(e) => {
                 // User may have pressed Ctrl+C
-                eprintln!("\n{} {}", style("Cancelled:").yellow(), e);
+                eprintln!(
+                    "\n{} {}",
+                    style("Cancelled:").yellow(),
+                    secret::redact(&e.to_string())
+                );
                 break;
             }
         };
@@ -82,8 +156,17 @@ fn run() -> Result<()> {
         }
 
         // Run the benchmark
-        if let Err(e) = run_benchmark_suite(&config, &mut state) {
-            eprintln!("{} {:#}", style("Error:").red().bold(), e);
+        if let Err(e) = run_benchmark_suite(&config, &mut state, history_dir.as_deref()) {
+            eprintln!(
+                "{} {}",
+                style("Error:").red().bold(),
+                secret::redact(&format!("{:#}", e))
+            );
+        }
+
+        // Remember the settings (and report folder) for the next launch
+        if let Err(e) = settings::save_session(&state) {
+            eprintln!("{} Failed to save settings: {:#}", style("✗").red(), e);
         }
 
         // Ask what to do next
@@ -110,10 +193,69 @@ fn run() -> Result<()> {
         }
     }
 
+    Ok(gate::EXIT_PASS)
+}
+
+fn run_history_command(command: &Command, args: &Args) -> Result<()> {
+    let dir = history::resolve_history_dir(args.history_dir.as_deref());
+
+    match command {
+        Command::History {
+            scenario,
+            url,
+            limit,
+        } => {
+            let runs = history::load_runs(&dir, scenario.as_deref(), url.as_deref())?;
+            let skip = runs.len().saturating_sub(*limit);
+            print_history(&runs[skip..]);
+        }
+        Command::Trend {
+            scenario,
+            url,
+            at_rate,
+            output,
+        } => {
+            let runs = history::load_runs(&dir, scenario.as_deref(), url.as_deref())?;
+            print_trend(&runs, *at_rate);
+
+            if let Some(ref path) = output {
+                if runs.is_empty() {
+                    return Ok(());
+                }
+                match graph::generate_trend_graph(&runs, path, *at_rate) {
+                    Ok(_) => {
+                        println!();
+                        println!("{} Trend graph saved to: {}", style("✓").green(), path);
+                    }
+                    Err(e) => {
+                        eprintln!("{} Failed to save trend graph: {}", style("✗").red(), e);
+                    }
+                }
+            }
+        }
+        Command::Worker { .. } => {}
+    }
+
     Ok(())
 }
 
-fn run_benchmark_suite(config: &BenchmarkConfig, state: &mut SessionState) -> Result<()> {
+fn run_benchmark_suite(
+    config: &BenchmarkConfig,
+    state: &mut SessionState,
+    history_dir: Option<&Path>,
+) -> Result<Vec<UrlBenchmarkResults>> {
+    let outcome = run_suite(config, state, history_dir);
+    if let Err(ref e) = outcome {
+        notify::send(&config.notify, &Notification::failure(None, e));
+    }
+    outcome
+}
+
+fn run_suite(
+    config: &BenchmarkConfig,
+    state: &mut SessionState,
+    history_dir: Option<&Path>,
+) -> Result<Vec<UrlBenchmarkResults>> {
     // Print header and config summary
     print_header();
     print_config_summary(config);
@@ -128,9 +270,22 @@ fn run_benchmark_suite(config: &BenchmarkConfig, state: &mut SessionState) -> Re
         bail!("No rates to test. Check your start/max rate configuration.");
     }
 
+    if !hooks::run(&config.hooks, &HookEvent::new(Hook::BeforeRun)) {
+        bail!("The before-run hook failed");
+    }
+
+    // Load body/data files and templates once for the whole run
+    let mut plan = RequestPlan::new(config)?;
+
     // Store results for all URLs
     let mut all_url_results: Vec<UrlBenchmarkResults> = Vec::new();
 
+    // Set when the safety cutoff stops the whole run
+    let mut cutoff_reached = false;
+
+    // Only the first break of the run is notified
+    let mut break_notified = false;
+
     // Run benchmarks for each URL
     for (url_idx, url) in config.urls.iter().enumerate() {
         // Print URL header for multi-URL runs
@@ -145,9 +300,14 @@ fn run_benchmark_suite(config: &BenchmarkConfig, state: &mut SessionState) -> Re
                 "Warming up {} for {}s at {} req/s...",
                 url, config.warmup_seconds, config.ramping.start_rate
             ));
-            if let Err(e) = run_warmup(config, url) {
+            if let Err(e) = run_warmup(config, url, &mut plan) {
                 spinner.finish_and_clear();
-                eprintln!("{} Warmup failed for {}: {}", style("✗").red(), url, e);
+                eprintln!(
+                    "{} Warmup failed for {}: {}",
+                    style("✗").red(),
+                    url,
+                    secret::redact(&format!("{:#}", e))
+                );
                 continue;
             }
             spinner.finish_and_clear();
@@ -169,25 +329,66 @@ fn run_benchmark_suite(config: &BenchmarkConfig, state: &mut SessionState) -> Re
         let mut results: Vec<BenchmarkResult> = Vec::new();
         let mut analyses: Vec<AnalysisResult> = Vec::new();
 
+        // Live dashboard replaces the progress bar when running in a terminal
+        let mut dashboard = if config.live {
+            Dashboard::new(rates.len())
+        } else {
+            None
+        };
+
         for (i, &rate) in rates.iter().enumerate() {
+            hooks::run(
+                &config.hooks,
+                &HookEvent {
+                    url: Some(url),
+                    step: Some(i + 1),
+                    steps: Some(rates.len()),
+                    rate: Some(rate),
+                    ..HookEvent::new(Hook::BeforeStep)
+                },
+            );
+
             // Create progress indicator for this step
-            let pb =
-                create_step_progress(i + 1, rates.len(), rate, config.ramping.duration_seconds);
+            let pb = match dashboard {
+                Some(ref mut dashboard) => {
+                    dashboard.begin_step(i + 1, rate, config.ramping.max_step_seconds());
+                    ProgressBar::hidden()
+                }
+                None => create_step_progress(
+                    i + 1,
+                    rates.len(),
+                    rate,
+                    config.ramping.max_step_seconds(),
+                ),
+            };
 
             // Run benchmark
-            let result = match run_benchmark(config, url, rate) {
+            let result = match run_benchmark(config, url, rate, &mut plan, dashboard.as_mut()) {
                 Ok(r) => r,
                 Err(e) => {
                     pb.finish_and_clear();
-                    eprintln!("{} Failed at {} req/s: {}", style("✗").red(), rate, e);
+                    if let Some(ref mut dashboard) = dashboard {
+                        dashboard.clear();
+                    }
+                    eprintln!(
+                        "{} Failed at {} req/s: {}",
+                        style("✗").red(),
+                        rate,
+                        secret::redact(&format!("{:#}", e))
+                    );
+                    let e = e.context(format!("Failed at {} req/s", rate));
+                    notify::send(&config.notify, &Notification::failure(Some(url), &e));
                     break;
                 }
             };
 
             pb.finish_and_clear();
+            if let Some(ref mut dashboard) = dashboard {
+                dashboard.end_step(&result);
+            }
 
             // Analyze result
-            let analysis = analyze_result(&result, &config.thresholds);
+            let analysis = analyze_result(&result, &config.thresholds, &config.status_classes);
 
             // Fix error_rate for GONE status (100% failure) before printing/storing
             let mut result = result;
@@ -196,8 +397,9 @@ fn run_benchmark_suite(config: &BenchmarkConfig, state: &mut SessionState) -> Re
             }
 
             // Print row
-            print_result_row(&result, &analysis);
+            print_result_row(&result, &analysis, config.ramping.adaptive.is_some());
 
+            cutoff_reached = result.safety_cutoff;
             let should_break = matches!(
                 analysis.status,
                 StepStatus::Break
@@ -206,13 +408,56 @@ fn run_benchmark_suite(config: &BenchmarkConfig, state: &mut SessionState) -> Re
                     | StepStatus::Hung
                     | StepStatus::Gone
             );
+
+            let step_event = |event| HookEvent {
+                url: Some(url),
+                step: Some(i + 1),
+                steps: Some(rates.len()),
+                rate: Some(rate),
+                status: Some(analysis.status),
+                break_reason: Some(&analysis.break_reason),
+                result: Some(&result),
+                ..HookEvent::new(event)
+            };
+            let vetoed =
+                !hooks::run(&config.hooks, &step_event(Hook::AfterStep)) && config.hooks.veto;
+            if should_break {
+                hooks::run(&config.hooks, &step_event(Hook::OnBreak));
+                if !break_notified {
+                    break_notified = true;
+                    notify::send(
+                        &config.notify,
+                        &Notification::breaking(url, &result, &analysis.break_reason),
+                    );
+                }
+            }
+
             results.push(result);
             analyses.push(analysis);
 
             // Check if we should stop
+            if cutoff_reached {
+                println!(
+                    "{} Safety cutoff reached at {} req/s, stopping the run",
+                    style("■").red(),
+                    rate
+                );
+                break;
+            }
             if should_break {
                 break;
             }
+            if vetoed {
+                println!(
+                    "{} Ramp stopped by the after-step hook",
+                    style("■").yellow()
+                );
+                break;
+            }
+            if dashboard.as_ref().is_some_and(|d| d.stop_requested()) {
+                println!("{} Ramp stopped from the dashboard", style("■").yellow());
+                break;
+            }
 
             // Cooldown between steps (skip after last step)
             if config.cooldown_seconds > 0 && i < rates.len() - 1 {
@@ -223,7 +468,7 @@ fn run_benchmark_suite(config: &BenchmarkConfig, state: &mut SessionState) -> Re
         }
 
         // Generate summary for this URL
-        let summary = generate_summary(&results, &analyses, config.ramping.duration_seconds);
+        let summary = generate_summary(&results, &analyses);
 
         // Print summary
         print_summary(&summary);
@@ -235,8 +480,16 @@ fn run_benchmark_suite(config: &BenchmarkConfig, state: &mut SessionState) -> Re
             analyses,
             summary,
         });
+
+        if cutoff_reached {
+            break;
+        }
     }
 
+    // Paths of the saved report and graph, for the after-run hook
+    let mut saved_report: Option<String> = None;
+    let mut saved_graph: Option<String> = None;
+
     // Save report if configured
     if let (Some(ref dir), Some(ref name)) = (&config.report_dir, &config.report_name) {
         let (txt_path, png_path) = get_unique_report_paths(dir, name);
@@ -247,6 +500,7 @@ fn run_benchmark_suite(config: &BenchmarkConfig, state: &mut SessionState) -> Re
             Ok(_) => {
                 println!();
                 println!("{} Report saved to: {}", style("✓").green(), txt_path);
+                saved_report = Some(txt_path.clone());
 
                 // Update session state with the directory we saved to
                 if let Some(parent) = std::path::Path::new(&txt_path).parent() {
@@ -262,6 +516,7 @@ fn run_benchmark_suite(config: &BenchmarkConfig, state: &mut SessionState) -> Re
         match graph::generate_error_rate_graph(&all_url_results, &png_path, &config.thresholds) {
             Ok(_) => {
                 println!("{} Graph saved to: {}", style("✓").green(), png_path);
+                saved_graph = Some(png_path.clone());
             }
             Err(e) => {
                 eprintln!("{} Failed to save graph: {}", style("✗").red(), e);
@@ -269,7 +524,47 @@ fn run_benchmark_suite(config: &BenchmarkConfig, state: &mut SessionState) -> Re
         }
     }
 
-    Ok(())
+    // Append to the run history
+    if let Some(dir) = history_dir.filter(|_| !all_url_results.is_empty()) {
+        match history::record_runs(dir, config, &all_url_results) {
+            Ok(_) => {
+                println!(
+                    "{} Run recorded in history: {}",
+                    style("✓").green(),
+                    dir.display()
+                );
+            }
+            Err(e) => {
+                eprintln!("{} Failed to record run history: {:#}", style("✗").red(), e);
+            }
+        }
+    }
+
+    hooks::run(
+        &config.hooks,
+        &HookEvent {
+            summaries: all_url_results
+                .iter()
+                .map(|r| hooks::UrlSummary {
+                    url: &r.url,
+                    summary: &r.summary,
+                })
+                .collect(),
+            report: saved_report.as_deref(),
+            graph: saved_graph.as_deref(),
+            ..HookEvent::new(Hook::AfterRun)
+        },
+    );
+    notify::send(
+        &config.notify,
+        &Notification::complete(
+            &all_url_results,
+            saved_report.as_deref(),
+            saved_graph.as_deref(),
+        ),
+    );
+
+    Ok(all_url_results)
 }
 
 fn create_spinner(message: &str) -> ProgressBar {
@@ -1,18 +1,39 @@
 use anyhow::Result;
 use console::style;
-use dialoguer::{Confirm, Input, Select};
+use dialoguer::{Confirm, Editor, Input, Password, Select};
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 use crate::auth::{get_auth_type_names, index_to_auth_type};
 use crate::cli::{HttpMethod, RampingMode};
 use crate::config::{
-    get_downloads_dir, AuthConfig, BenchmarkConfig, RampingConfig, ThresholdConfig,
+    get_downloads_dir, AbortConfig, AdaptiveDuration, AuthConfig, BenchmarkConfig, GateConfig,
+    RampingConfig, ThresholdConfig,
 };
-use crate::output::print_header;
+use crate::hooks::HookConfig;
+use crate::import::{
+    apply_request, configs_from_imports, import_curl, import_from_args, import_har, import_openapi,
+    ImportedRequest,
+};
+use crate::login::{LoginConfig, DEFAULT_LOGIN_HEADER};
+use crate::metrics::MetricsConfig;
+use crate::notify::{NotifyConfig, Sink};
+use crate::output::{
+    describe_abort, describe_auth, describe_login, describe_status_classes, describe_tls,
+    describe_validation, print_header,
+};
+use crate::secret;
+use crate::settings;
+use crate::signing::{DEFAULT_JWT_ALGORITHM, DEFAULT_SIGNATURE_HEADER};
+use crate::status::{parse_status_rule, StatusClasses};
+use crate::tls::TlsConfig;
 use crate::user_agent::{get_preset_names, USER_AGENT_PRESETS};
+use crate::validate::{parse_json_assertion, ValidationConfig, Validator};
 
-/// Session state that persists across benchmark runs (within the same session)
-#[derive(Clone)]
+/// Settings remembered across benchmark runs, saved to the config dir between launches
+/// and as named presets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SessionState {
     pub last_report_dir: Option<PathBuf>,
     // Settings to remember (excludes: URL, filename, auth, headers)
@@ -25,13 +46,16 @@ pub struct SessionState {
     pub max_rate: u32,
     pub step: u32,
     pub duration_idx: usize,
+    pub adaptive: Option<AdaptiveDuration>,
     pub threads: u32,
     pub connections: u32,
     pub max_error_rate: f64,
     pub max_p99_ms: u32,
+    pub abort: AbortConfig,
     pub warmup_idx: usize,
     pub cooldown_idx: usize,
     pub save_report: bool,
+    pub live: bool,
 }
 
 impl Default for SessionState {
@@ -47,47 +71,812 @@ impl Default for SessionState {
             max_rate: 5000,
             step: 50,
             duration_idx: 0,
+            adaptive: None,
             threads: 4,
             connections: 100,
             max_error_rate: 5.0,
             max_p99_ms: 3000,
+            abort: AbortConfig::default(),
             warmup_idx: 1,
             cooldown_idx: 0,
             save_report: true,
+            live: false,
+        }
+    }
+}
+
+impl SessionState {
+    /// Clamp menu indices from a saved file that no longer match the menu's options
+    pub fn sanitize(&mut self) {
+        if self.user_agent_idx >= USER_AGENT_PRESETS.len() {
+            self.user_agent_idx = 0;
+        }
+        if self.duration_idx >= DURATION_OPTIONS.len() {
+            self.duration_idx = 0;
+        }
+        if self.warmup_idx >= WARMUP_OPTIONS.len() {
+            self.warmup_idx = 1;
+        }
+        if self.cooldown_idx >= COOLDOWN_OPTIONS.len() {
+            self.cooldown_idx = 0;
         }
     }
 }
 
+/// HTTP methods offered in the menu
+const METHODS: &[(&str, HttpMethod)] = &[
+    ("GET", HttpMethod::Get),
+    ("POST", HttpMethod::Post),
+    ("PUT", HttpMethod::Put),
+    ("PATCH", HttpMethod::Patch),
+    ("DELETE", HttpMethod::Delete),
+    ("HEAD", HttpMethod::Head),
+];
+
+/// Duration per step choices (label, seconds)
+const DURATION_OPTIONS: &[(&str, u32)] = &[
+    ("30 seconds (Recommended)", 30),
+    ("60 seconds", 60),
+    ("120 seconds", 120),
+];
+
+/// Warmup choices (label, seconds)
+const WARMUP_OPTIONS: &[(&str, u32)] = &[
+    ("No warmup", 0),
+    ("10 seconds (Recommended)", 10),
+    ("30 seconds", 30),
+];
+
+/// Cooldown choices (label, seconds)
+const COOLDOWN_OPTIONS: &[(&str, u32)] = &[
+    ("No cooldown (Recommended)", 0),
+    ("3 seconds", 3),
+    ("5 seconds", 5),
+    ("10 seconds", 10),
+];
+
+/// Longest value shown on the review screen before it is cut off
+const REVIEW_VALUE_WIDTH: usize = 56;
+
+/// A row of the review screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Urls,
+    Method,
+    Body,
+    UserAgent,
+    Auth,
+    Login,
+    Tls,
+    Headers,
+    Checks,
+    StatusClasses,
+    Mode,
+    StartRate,
+    MaxRate,
+    Step,
+    Duration,
+    Threads,
+    Connections,
+    MaxErrorRate,
+    MaxP99,
+    EarlyAbort,
+    Warmup,
+    Cooldown,
+    Live,
+    Report,
+    Presets,
+    Start,
+}
+
 /// Run the interactive menu and return a complete BenchmarkConfig
-/// Updates state with the selected values for session persistence
+/// After choosing the targets, every setting is shown on a review screen where any field can be
+/// edited before starting; remembered settings are written back to state
 pub fn run_interactive_menu(state: &mut SessionState) -> Result<BenchmarkConfig> {
     print_header();
     println!();
 
     let mut config = BenchmarkConfig::default();
 
-    // Target URLs (NOT remembered - always ask fresh)
-    println!(
-        "{}",
-        style("Enter target URL(s) to benchmark (comma-separated for multiple).").dim()
+    // Request source (NOT remembered - always ask fresh)
+    let sources = vec![
+        "Enter URL(s) manually",
+        "Paste a curl command",
+        "Import from HAR file",
+        "Import from OpenAPI spec",
+    ];
+    let source_idx = Select::new()
+        .with_prompt(format!("{}", style("Request source").cyan()))
+        .items(&sources)
+        .default(0)
+        .interact()?;
+
+    if source_idx == 0 {
+        // Target URLs (NOT remembered - always ask fresh)
+        println!(
+            "{}",
+            style("Enter target URL(s) to benchmark (comma-separated for multiple).").dim()
+        );
+        println!(
+            "{}",
+            style("URLs may use {{range:1:1000}}, {{uuid}} etc.; @path loads a file of URLs (one per line).")
+                .dim()
+        );
+        println!();
+        edit_urls(&mut config)?;
+        apply_session_defaults(&mut config, state);
+    } else {
+        // Imported method, body, User-Agent, auth and headers override the remembered values
+        let request = prompt_import(source_idx)?;
+        apply_session_defaults(&mut config, state);
+        apply_request(&mut config, &request);
+        println!("{} Imported {}", style("→").cyan(), request.name);
+    }
+
+    // Review screen: pick a field to change it, until the benchmark is started
+    let mut selected = 0;
+    loop {
+        println!();
+        let rows = review_rows(&config, state);
+        let items: Vec<String> = rows
+            .iter()
+            .map(|(_, label, value)| format!("{:<20} {}", label, value))
+            .collect();
+        selected = Select::new()
+            .with_prompt(format!(
+                "{}",
+                style("Review settings (Enter to edit, or start the benchmark)").cyan()
+            ))
+            .items(&items)
+            .default(selected.min(items.len() - 1))
+            .max_length(rows.len())
+            .interact()?;
+
+        let field = rows[selected].0;
+        if field == Field::Start {
+            if config.urls.is_empty() {
+                println!("{} At least one URL is required", style("Error:").red());
+                continue;
+            }
+            break;
+        }
+
+        // A bad value (missing file, unreadable secret) is reported and the field can be retried
+        if let Err(e) = edit_field(field, &mut config, state) {
+            if e.downcast_ref::<dialoguer::Error>().is_some() {
+                return Err(e);
+            }
+            println!(
+                "{} {}",
+                style("Error:").red(),
+                secret::redact(&format!("{:#}", e))
+            );
+        }
+    }
+
+    finish_report(&mut config, state);
+    remember_settings(&config, state);
+    if !has_body(config.method) {
+        set_body(&mut config, "");
+    }
+    secret::register_config(&config);
+    Ok(config)
+}
+
+/// Fill the config with the settings remembered from earlier runs in this session
+fn apply_session_defaults(config: &mut BenchmarkConfig, state: &SessionState) {
+    config.method = state.method;
+    set_body(config, state.body.as_deref().unwrap_or(""));
+    config.user_agent = if state.user_agent_idx == USER_AGENT_PRESETS.len() - 1 {
+        state.custom_user_agent.clone().unwrap_or_default()
+    } else {
+        USER_AGENT_PRESETS[state.user_agent_idx].value.to_string()
+    };
+    config.ramping.mode = state.ramping_mode;
+    config.ramping.start_rate = state.start_rate;
+    config.ramping.max_rate = state.max_rate;
+    config.ramping.step = state.step;
+    config.ramping.duration_seconds = DURATION_OPTIONS[state.duration_idx].1;
+    config.ramping.adaptive = state.adaptive.clone();
+    config.ramping.threads = state.threads;
+    config.ramping.connections = state.connections;
+    config.thresholds.max_error_rate = state.max_error_rate;
+    config.thresholds.max_p99_ms = state.max_p99_ms;
+    config.abort = state.abort.clone();
+    config.warmup_seconds = WARMUP_OPTIONS[state.warmup_idx].1;
+    config.cooldown_seconds = COOLDOWN_OPTIONS[state.cooldown_idx].1;
+    config.live = state.live;
+    config.report_dir = Some(
+        state
+            .last_report_dir
+            .clone()
+            .unwrap_or_else(get_downloads_dir)
+            .to_string_lossy()
+            .to_string(),
     );
-    println!();
+}
+
+/// Write the remembered settings back to the session state
+/// A body is only remembered for methods that take one
+fn remember_settings(config: &BenchmarkConfig, state: &mut SessionState) {
+    state.method = config.method;
+    if has_body(config.method) {
+        state.body = match (&config.body, &config.body_file) {
+            (Some(body), _) => Some(body.clone()),
+            (None, Some(path)) => Some(format!("@{}", path)),
+            (None, None) => None,
+        };
+    }
+    state.ramping_mode = config.ramping.mode;
+    state.start_rate = config.ramping.start_rate;
+    state.max_rate = config.ramping.max_rate;
+    state.step = config.ramping.step;
+    state.threads = config.ramping.threads;
+    state.connections = config.ramping.connections;
+    state.max_error_rate = config.thresholds.max_error_rate;
+    state.max_p99_ms = config.thresholds.max_p99_ms;
+    state.abort = config.abort.clone();
+    state.live = config.live;
+}
+
+/// Settle the report location: derive the name from the URL unless one was given
+fn finish_report(config: &mut BenchmarkConfig, state: &mut SessionState) {
+    if !state.save_report {
+        config.report_dir = None;
+        config.report_name = None;
+        return;
+    }
+    if config.report_name.is_none() {
+        config.report_name = Some(default_report_name(config));
+    }
+    state.last_report_dir = config.report_dir.as_ref().map(PathBuf::from);
+}
+
+/// Default benchmark name - the URL for a single target, otherwise a generic name
+/// (NOT remembered - derived from the URL each time)
+fn default_report_name(config: &BenchmarkConfig) -> String {
+    if config.urls.len() == 1 {
+        url_to_safe_name(&config.urls[0])
+    } else {
+        "ohabench_benchmark".to_string()
+    }
+}
+
+/// Whether the method sends a request body
+fn has_body(method: HttpMethod) -> bool {
+    matches!(
+        method,
+        HttpMethod::Post | HttpMethod::Put | HttpMethod::Patch
+    )
+}
+
+/// Set the body from menu input: `@path` loads a file, empty means no body
+fn set_body(config: &mut BenchmarkConfig, body: &str) {
+    config.body = None;
+    config.body_file = None;
+    if let Some(path) = body.strip_prefix('@') {
+        config.body_file = Some(path.trim().to_string());
+    } else if !body.is_empty() {
+        config.body = Some(body.to_string());
+    }
+}
+
+/// The rows of the review screen with their current values
+fn review_rows(
+    config: &BenchmarkConfig,
+    state: &SessionState,
+) -> Vec<(Field, &'static str, String)> {
+    let mut rows = vec![(Field::Urls, "Target URL(s)", config.urls.join(", "))];
+    rows.push((
+        Field::Method,
+        "HTTP Method",
+        config.method.to_string().to_uppercase(),
+    ));
+    if has_body(config.method) {
+        rows.push((Field::Body, "Request body", describe_body(config)));
+    }
+    rows.push((Field::UserAgent, "User-Agent", config.user_agent.clone()));
+    rows.push((
+        Field::Auth,
+        "Authentication",
+        describe_auth(config).unwrap_or_else(|| "None".to_string()),
+    ));
+    rows.push((
+        Field::Login,
+        "Login",
+        config
+            .login
+            .as_ref()
+            .map(describe_login)
+            .unwrap_or_else(|| "None".to_string()),
+    ));
+    rows.push((
+        Field::Tls,
+        "TLS",
+        if config.tls.is_empty() {
+            "Default".to_string()
+        } else {
+            describe_tls(&config.tls)
+        },
+    ));
+    rows.push((
+        Field::Headers,
+        "Headers",
+        match config.headers.len() {
+            0 => "None".to_string(),
+            _ => config
+                .headers
+                .iter()
+                .map(|h| secret::redact_header(h))
+                .collect::<Vec<_>>()
+                .join(" | "),
+        },
+    ));
+    rows.push((
+        Field::Checks,
+        "Response checks",
+        if config.validation.is_empty() {
+            "None".to_string()
+        } else {
+            describe_validation(&config.validation)
+        },
+    ));
+    rows.push((
+        Field::StatusClasses,
+        "Status classes",
+        if config.status_classes.is_default() {
+            "Default".to_string()
+        } else {
+            describe_status_classes(&config.status_classes)
+        },
+    ));
+    rows.push((Field::Mode, "Ramping mode", config.ramping.mode.to_string()));
+    rows.push((
+        Field::StartRate,
+        "Starting rate",
+        format!("{} req/s", config.ramping.start_rate),
+    ));
+    rows.push((
+        Field::MaxRate,
+        "Maximum rate",
+        format!("{} req/s", config.ramping.max_rate),
+    ));
+    if config.ramping.mode == RampingMode::Linear {
+        rows.push((Field::Step, "Step size", config.ramping.step.to_string()));
+    }
+    rows.push((
+        Field::Duration,
+        "Duration per step",
+        match config.ramping.adaptive {
+            Some(ref adaptive) => format!(
+                "Adaptive, {}-{}s (within {}%)",
+                adaptive.min_seconds, adaptive.max_seconds, adaptive.tolerance_percent
+            ),
+            None => format!("{}s", config.ramping.duration_seconds),
+        },
+    ));
+    rows.push((
+        Field::Threads,
+        "Threads",
+        config.ramping.threads.to_string(),
+    ));
+    rows.push((
+        Field::Connections,
+        "Connections",
+        config.ramping.connections.to_string(),
+    ));
+    rows.push((
+        Field::MaxErrorRate,
+        "Max error rate",
+        format!("{}%", config.thresholds.max_error_rate),
+    ));
+    rows.push((
+        Field::MaxP99,
+        "Max p99 latency",
+        format!("{}ms", config.thresholds.max_p99_ms),
+    ));
+    rows.push((
+        Field::EarlyAbort,
+        "Early abort",
+        describe_abort(&config.abort),
+    ));
+    rows.push((
+        Field::Warmup,
+        "Warmup",
+        WARMUP_OPTIONS[state.warmup_idx].0.to_string(),
+    ));
+    rows.push((
+        Field::Cooldown,
+        "Cooldown",
+        COOLDOWN_OPTIONS[state.cooldown_idx].0.to_string(),
+    ));
+    rows.push((
+        Field::Live,
+        "Live dashboard",
+        if config.live { "On" } else { "Off" }.to_string(),
+    ));
+    rows.push((
+        Field::Report,
+        "Report",
+        match (state.save_report, &config.report_dir) {
+            (true, Some(dir)) => format!(
+                "{}/{}.txt",
+                dir.trim_end_matches(['/', '\\']),
+                config
+                    .report_name
+                    .clone()
+                    .unwrap_or_else(|| default_report_name(config))
+            ),
+            _ => "Don't save".to_string(),
+        },
+    ));
 
+    rows.push((
+        Field::Presets,
+        "Presets",
+        "Save, load or delete named settings".to_string(),
+    ));
+
+    let mut rows: Vec<(Field, &'static str, String)> = rows
+        .into_iter()
+        .map(|(field, label, value)| (field, label, truncate(&value, REVIEW_VALUE_WIDTH)))
+        .collect();
+    rows.push((Field::Start, "▶ Start benchmark", String::new()));
+    rows
+}
+
+/// Describe the body for the review screen: the file, or the body folded onto one line
+fn describe_body(config: &BenchmarkConfig) -> String {
+    match (&config.body, &config.body_file) {
+        (_, Some(path)) => format!("@{}", path),
+        (Some(body), None) => {
+            let lines = body.lines().count();
+            let folded = body.split_whitespace().collect::<Vec<_>>().join(" ");
+            if lines > 1 {
+                format!("({} lines) {}", lines, folded)
+            } else {
+                folded
+            }
+        }
+        (None, None) => "None".to_string(),
+    }
+}
+
+/// Shorten a value to `width` characters
+fn truncate(value: &str, width: usize) -> String {
+    if value.chars().count() <= width {
+        return value.to_string();
+    }
+    let cut: String = value.chars().take(width.saturating_sub(1)).collect();
+    format!("{}…", cut)
+}
+
+/// Edit one field of the review screen
+fn edit_field(field: Field, config: &mut BenchmarkConfig, state: &mut SessionState) -> Result<()> {
+    match field {
+        Field::Urls => edit_urls(config)?,
+        Field::Method => {
+            let names: Vec<&str> = METHODS.iter().map(|(name, _)| *name).collect();
+            let current = METHODS
+                .iter()
+                .position(|(_, m)| *m == config.method)
+                .unwrap_or(0);
+            let idx = Select::new()
+                .with_prompt(format!("{}", style("HTTP Method").cyan()))
+                .items(&names)
+                .default(current)
+                .interact()?;
+            config.method = METHODS[idx].1;
+        }
+        Field::Body => edit_body(config)?,
+        Field::UserAgent => {
+            let ua_names = get_preset_names();
+            let ua_idx = Select::new()
+                .with_prompt(format!("{}", style("User-Agent").cyan()))
+                .items(&ua_names)
+                .default(state.user_agent_idx)
+                .interact()?;
+            state.user_agent_idx = ua_idx;
+
+            if ua_idx == ua_names.len() - 1 {
+                // Custom
+                let custom_ua: String = Input::new()
+                    .with_prompt(format!("{}", style("Custom User-Agent").cyan()))
+                    .with_initial_text(config.user_agent.clone())
+                    .interact_text()?;
+                config.user_agent = custom_ua.clone();
+                state.custom_user_agent = Some(custom_ua);
+            } else {
+                config.user_agent = USER_AGENT_PRESETS[ua_idx].value.to_string();
+            }
+        }
+        Field::Auth => edit_auth(config)?,
+        Field::Login => {
+            let use_login = Confirm::new()
+                .with_prompt(format!(
+                    "{}",
+                    style("Log in before benchmarking? (form/cookie or token login)").cyan()
+                ))
+                .default(config.login.is_some())
+                .interact()?;
+            config.login = if use_login {
+                Some(prompt_login(config.login.as_ref())?)
+            } else {
+                None
+            };
+        }
+        Field::Tls => {
+            let use_tls = Confirm::new()
+                .with_prompt(format!(
+                    "{}",
+                    style("Configure TLS? (client certificate, CA bundle, SNI, insecure)").cyan()
+                ))
+                .default(!config.tls.is_empty())
+                .interact()?;
+            config.tls = if use_tls {
+                prompt_tls(&config.tls)?
+            } else {
+                TlsConfig::default()
+            };
+        }
+        Field::Headers => edit_headers(&mut config.headers)?,
+        Field::Checks => config.validation = prompt_validation(&config.validation)?,
+        Field::StatusClasses => {
+            let rules: String = Input::new()
+                .with_prompt(format!(
+                    "{} (e.g. 404=success, 503=rate-limit, 5xx=error; leave empty for defaults)",
+                    style("Status classes").cyan()
+                ))
+                .with_initial_text(describe_status_classes(&config.status_classes))
+                .allow_empty(true)
+                .interact_text()?;
+            config.status_classes = StatusClasses {
+                rules: rules
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|rule| !rule.is_empty())
+                    .map(parse_status_rule)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| anyhow::anyhow!(e))?,
+            };
+        }
+        Field::Mode => {
+            let ramping_modes = vec![
+                "Linear (50, 100, 150, 200...)",
+                "Exponential (50, 100, 200, 400...)",
+            ];
+            let mode_idx = Select::new()
+                .with_prompt(format!("{}", style("Ramping mode").cyan()))
+                .items(&ramping_modes)
+                .default(match config.ramping.mode {
+                    RampingMode::Linear => 0,
+                    RampingMode::Exponential => 1,
+                })
+                .interact()?;
+            config.ramping.mode = match mode_idx {
+                1 => RampingMode::Exponential,
+                _ => RampingMode::Linear,
+            };
+        }
+        Field::StartRate => {
+            config.ramping.start_rate = Input::new()
+                .with_prompt(format!("{}", style("Starting rate (req/s)").cyan()))
+                .default(config.ramping.start_rate)
+                .interact_text()?;
+        }
+        Field::MaxRate => {
+            config.ramping.max_rate = Input::new()
+                .with_prompt(format!("{}", style("Maximum rate (req/s)").cyan()))
+                .default(config.ramping.max_rate)
+                .interact_text()?;
+        }
+        Field::Step => {
+            config.ramping.step = Input::new()
+                .with_prompt(format!("{}", style("Step size").cyan()))
+                .default(config.ramping.step)
+                .interact_text()?;
+        }
+        Field::Duration => {
+            let mut labels: Vec<&str> = DURATION_OPTIONS.iter().map(|(label, _)| *label).collect();
+            labels.push("Adaptive (extend until latency and throughput settle)");
+            let idx = Select::new()
+                .with_prompt(format!("{}", style("Duration per step").cyan()))
+                .items(&labels)
+                .default(match config.ramping.adaptive {
+                    Some(_) => DURATION_OPTIONS.len(),
+                    None => state.duration_idx,
+                })
+                .interact()?;
+            if idx == DURATION_OPTIONS.len() {
+                config.ramping.adaptive = Some(prompt_adaptive(
+                    config
+                        .ramping
+                        .adaptive
+                        .as_ref()
+                        .unwrap_or(&AdaptiveDuration::default()),
+                )?);
+            } else {
+                state.duration_idx = idx;
+                config.ramping.duration_seconds = DURATION_OPTIONS[idx].1;
+                config.ramping.adaptive = None;
+            }
+            state.adaptive = config.ramping.adaptive.clone();
+        }
+        Field::Threads => {
+            config.ramping.threads = Input::new()
+                .with_prompt(format!("{}", style("Threads").cyan()))
+                .default(config.ramping.threads)
+                .interact_text()?;
+        }
+        Field::Connections => {
+            config.ramping.connections = Input::new()
+                .with_prompt(format!("{}", style("Connections").cyan()))
+                .default(config.ramping.connections)
+                .interact_text()?;
+        }
+        Field::MaxErrorRate => {
+            config.thresholds.max_error_rate = Input::new()
+                .with_prompt(format!("{}", style("Max error rate (%)").cyan()))
+                .default(config.thresholds.max_error_rate)
+                .interact_text()?;
+        }
+        Field::MaxP99 => {
+            config.thresholds.max_p99_ms = Input::new()
+                .with_prompt(format!("{}", style("Max p99 latency (ms)").cyan()))
+                .default(config.thresholds.max_p99_ms)
+                .interact_text()?;
+        }
+        Field::EarlyAbort => config.abort = prompt_abort(&config.abort)?,
+        Field::Warmup => {
+            state.warmup_idx = select_option(
+                "Warmup period before ramping",
+                WARMUP_OPTIONS,
+                state.warmup_idx,
+            )?;
+            config.warmup_seconds = WARMUP_OPTIONS[state.warmup_idx].1;
+        }
+        Field::Cooldown => {
+            state.cooldown_idx = select_option(
+                "Cooldown between steps",
+                COOLDOWN_OPTIONS,
+                state.cooldown_idx,
+            )?;
+            config.cooldown_seconds = COOLDOWN_OPTIONS[state.cooldown_idx].1;
+        }
+        Field::Live => {
+            config.live = Confirm::new()
+                .with_prompt(format!(
+                    "{}",
+                    style("Show a live dashboard during each step?").cyan()
+                ))
+                .default(config.live)
+                .interact()?;
+            state.live = config.live;
+        }
+        Field::Report => edit_report(config, state)?,
+        Field::Presets => edit_presets(config, state)?,
+        Field::Start => {}
+    }
+    Ok(())
+}
+
+/// Ask for the adaptive step bounds and stability tolerance
+fn prompt_adaptive(current: &AdaptiveDuration) -> Result<AdaptiveDuration> {
+    let min_seconds: u32 = Input::new()
+        .with_prompt(format!("{}", style("Shortest step (seconds)").cyan()))
+        .default(current.min_seconds)
+        .interact_text()?;
+    let max_seconds: u32 = Input::new()
+        .with_prompt(format!("{}", style("Longest step (seconds)").cyan()))
+        .default(current.max_seconds.max(min_seconds))
+        .validate_with(|value: &u32| {
+            if *value >= min_seconds {
+                Ok(())
+            } else {
+                Err("must be at least the shortest step")
+            }
+        })
+        .interact_text()?;
+    let tolerance_percent = Input::new()
+        .with_prompt(format!(
+            "{}",
+            style("Stable when p50, p99 and throughput change less than (%)").cyan()
+        ))
+        .default(current.tolerance_percent)
+        .interact_text()?;
+    Ok(AdaptiveDuration {
+        min_seconds: min_seconds.max(1),
+        max_seconds: max_seconds.max(1),
+        tolerance_percent,
+    })
+}
+
+/// Ask for the early abort margin and window, and the run-wide safety cutoff
+fn prompt_abort(current: &AbortConfig) -> Result<AbortConfig> {
+    let mut abort = AbortConfig {
+        window_seconds: current.window_seconds,
+        ..AbortConfig::default()
+    };
+
+    let use_margin = Confirm::new()
+        .with_prompt(format!(
+            "{}",
+            style("Abort a step early when it clearly exceeds the break thresholds?").cyan()
+        ))
+        .default(current.margin_percent.is_some())
+        .interact()?;
+    if use_margin {
+        abort.margin_percent = Some(
+            Input::new()
+                .with_prompt(format!(
+                    "{}",
+                    style("Margin above the thresholds (%)").cyan()
+                ))
+                .default(current.margin_percent.unwrap_or(50.0))
+                .interact_text()?,
+        );
+    }
+
+    let cutoff: f64 = Input::new()
+        .with_prompt(format!(
+            "{}",
+            style("Safety cutoff: stop the whole run at this error rate (%, 0 = off)").cyan()
+        ))
+        .default(current.cutoff_error_rate.unwrap_or(0.0))
+        .interact_text()?;
+    abort.cutoff_error_rate = (cutoff > 0.0).then_some(cutoff);
+
+    let cutoff_p99: u32 = Input::new()
+        .with_prompt(format!(
+            "{}",
+            style("Safety cutoff: stop the whole run at this p99 (ms, 0 = off)").cyan()
+        ))
+        .default(current.cutoff_p99_ms.unwrap_or(0))
+        .interact_text()?;
+    abort.cutoff_p99_ms = (cutoff_p99 > 0).then_some(cutoff_p99);
+
+    if abort.is_enabled() {
+        let window: u32 = Input::new()
+            .with_prompt(format!(
+                "{}",
+                style("Check window (seconds the thresholds must stay exceeded)").cyan()
+            ))
+            .default(current.window_seconds)
+            .interact_text()?;
+        abort.window_seconds = window.max(1);
+    }
+    Ok(abort)
+}
+
+/// Pick one of a fixed set of labelled values, returning its index
+fn select_option(prompt: &str, options: &[(&str, u32)], current: usize) -> Result<usize> {
+    let labels: Vec<&str> = options.iter().map(|(label, _)| *label).collect();
+    Ok(Select::new()
+        .with_prompt(format!("{}", style(prompt).cyan()))
+        .items(&labels)
+        .default(current)
+        .interact()?)
+}
+
+/// Ask for the target URLs (comma-separated)
+fn edit_urls(config: &mut BenchmarkConfig) -> Result<()> {
     let urls_input: String = Input::new()
         .with_prompt(format!("{}", style("Target URL(s)").cyan()))
+        .with_initial_text(config.urls.join(", "))
         .interact_text()?;
 
     // Parse URLs - split by comma and ensure protocol
-    config.urls = urls_input
+    let urls: Vec<String> = urls_input
         .split(',')
         .map(|s| s.trim().to_string())
         .filter(|s| !s.is_empty())
         .map(|url| ensure_protocol(&url))
         .collect();
 
-    if config.urls.is_empty() {
+    if urls.is_empty() {
         anyhow::bail!("At least one URL is required");
     }
+    config.urls = urls;
 
     // Show how many URLs will be tested
     if config.urls.len() > 1 {
@@ -99,348 +888,690 @@ pub fn run_interactive_menu(state: &mut SessionState) -> Result<BenchmarkConfig>
         for (i, url) in config.urls.iter().enumerate() {
             println!("  {}. {}", i + 1, url);
         }
-        println!();
     }
+    Ok(())
+}
 
-    // HTTP Method
-    let methods = vec!["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD"];
-    let method_default = match state.method {
-        HttpMethod::Get => 0,
-        HttpMethod::Post => 1,
-        HttpMethod::Put => 2,
-        HttpMethod::Patch => 3,
-        HttpMethod::Delete => 4,
-        HttpMethod::Head => 5,
-    };
-    let method_idx = Select::new()
-        .with_prompt(format!("{}", style("HTTP Method").cyan()))
-        .items(&methods)
-        .default(method_default)
+/// Edit the request body: in $EDITOR (multi-line), as one line, from a file, or none
+fn edit_body(config: &mut BenchmarkConfig) -> Result<()> {
+    println!(
+        "{}",
+        style("{{uuid}}, {{counter}}, {{random_int:1:100}} etc. vary each body").dim()
+    );
+    let choices = vec![
+        "Edit in $EDITOR (multi-line)",
+        "Enter a single line",
+        "Load from a file",
+        "No body",
+    ];
+    let choice = Select::new()
+        .with_prompt(format!("{}", style("Request body").cyan()))
+        .items(&choices)
+        .default(0)
         .interact()?;
-    config.method = match method_idx {
-        0 => HttpMethod::Get,
-        1 => HttpMethod::Post,
-        2 => HttpMethod::Put,
-        3 => HttpMethod::Patch,
-        4 => HttpMethod::Delete,
-        5 => HttpMethod::Head,
-        _ => HttpMethod::Get,
-    };
-    state.method = config.method;
 
-    // Request body for POST/PUT/PATCH
-    if matches!(
-        config.method,
-        HttpMethod::Post | HttpMethod::Put | HttpMethod::Patch
-    ) {
-        let body_default = state.body.clone().unwrap_or_default();
-        let body: String = Input::new()
+    match choice {
+        0 => {
+            let current = config.body.clone().unwrap_or_default();
+            let extension = if current.trim_start().starts_with(['{', '[']) || current.is_empty() {
+                ".json"
+            } else {
+                ".txt"
+            };
+            // None means the editor was closed without saving: keep the current body
+            if let Some(body) = Editor::new().extension(extension).edit(&current)? {
+                let body = body.trim_end_matches(['\r', '\n']);
+                config.body = Some(body.to_string()).filter(|b| !b.is_empty());
+                config.body_file = None;
+            }
+        }
+        1 => {
+            let body: String = Input::new()
+                .with_prompt(format!("{}", style("Request body").cyan()))
+                .with_initial_text(config.body.clone().unwrap_or_default())
+                .allow_empty(true)
+                .interact_text()?;
+            config.body = Some(body).filter(|b| !b.is_empty());
+            config.body_file = None;
+        }
+        2 => {
+            let path: String = Input::new()
+                .with_prompt(format!("{}", style("Body file").cyan()))
+                .with_initial_text(config.body_file.clone().unwrap_or_default())
+                .interact_text()?;
+            let path = path.trim().trim_start_matches('@');
+            if !std::path::Path::new(path).is_file() {
+                anyhow::bail!("Body file {} does not exist", path);
+            }
+            set_body(config, &format!("@{}", path));
+        }
+        _ => set_body(config, ""),
+    }
+    Ok(())
+}
+
+/// Edit the headers, one per entry (values may contain commas)
+fn edit_headers(headers: &mut Vec<String>) -> Result<()> {
+    loop {
+        let mut items: Vec<String> = headers.iter().map(|h| secret::redact_header(h)).collect();
+        items.push("+ Add header".to_string());
+        items.push("✓ Done".to_string());
+
+        let idx = Select::new()
             .with_prompt(format!(
-                "{} (leave empty for none)",
-                style("Request body").cyan()
+                "{}",
+                style("Headers (select one to edit or remove)").cyan()
             ))
-            .default(body_default)
-            .allow_empty(true)
-            .interact_text()?;
-        if !body.is_empty() {
-            config.body = Some(body.clone());
-            state.body = Some(body);
+            .items(&items)
+            .default(headers.len())
+            .interact()?;
+
+        if idx == headers.len() {
+            println!(
+                "{}",
+                style("Example: Accept: application/json, text/plain").dim()
+            );
+            let header = prompt_header("")?;
+            if !header.is_empty() {
+                headers.push(header);
+            }
+        } else if idx < headers.len() {
+            let actions = vec!["Edit", "Remove", "Keep"];
+            let action = Select::new()
+                .with_prompt(format!("{}", style(&items[idx]).cyan()))
+                .items(&actions)
+                .default(0)
+                .interact()?;
+            match action {
+                0 => {
+                    let header = prompt_header(&headers[idx])?;
+                    if header.is_empty() {
+                        headers.remove(idx);
+                    } else {
+                        headers[idx] = header;
+                    }
+                }
+                1 => {
+                    headers.remove(idx);
+                }
+                _ => {}
+            }
         } else {
-            state.body = None;
+            return Ok(());
         }
     }
+}
 
-    // User-Agent
-    let ua_names = get_preset_names();
-    let ua_idx = Select::new()
-        .with_prompt(format!("{}", style("User-Agent").cyan()))
-        .items(&ua_names)
-        .default(state.user_agent_idx)
-        .interact()?;
-    state.user_agent_idx = ua_idx;
-
-    if ua_idx == ua_names.len() - 1 {
-        // Custom
-        let custom_default = state.custom_user_agent.clone().unwrap_or_default();
-        let custom_ua: String = Input::new()
-            .with_prompt(format!("{}", style("Custom User-Agent").cyan()))
-            .default(custom_default)
-            .interact_text()?;
-        config.user_agent = custom_ua.clone();
-        state.custom_user_agent = Some(custom_ua);
-    } else {
-        config.user_agent = USER_AGENT_PRESETS[ua_idx].value.to_string();
-    }
+/// Ask for one `Name: value` header (empty to cancel)
+fn prompt_header(current: &str) -> Result<String> {
+    let header: String = Input::new()
+        .with_prompt(format!(
+            "{} (Name: value, leave empty to skip)",
+            style("Header").cyan()
+        ))
+        .with_initial_text(current)
+        .allow_empty(true)
+        .validate_with(|h: &String| -> std::result::Result<(), &str> {
+            if h.trim().is_empty() || h.contains(':') {
+                Ok(())
+            } else {
+                Err("Headers look like Name: value")
+            }
+        })
+        .interact_text()?;
+    Ok(header.trim().to_string())
+}
 
-    // Authentication (NOT remembered - always start fresh)
+/// Choose the authentication type and ask for its credentials
+fn edit_auth(config: &mut BenchmarkConfig) -> Result<()> {
+    println!(
+        "{}",
+        style("Secrets may be entered as env:NAME or file:PATH instead of the value").dim()
+    );
     let auth_types = get_auth_type_names();
+    let current = (0..auth_types.len())
+        .position(|i| index_to_auth_type(i) == config.auth.auth_type)
+        .unwrap_or(0);
     let auth_idx = Select::new()
         .with_prompt(format!("{}", style("Authentication").cyan()))
         .items(&auth_types)
-        .default(0)
+        .default(current)
         .interact()?;
 
-    config.auth.auth_type = index_to_auth_type(auth_idx);
+    let mut auth = AuthConfig {
+        auth_type: index_to_auth_type(auth_idx),
+        ..AuthConfig::default()
+    };
 
-    match config.auth.auth_type {
+    match auth.auth_type {
         crate::cli::AuthType::Basic => {
             let username: String = Input::new()
                 .with_prompt(format!("{}", style("Username").cyan()))
                 .interact_text()?;
-            let password: String = Input::new()
+            let password = Password::new()
                 .with_prompt(format!("{}", style("Password").cyan()))
-                .interact_text()?;
-            config.auth.username = Some(username);
-            config.auth.password = Some(password);
+                .interact()?;
+            auth.username = Some(username);
+            auth.password = Some(secret::resolve_secret(&password)?);
         }
         crate::cli::AuthType::Bearer => {
-            let token: String = Input::new()
+            let token = Password::new()
                 .with_prompt(format!("{}", style("Bearer Token").cyan()))
-                .interact_text()?;
-            config.auth.token = Some(token);
+                .interact()?;
+            auth.token = Some(secret::resolve_secret(&token)?);
         }
         crate::cli::AuthType::Header => {
-            let header: String = Input::new()
+            let header = Password::new()
                 .with_prompt(format!(
                     "{} (e.g., X-API-Key: secret)",
                     style("Custom Header").cyan()
                 ))
+                .interact()?;
+            auth.custom_header = Some(secret::resolve_secret(&header)?);
+        }
+        crate::cli::AuthType::OAuth2 => {
+            let token_url: String = Input::new()
+                .with_prompt(format!("{}", style("Token URL").cyan()))
                 .interact_text()?;
-            config.auth.custom_header = Some(header);
+            let client_id: String = Input::new()
+                .with_prompt(format!("{}", style("Client ID").cyan()))
+                .interact_text()?;
+            let client_secret = Password::new()
+                .with_prompt(format!("{}", style("Client secret").cyan()))
+                .interact()?;
+            let scope: String = Input::new()
+                .with_prompt(format!("{} (leave empty for none)", style("Scope").cyan()))
+                .allow_empty(true)
+                .interact_text()?;
+            auth.token_url = Some(ensure_protocol(token_url.trim()));
+            auth.client_id = Some(client_id);
+            auth.client_secret = Some(secret::resolve_secret(&client_secret)?);
+            auth.scope = Some(scope).filter(|s| !s.is_empty());
+        }
+        crate::cli::AuthType::Hmac => {
+            let key = Password::new()
+                .with_prompt(format!("{}", style("HMAC secret").cyan()))
+                .interact()?;
+            let header: String = Input::new()
+                .with_prompt(format!("{}", style("Signature header").cyan()))
+                .default(DEFAULT_SIGNATURE_HEADER.to_string())
+                .interact_text()?;
+            auth.signing_key = Some(secret::resolve_secret(&key)?);
+            auth.signature_header = Some(header);
+        }
+        crate::cli::AuthType::SigV4 => {
+            let access_key: String = Input::new()
+                .with_prompt(format!("{}", style("AWS access key ID").cyan()))
+                .interact_text()?;
+            let secret_key = Password::new()
+                .with_prompt(format!("{}", style("AWS secret access key").cyan()))
+                .interact()?;
+            let region: String = Input::new()
+                .with_prompt(format!("{}", style("AWS region").cyan()))
+                .default("us-east-1".to_string())
+                .interact_text()?;
+            let service: String = Input::new()
+                .with_prompt(format!("{}", style("AWS service").cyan()))
+                .default("execute-api".to_string())
+                .interact_text()?;
+            auth.key_id = Some(access_key);
+            auth.signing_key = Some(secret::resolve_secret(&secret_key)?);
+            auth.region = Some(region);
+            auth.service = Some(service);
+        }
+        crate::cli::AuthType::Jwt => {
+            let algorithm: String = Input::new()
+                .with_prompt(format!("{}", style("JWT algorithm").cyan()))
+                .default(DEFAULT_JWT_ALGORITHM.to_string())
+                .interact_text()?;
+            let key = Password::new()
+                .with_prompt(format!(
+                    "{} (secret for HS*, file:PATH for a PEM key)",
+                    style("Signing key").cyan()
+                ))
+                .interact()?;
+            let claims: String = Input::new()
+                .with_prompt(format!(
+                    "{} (JSON, e.g. {{\"sub\":\"bench\"}}; leave empty for none)",
+                    style("Claims").cyan()
+                ))
+                .allow_empty(true)
+                .interact_text()?;
+            auth.algorithm = Some(algorithm);
+            auth.signing_key = Some(secret::resolve_secret(&key)?);
+            auth.claims = Some(claims).filter(|c| !c.is_empty());
         }
         crate::cli::AuthType::None => {}
     }
 
-    // Additional headers (NOT remembered - always start fresh)
-    println!(
-        "{}",
-        style("Example: X-API-Key: secret, Accept: application/json").dim()
-    );
-    let extra_headers: String = Input::new()
+    config.auth = auth;
+    Ok(())
+}
+
+/// Ask for the login pre-step, starting from the current one
+fn prompt_login(current: Option<&LoginConfig>) -> Result<LoginConfig> {
+    let url: String = Input::new()
+        .with_prompt(format!("{}", style("Login URL").cyan()))
+        .with_initial_text(current.map(|l| l.url.clone()).unwrap_or_default())
+        .interact_text()?;
+    let body = Password::new()
+        .with_prompt(format!(
+            "{} (e.g. user=me&password=pw or JSON)",
+            style("Login body").cyan()
+        ))
+        .interact()?;
+    let token_path: String = Input::new()
         .with_prompt(format!(
-            "{} (comma-separated, leave empty to skip)",
-            style("Additional headers").cyan()
+            "{} (e.g. data.token; leave empty to use cookies)",
+            style("Token path in JSON response").cyan()
         ))
+        .with_initial_text(
+            current
+                .and_then(|l| l.token_path.clone())
+                .unwrap_or_default(),
+        )
         .allow_empty(true)
         .interact_text()?;
-    if !extra_headers.is_empty() {
-        config.headers = extra_headers
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
-    }
+    Ok(LoginConfig {
+        url: ensure_protocol(url.trim()),
+        body: secret::resolve_secret(&body)?,
+        content_type: None,
+        token_path: Some(token_path).filter(|p| !p.is_empty()),
+        header: DEFAULT_LOGIN_HEADER.to_string(),
+    })
+}
 
-    // Ramping mode
-    let ramping_modes = vec![
-        "Linear (50, 100, 150, 200...)",
-        "Exponential (50, 100, 200, 400...)",
-    ];
-    let mode_default = match state.ramping_mode {
-        RampingMode::Linear => 0,
-        RampingMode::Exponential => 1,
-    };
-    let mode_idx = Select::new()
-        .with_prompt(format!("{}", style("Ramping mode").cyan()))
-        .items(&ramping_modes)
-        .default(mode_default)
-        .interact()?;
-    config.ramping.mode = match mode_idx {
-        0 => RampingMode::Linear,
-        1 => RampingMode::Exponential,
-        _ => RampingMode::Linear,
+/// Ask for the TLS options, starting from the current ones; empty answers leave an option unset
+fn prompt_tls(current: &TlsConfig) -> Result<TlsConfig> {
+    let optional = |prompt: &str, initial: Option<&String>| -> Result<Option<String>> {
+        let value: String = Input::new()
+            .with_prompt(format!("{} (leave empty to skip)", style(prompt).cyan()))
+            .with_initial_text(initial.cloned().unwrap_or_default())
+            .allow_empty(true)
+            .interact_text()?;
+        Ok(Some(value.trim().to_string()).filter(|v| !v.is_empty()))
     };
-    state.ramping_mode = config.ramping.mode;
 
-    // Starting rate
-    config.ramping.start_rate = Input::new()
-        .with_prompt(format!("{}", style("Starting rate (req/s)").cyan()))
-        .default(state.start_rate)
-        .interact_text()?;
-    state.start_rate = config.ramping.start_rate;
+    let cert = optional("Client certificate (PEM file)", current.cert.as_ref())?;
+    let key = match cert {
+        Some(_) => Some(
+            Input::<String>::new()
+                .with_prompt(format!("{}", style("Client key (PEM file)").cyan()))
+                .with_initial_text(current.key.clone().unwrap_or_default())
+                .interact_text()?
+                .trim()
+                .to_string(),
+        ),
+        None => None,
+    };
+    let ca_certs = optional("Extra CA bundle (PEM file)", current.ca_certs.first())?
+        .into_iter()
+        .collect();
+    let sni = optional("SNI / Host name override", current.sni.as_ref())?;
+    let insecure = Confirm::new()
+        .with_prompt(format!(
+            "{}",
+            style("Skip certificate verification (insecure)?").cyan()
+        ))
+        .default(current.insecure)
+        .interact()?;
 
-    // Maximum rate
-    config.ramping.max_rate = Input::new()
-        .with_prompt(format!("{}", style("Maximum rate (req/s)").cyan()))
-        .default(state.max_rate)
-        .interact_text()?;
-    state.max_rate = config.ramping.max_rate;
+    let tls = TlsConfig {
+        ca_certs,
+        cert,
+        key,
+        sni,
+        insecure,
+    };
+    tls.validate()?;
+    Ok(tls)
+}
 
-    // Step size (only for linear)
-    if config.ramping.mode == RampingMode::Linear {
-        config.ramping.step = Input::new()
-            .with_prompt(format!("{}", style("Step size").cyan()))
-            .default(state.step)
+/// Ask for the response assertions, starting from the current ones; empty answers skip one
+fn prompt_validation(current: &ValidationConfig) -> Result<ValidationConfig> {
+    let optional = |prompt: &str, initial: Option<String>| -> Result<Option<String>> {
+        let value: String = Input::new()
+            .with_prompt(format!("{} (leave empty to skip)", style(prompt).cyan()))
+            .with_initial_text(initial.unwrap_or_default())
+            .allow_empty(true)
             .interact_text()?;
-        state.step = config.ramping.step;
-    }
-
-    // Duration per step
-    let durations = vec!["30 seconds (Recommended)", "60 seconds", "120 seconds"];
-    let dur_idx = Select::new()
-        .with_prompt(format!("{}", style("Duration per step").cyan()))
-        .items(&durations)
-        .default(state.duration_idx)
-        .interact()?;
-    state.duration_idx = dur_idx;
-    config.ramping.duration_seconds = match dur_idx {
-        0 => 30,
-        1 => 60,
-        2 => 120,
-        _ => 30,
+        Ok(Some(value.trim().to_string()).filter(|v| !v.is_empty()))
     };
 
-    // Threads
-    config.ramping.threads = Input::new()
-        .with_prompt(format!("{}", style("Threads").cyan()))
-        .default(state.threads)
-        .interact_text()?;
-    state.threads = config.ramping.threads;
-
-    // Connections
-    config.ramping.connections = Input::new()
-        .with_prompt(format!("{}", style("Connections").cyan()))
-        .default(state.connections)
-        .interact_text()?;
-    state.connections = config.ramping.connections;
-
-    // Breaking point thresholds
-    println!();
-    println!("{}", style("Breaking point thresholds:").yellow().bold());
+    let status = optional(
+        "Accepted status codes (e.g. 200,201)",
+        Some(
+            current
+                .status
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+        .filter(|s| !s.is_empty()),
+    )?
+    .map(|codes| {
+        codes
+            .split(',')
+            .map(|c| c.trim().parse::<u16>())
+            .collect::<Result<Vec<_>, _>>()
+    })
+    .transpose()
+    .map_err(|_| anyhow::anyhow!("Status codes must be numbers, e.g. 200,201"))?
+    .unwrap_or_default();
+    let body_contains = optional("Body must contain", current.body_contains.first().cloned())?;
+    let body_regex = optional("Body must match regex", current.body_regex.first().cloned())?;
+    let json_equals = optional(
+        "JSON value (PATH=VALUE, e.g. status=ok)",
+        current
+            .json_equals
+            .first()
+            .map(|(path, value)| format!("{}={}", path, value)),
+    )?
+    .map(|text| parse_json_assertion(&text))
+    .transpose()
+    .map_err(|e| anyhow::anyhow!(e))?;
+    let schema = optional("JSON Schema file", current.schema.clone())?;
+    let max_size = optional(
+        "Max body size (bytes)",
+        current.max_size.map(|b| b.to_string()),
+    )?
+    .map(|size| size.parse::<u64>())
+    .transpose()
+    .map_err(|_| anyhow::anyhow!("Max body size must be a number of bytes"))?;
 
-    config.thresholds.max_error_rate = Input::new()
-        .with_prompt(format!("{}", style("Max error rate (%)").cyan()))
-        .default(state.max_error_rate)
-        .interact_text()?;
-    state.max_error_rate = config.thresholds.max_error_rate;
+    let validation = ValidationConfig {
+        status,
+        body_contains: body_contains.into_iter().collect(),
+        body_regex: body_regex.into_iter().collect(),
+        json_equals: json_equals.into_iter().collect(),
+        schema,
+        max_size,
+        sample_rate: current.sample_rate,
+    };
+    if !validation.is_empty() {
+        // Compile now so a bad regex or schema is reported here rather than at start
+        Validator::new(
+            &validation,
+            &TlsConfig::default(),
+            true,
+            &StatusClasses::default(),
+            false,
+        )?;
+    }
+    Ok(validation)
+}
 
-    config.thresholds.max_p99_ms = Input::new()
-        .with_prompt(format!("{}", style("Max p99 latency (ms)").cyan()))
-        .default(state.max_p99_ms)
-        .interact_text()?;
-    state.max_p99_ms = config.thresholds.max_p99_ms;
+/// Save the current settings as a named preset, or load or delete one
+/// Presets hold the remembered settings (method, body, User-Agent, ramping, thresholds,
+/// warmup, cooldown, report folder) - never URLs, credentials or headers
+fn edit_presets(config: &mut BenchmarkConfig, state: &mut SessionState) -> Result<()> {
+    let presets = settings::load_presets()?;
+    let names: Vec<&String> = presets.keys().collect();
 
-    // Warmup period
-    let warmup_options = vec!["No warmup", "10 seconds (Recommended)", "30 seconds"];
-    let warmup_idx = Select::new()
-        .with_prompt(format!("{}", style("Warmup period before ramping").cyan()))
-        .items(&warmup_options)
-        .default(state.warmup_idx)
+    let mut actions = vec!["Save current settings as a preset"];
+    if !presets.is_empty() {
+        actions.push("Load a preset");
+        actions.push("Delete a preset");
+    }
+    actions.push("Back");
+    let action = Select::new()
+        .with_prompt(format!("{}", style("Presets").cyan()))
+        .items(&actions)
+        .default(0)
         .interact()?;
-    state.warmup_idx = warmup_idx;
-    config.warmup_seconds = match warmup_idx {
-        0 => 0,
-        1 => 10,
-        2 => 30,
-        _ => 10,
-    };
 
-    // Cooldown between steps
-    let cooldown_options = vec![
-        "No cooldown (Recommended)",
-        "3 seconds",
-        "5 seconds",
-        "10 seconds",
-    ];
-    let cooldown_idx = Select::new()
-        .with_prompt(format!("{}", style("Cooldown between steps").cyan()))
-        .items(&cooldown_options)
-        .default(state.cooldown_idx)
-        .interact()?;
-    state.cooldown_idx = cooldown_idx;
-    config.cooldown_seconds = match cooldown_idx {
-        0 => 0,
-        1 => 3,
-        2 => 5,
-        3 => 10,
-        _ => 5,
-    };
+    match actions[action] {
+        "Save current settings as a preset" => {
+            let name: String = Input::new()
+                .with_prompt(format!(
+                    "{} (e.g. staging API quick check)",
+                    style("Preset name").cyan()
+                ))
+                .interact_text()?;
+            let name = name.trim();
+            if name.is_empty() {
+                return Ok(());
+            }
+            if presets.contains_key(name)
+                && !Confirm::new()
+                    .with_prompt(format!("Replace the preset '{}'?", name))
+                    .default(false)
+                    .interact()?
+            {
+                return Ok(());
+            }
 
-    // Report file (txt)
-    let save_report = Confirm::new()
+            let mut preset = state.clone();
+            remember_settings(config, &mut preset);
+            preset.last_report_dir = config.report_dir.as_ref().map(PathBuf::from);
+            settings::save_preset(name, &preset)?;
+            println!("{} Saved preset '{}'", style("✓").green(), name);
+        }
+        "Load a preset" => {
+            let idx = Select::new()
+                .with_prompt(format!("{}", style("Preset to load").cyan()))
+                .items(&names)
+                .default(0)
+                .interact()?;
+            *state = presets[names[idx]].clone();
+            apply_session_defaults(config, state);
+            println!("{} Loaded preset '{}'", style("✓").green(), names[idx]);
+        }
+        "Delete a preset" => {
+            let idx = Select::new()
+                .with_prompt(format!("{}", style("Preset to delete").cyan()))
+                .items(&names)
+                .default(0)
+                .interact()?;
+            if Confirm::new()
+                .with_prompt(format!("Delete the preset '{}'?", names[idx]))
+                .default(false)
+                .interact()?
+            {
+                settings::delete_preset(names[idx])?;
+                println!("{} Deleted preset '{}'", style("✓").green(), names[idx]);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Ask whether to save a report and where
+fn edit_report(config: &mut BenchmarkConfig, state: &mut SessionState) -> Result<()> {
+    state.save_report = Confirm::new()
         .with_prompt(format!("{}", style("Save report to file?").cyan()))
         .default(state.save_report)
         .interact()?;
-    state.save_report = save_report;
-
-    if save_report {
-        // Use last report dir if available, otherwise use downloads
-        let default_dir = state
-            .last_report_dir
-            .clone()
-            .unwrap_or_else(get_downloads_dir)
-            .to_string_lossy()
-            .to_string();
-
-        let folder_input: String = Input::new()
-            .with_prompt(format!("{}", style("Report folder").cyan()))
-            .default(default_dir)
-            .interact_text()?;
+    if !state.save_report {
+        return Ok(());
+    }
 
-        // If user provided a file path, extract directory and use filename as name hint
-        let folder_path = std::path::Path::new(&folder_input);
-        let (report_dir, name_hint) = if folder_path.extension().is_some() {
-            // Looks like a file path - extract parent dir and stem
-            let parent = folder_path
-                .parent()
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_else(|| ".".to_string());
-            let stem = folder_path
-                .file_stem()
-                .map(|s| s.to_string_lossy().to_string());
-            (parent, stem)
-        } else {
-            (folder_input.clone(), None)
-        };
+    let folder_input: String = Input::new()
+        .with_prompt(format!("{}", style("Report folder").cyan()))
+        .default(
+            config
+                .report_dir
+                .clone()
+                .unwrap_or_else(|| get_downloads_dir().to_string_lossy().to_string()),
+        )
+        .interact_text()?;
 
-        // Update last_report_dir for next run
-        state.last_report_dir = Some(PathBuf::from(&report_dir));
+    // If user provided a file path, extract directory and use filename as name hint
+    let folder_path = std::path::Path::new(&folder_input);
+    let (report_dir, name_hint) = if folder_path.extension().is_some() {
+        // Looks like a file path - extract parent dir and stem
+        let parent = folder_path
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| ".".to_string());
+        let stem = folder_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string());
+        (parent, stem)
+    } else {
+        (folder_input.clone(), None)
+    };
+    config.report_dir = Some(report_dir);
 
-        // Default benchmark name - use URL if single target, otherwise generic name
-        // (NOT remembered - derived from URL each time)
-        let default_name = name_hint.unwrap_or_else(|| {
-            if config.urls.len() == 1 {
-                url_to_safe_name(&config.urls[0])
-            } else {
-                "ohabench_benchmark".to_string()
-            }
-        });
+    let default_name = name_hint
+        .or_else(|| config.report_name.clone())
+        .unwrap_or_else(|| default_report_name(config));
+    let benchmark_name: String = Input::new()
+        .with_prompt(format!("{}", style("Benchmark name").cyan()))
+        .default(default_name)
+        .interact_text()?;
+    config.report_name = Some(benchmark_name);
+    Ok(())
+}
 
-        let benchmark_name: String = Input::new()
-            .with_prompt(format!("{}", style("Benchmark name").cyan()))
-            .default(default_name)
-            .interact_text()?;
+/// Ask for a curl command, HAR file or OpenAPI spec and let the user pick one request
+fn prompt_import(source_idx: usize) -> Result<ImportedRequest> {
+    let requests = match source_idx {
+        1 => {
+            let command: String = Input::new()
+                .with_prompt(format!("{}", style("curl command").cyan()))
+                .interact_text()?;
+            import_curl(&command)?
+        }
+        2 => {
+            let path: String = Input::new()
+                .with_prompt(format!("{}", style("HAR file").cyan()))
+                .interact_text()?;
+            import_har(path.trim())?
+        }
+        _ => {
+            let path: String = Input::new()
+                .with_prompt(format!("{}", style("OpenAPI spec (JSON or YAML)").cyan()))
+                .interact_text()?;
+            import_openapi(path.trim())?
+        }
+    };
 
-        config.report_dir = Some(report_dir);
-        config.report_name = Some(benchmark_name);
+    match requests.len() {
+        0 => anyhow::bail!("No requests found to import"),
+        1 => Ok(requests.into_iter().next().unwrap()),
+        _ => {
+            let names: Vec<&str> = requests.iter().map(|r| r.name.as_str()).collect();
+            let idx = Select::new()
+                .with_prompt(format!("{}", style("Request to benchmark").cyan()))
+                .items(&names)
+                .default(0)
+                .max_length(15)
+                .interact()?;
+            Ok(requests[idx].clone())
+        }
     }
-
-    Ok(config)
 }
 
 /// Build config from CLI args
-pub fn config_from_args(args: &crate::cli::Args) -> BenchmarkConfig {
+/// Secret options are resolved (env:, file:, stdin) and registered for redaction
+pub fn config_from_args(args: &crate::cli::Args) -> Result<BenchmarkConfig> {
     use crate::user_agent::resolve_user_agent;
 
-    BenchmarkConfig {
-        urls: args.url.iter().map(|u| ensure_protocol(u)).collect(),
+    if let Some(identity) = args.identity.iter().find(|h| !h.contains(':')) {
+        anyhow::bail!(
+            "--identity must be a header (Name: value), got '{}'",
+            identity
+        );
+    }
+
+    let config = BenchmarkConfig {
+        urls: args
+            .url
+            .iter()
+            .map(|u| ensure_protocol(u))
+            .chain(args.targets_file.iter().map(|f| format!("@{}", f)))
+            .collect(),
         method: args.method,
         body: args.body.clone(),
+        body_file: args.body_file.clone(),
+        content_type: args.content_type.clone(),
+        data_file: args.data_file.clone(),
+        body_variants: args.body_variants,
+        url_variants: args.url_variants,
         user_agent: resolve_user_agent(&args.user_agent),
         auth: AuthConfig {
             auth_type: args.auth_type,
             username: args.auth_user.clone(),
-            password: args.auth_pass.clone(),
-            token: args.auth_token.clone(),
-            custom_header: args.auth_header.clone(),
+            password: secret::resolve_optional(args.auth_pass.as_deref())?,
+            token: secret::resolve_optional(args.auth_token.as_deref())?,
+            custom_header: secret::resolve_optional(args.auth_header.as_deref())?,
+            token_url: args.token_url.clone(),
+            client_id: args.client_id.clone(),
+            client_secret: secret::resolve_optional(args.client_secret.as_deref())?,
+            scope: args.oauth_scope.clone(),
+            key_id: args.key_id.clone(),
+            signing_key: secret::resolve_optional(args.signing_key.as_deref())?,
+            session_token: secret::resolve_optional(args.aws_session_token.as_deref())?,
+            region: args.aws_region.clone(),
+            service: args.aws_service.clone(),
+            algorithm: Some(args.jwt_algorithm.clone()),
+            claims: args.jwt_claims.clone(),
+            signature_header: Some(args.signature_header.clone()),
+            signature_ttl: args.signature_ttl.max(1),
+        },
+        login: match args.login_url {
+            Some(ref url) => Some(LoginConfig {
+                url: ensure_protocol(url),
+                body: secret::resolve_optional(args.login_body.as_deref())?.unwrap_or_default(),
+                content_type: args.login_content_type.clone(),
+                token_path: args.login_token_path.clone(),
+                header: args.login_header.clone(),
+            }),
+            None => None,
+        },
+        tls: TlsConfig {
+            ca_certs: args.cacert.clone(),
+            cert: args.cert.clone(),
+            key: args.key.clone(),
+            sni: args.sni.clone(),
+            insecure: args.insecure,
         },
         headers: args.headers.clone(),
+        status_classes: StatusClasses {
+            rules: args.status_class.clone(),
+        },
+        rate_limit_headers: !args.no_rate_limit_headers,
+        identities: args.identity.clone(),
+        probe_past_limit: args.probe_past_limit,
+        workers: args.worker.clone(),
+        worker_token: secret::resolve_optional(args.worker_token.as_deref())?,
+        metrics: MetricsConfig {
+            url: args.metrics_url.clone(),
+            command: args.metrics_command.clone(),
+            series: args.metric.clone(),
+        },
+        hooks: HookConfig {
+            before_run: args.hook_before_run.clone(),
+            before_step: args.hook_before_step.clone(),
+            after_step: args.hook_after_step.clone(),
+            on_break: args.hook_on_break.clone(),
+            after_run: args.hook_after_run.clone(),
+            veto: args.hook_veto,
+        },
+        notify: notify_from_args(args)?,
+        validation: ValidationConfig {
+            status: args.expect_status.clone(),
+            body_contains: args.expect_body_contains.clone(),
+            body_regex: args.expect_body_regex.clone(),
+            json_equals: args.expect_json.clone(),
+            schema: args.expect_schema.clone(),
+            max_size: args.expect_max_size,
+            sample_rate: args.sample_rate.max(1),
+        },
         ramping: RampingConfig {
             mode: args.mode,
             start_rate: args.start_rate,
             max_rate: args.max_rate,
             step: args.step,
             duration_seconds: args.duration,
+            adaptive: args.adaptive.then(|| AdaptiveDuration {
+                min_seconds: args.adaptive_min.max(1),
+                max_seconds: args.adaptive_max.max(args.adaptive_min).max(1),
+                tolerance_percent: args.adaptive_tolerance,
+            }),
             threads: args.threads,
             connections: args.connections,
         },
@@ -448,16 +1579,76 @@ pub fn config_from_args(args: &crate::cli::Args) -> BenchmarkConfig {
             max_error_rate: args.max_error_rate,
             max_p99_ms: args.max_p99,
         },
+        abort: AbortConfig {
+            margin_percent: args.abort_margin,
+            window_seconds: args.abort_window.max(1),
+            cutoff_error_rate: args.cutoff_error_rate,
+            cutoff_p99_ms: args.cutoff_p99,
+        },
         warmup_seconds: args.warmup,
         cooldown_seconds: args.cooldown,
+        live: args.live,
+        live_interval: args.live_interval.max(1),
         report_dir: args.report_dir.clone(),
         report_name: args.report_name.clone(),
+        scenario: args.scenario.clone(),
+        gates: GateConfig {
+            require_rate: args.require_rate,
+            max_p99_at: args.max_p99_at.clone(),
+            fail_on: args.fail_on.clone(),
+        },
+    };
+
+    secret::register_config(&config);
+    Ok(config)
+}
+
+/// Collect the notification sinks; webhook URLs are secrets (they embed a token)
+fn notify_from_args(args: &crate::cli::Args) -> Result<NotifyConfig> {
+    let mut sinks = Vec::new();
+    for url in &args.notify_webhook {
+        sinks.push(Sink::Webhook(secret::resolve_secret(url)?));
     }
+    for url in &args.notify_slack {
+        sinks.push(Sink::Slack(secret::resolve_secret(url)?));
+    }
+    for url in &args.notify_teams {
+        sinks.push(Sink::Teams(secret::resolve_secret(url)?));
+    }
+    for command in &args.notify_command {
+        sinks.push(Sink::Command(command.clone()));
+    }
+    Ok(NotifyConfig {
+        sinks,
+        events: args.notify_on.clone(),
+    })
+}
+
+/// Build the configs to run from CLI args
+/// Without `--import-*` options this is the single config from `config_from_args`; imported
+/// requests are grouped so requests sharing method, headers, body and auth run as one config
+pub fn configs_from_args(args: &crate::cli::Args) -> Result<Vec<BenchmarkConfig>> {
+    let base = config_from_args(args)?;
+
+    if args.import_curl.is_none() && args.import_har.is_none() && args.import_openapi.is_none() {
+        return Ok(vec![base]);
+    }
+
+    let requests = import_from_args(args)?;
+    let mut configs = configs_from_imports(&base, &requests);
+
+    // Explicit --url/--targets-file targets run alongside the imported ones
+    if !base.urls.is_empty() {
+        configs.insert(0, base);
+    }
+
+    Ok(configs)
 }
 
 /// Ensure URL has a protocol, defaulting to https://
-fn ensure_protocol(url: &str) -> String {
-    if url.starts_with("http://") || url.starts_with("https://") {
+/// `@path` targets file entries are returned unchanged
+pub fn ensure_protocol(url: &str) -> String {
+    if url.starts_with("http://") || url.starts_with("https://") || url.starts_with('@') {
         url.to_string()
     } else {
         format!("https://{}", url)
@@ -465,21 +1656,14 @@ fn ensure_protocol(url: &str) -> String {
 }
 
 /// Convert URL to a safe filename (without extension)
-fn url_to_safe_name(url: &str) -> String {
+pub fn url_to_safe_name(url: &str) -> String {
     let mut name = url.to_string();
 
     // Remove protocol
     name = name.replace("https://", "").replace("http://", "");
 
     // Replace unsafe characters
-    name = name
-        .replace('/', "_")
-        .replace(':', "_")
-        .replace('?', "_")
-        .replace('&', "_")
-        .replace('=', "_")
-        .replace('#', "_")
-        .replace(' ', "_");
+    name = name.replace(['/', ':', '?', '&', '=', '#', ' '], "_");
 
     // Remove trailing underscores
     while name.ends_with('_') {
@@ -0,0 +1,330 @@
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::runner::shell_command;
+use crate::tls::{self, TlsConfig};
+
+/// Timeout for one metrics scrape
+const SCRAPE_TIMEOUT_SECONDS: u64 = 10;
+
+/// Series reported when none are selected: the first alternative the endpoint exposes is used
+const DEFAULT_SERIES: &[(&str, &str)] = &[
+    ("cpu", "process_cpu_seconds_total"),
+    ("memory", "process_resident_memory_bytes"),
+    (
+        "gc",
+        "go_gc_duration_seconds_sum|jvm_gc_pause_seconds_sum|nodejs_gc_duration_seconds_sum",
+    ),
+    (
+        "db_pool",
+        "hikaricp_connections_active|pgxpool_acquired_conns|go_sql_in_use_connections|db_client_connections_usage",
+    ),
+];
+
+/// Where server metrics are scraped from at the start and end of each step
+#[derive(Debug, Clone, Default)]
+pub struct MetricsConfig {
+    /// Prometheus text-format endpoint
+    pub url: Option<String>,
+    /// Local command printing Prometheus text format
+    pub command: Option<String>,
+    /// Selected series (the defaults when empty)
+    pub series: Vec<MetricSeries>,
+}
+
+impl MetricsConfig {
+    /// Whether a metrics source is configured
+    pub fn is_enabled(&self) -> bool {
+        self.url.is_some() || self.command.is_some()
+    }
+
+    /// The selected series, or the default CPU, memory, GC and DB pool series
+    pub fn series(&self) -> Vec<MetricSeries> {
+        if self.series.is_empty() {
+            DEFAULT_SERIES
+                .iter()
+                .map(|(label, query)| parse_metric_series(&format!("{}={}", label, query)).unwrap())
+                .collect()
+        } else {
+            self.series.clone()
+        }
+    }
+}
+
+/// A reported series: a label and the metric(s) it reads, e.g.
+/// `db_pool=hikaricp_connections_active{pool="main"}`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSeries {
+    pub label: String,
+    /// Alternatives, the first one present is used
+    queries: Vec<Query>,
+}
+
+impl std::fmt::Display for MetricSeries {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+/// Label pairs of a sample or query
+type Labels = Vec<(String, String)>;
+
+/// A metric name with the labels a sample must have
+#[derive(Debug, Clone, PartialEq)]
+struct Query {
+    name: String,
+    labels: Labels,
+}
+
+/// Parse `LABEL=METRIC`, where METRIC is `name`, `name{key="value",...}` or alternatives
+/// separated by `|`
+pub fn parse_metric_series(value: &str) -> Result<MetricSeries, String> {
+    let Some((label, queries)) = value.split_once('=') else {
+        return Err(format!("expected LABEL=METRIC, got '{}'", value));
+    };
+    let label = label.trim();
+    if label.is_empty() {
+        return Err(format!("missing label in '{}'", value));
+    }
+    let queries = queries
+        .split('|')
+        .map(|q| parse_query(q.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(MetricSeries {
+        label: label.to_string(),
+        queries,
+    })
+}
+
+fn parse_query(query: &str) -> Result<Query, String> {
+    let (name, labels) = match query.split_once('{') {
+        Some((name, rest)) => {
+            let inner = rest
+                .strip_suffix('}')
+                .ok_or_else(|| format!("unclosed '{{' in '{}'", query))?;
+            (name, parse_labels(inner))
+        }
+        None => (query, Vec::new()),
+    };
+    if name.is_empty() {
+        return Err(format!("missing metric name in '{}'", query));
+    }
+    Ok(Query {
+        name: name.to_string(),
+        labels,
+    })
+}
+
+/// Parse `key="value",key2="value2"` (quotes optional)
+fn parse_labels(text: &str) -> Labels {
+    let mut labels = Vec::new();
+    let mut rest = text.trim();
+    while let Some((key, after)) = rest.split_once('=') {
+        let after = after.trim_start();
+        let (value, remaining) = match after.strip_prefix('"') {
+            Some(quoted) => {
+                // Find the closing quote, skipping escaped ones
+                let mut end = quoted.len();
+                let mut escaped = false;
+                for (i, c) in quoted.char_indices() {
+                    match c {
+                        '\\' if !escaped => escaped = true,
+                        '"' if !escaped => {
+                            end = i;
+                            break;
+                        }
+                        _ => escaped = false,
+                    }
+                }
+                let value = quoted[..end].replace("\\\"", "\"").replace("\\\\", "\\");
+                (value, quoted.get(end + 1..).unwrap_or(""))
+            }
+            None => match after.split_once(',') {
+                Some((value, remaining)) => (value.trim().to_string(), remaining),
+                None => (after.trim().to_string(), ""),
+            },
+        };
+        labels.push((key.trim().to_string(), value));
+        rest = remaining.trim_start().trim_start_matches(',').trim_start();
+    }
+    labels
+}
+
+/// One parsed scrape
+#[derive(Debug, Default)]
+pub struct Scrape {
+    /// (name, labels, value)
+    samples: Vec<(String, Labels, f64)>,
+    /// Declared `# TYPE` per metric family
+    types: HashMap<String, String>,
+}
+
+impl Scrape {
+    /// Parse Prometheus text exposition format
+    pub fn parse(text: &str) -> Self {
+        let mut scrape = Scrape::default();
+        for line in text.lines() {
+            let line = line.trim();
+            if let Some(declaration) = line.strip_prefix("# TYPE ") {
+                let mut parts = declaration.split_whitespace();
+                if let (Some(name), Some(kind)) = (parts.next(), parts.next()) {
+                    scrape.types.insert(name.to_string(), kind.to_string());
+                }
+                continue;
+            }
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (series, value) = match line.rfind('}') {
+                Some(close) => (&line[..=close], line[close + 1..].trim()),
+                None => match line.split_once(char::is_whitespace) {
+                    Some((name, value)) => (name, value.trim()),
+                    None => continue,
+                },
+            };
+            // A trailing timestamp follows the value
+            let Some(value) = value.split_whitespace().next().and_then(parse_value) else {
+                continue;
+            };
+            let (name, labels) = match series.split_once('{') {
+                Some((name, rest)) => (name, parse_labels(rest.trim_end_matches('}'))),
+                None => (series, Vec::new()),
+            };
+            scrape.samples.push((name.to_string(), labels, value));
+        }
+        scrape
+    }
+
+    /// Sum of the samples matching a query, None when there are none
+    fn sum(&self, query: &Query) -> Option<f64> {
+        let mut found = false;
+        let mut total = 0.0;
+        for (name, labels, value) in &self.samples {
+            if *name == query.name && query.labels.iter().all(|l| labels.contains(l)) {
+                found = true;
+                total += value;
+            }
+        }
+        found.then_some(total)
+    }
+
+    /// Whether a metric only ever grows (counters, histogram/summary sums and counts)
+    fn is_counter(&self, name: &str) -> bool {
+        let family = ["_total", "_sum", "_count"]
+            .iter()
+            .find_map(|suffix| name.strip_suffix(suffix))
+            .unwrap_or(name);
+        match self.types.get(name).or_else(|| self.types.get(family)) {
+            Some(kind) => matches!(kind.as_str(), "counter" | "histogram" | "summary"),
+            None => family != name,
+        }
+    }
+}
+
+fn parse_value(text: &str) -> Option<f64> {
+    match text {
+        "+Inf" => Some(f64::INFINITY),
+        "-Inf" => Some(f64::NEG_INFINITY),
+        _ => text.parse().ok().filter(|v: &f64| !v.is_nan()),
+    }
+}
+
+/// Scrapes the configured source and turns two scrapes into per-step values
+#[derive(Debug)]
+pub struct MetricsScraper {
+    url: Option<String>,
+    command: Option<String>,
+    series: Vec<MetricSeries>,
+    agent: ureq::Agent,
+}
+
+impl MetricsScraper {
+    /// Set up the scraper and scrape once, so an unreachable endpoint fails before the ramp
+    pub fn new(config: &MetricsConfig, tls: &TlsConfig) -> Result<Self> {
+        if config.url.is_some() && config.command.is_some() {
+            bail!("--metrics-url and --metrics-command can't be used together");
+        }
+        let agent = tls::agent_builder(tls)?
+            .timeout(Duration::from_secs(SCRAPE_TIMEOUT_SECONDS))
+            .build();
+        let scraper = Self {
+            url: config.url.clone(),
+            command: config.command.clone(),
+            series: config.series(),
+            agent,
+        };
+        scraper.scrape()?;
+        Ok(scraper)
+    }
+
+    /// Fetch and parse the current metrics
+    pub fn scrape(&self) -> Result<Scrape> {
+        let text = if let Some(ref url) = self.url {
+            self.agent
+                .get(url)
+                .call()
+                .with_context(|| format!("Failed to scrape metrics from {}", url))?
+                .into_string()
+                .context("Failed to read metrics response")?
+        } else if let Some(ref command) = self.command {
+            let output = shell_command(command)
+                .output()
+                .with_context(|| format!("Failed to run metrics command '{}'", command))?;
+            if !output.status.success() {
+                bail!(
+                    "Metrics command '{}' failed: {}",
+                    command,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        } else {
+            bail!("No metrics source configured");
+        };
+        Ok(Scrape::parse(&text))
+    }
+
+    /// Values of the selected series over a step: counters as their increase per second,
+    /// gauges as their value at the end of the step
+    pub fn step_values(&self, start: &Scrape, end: &Scrape, seconds: u32) -> Vec<(String, f64)> {
+        let mut values = Vec::new();
+        for series in &self.series {
+            let Some((query, last)) = series
+                .queries
+                .iter()
+                .find_map(|q| end.sum(q).map(|v| (q, v)))
+            else {
+                continue;
+            };
+            let value = if end.is_counter(&query.name) {
+                let Some(first) = start.sum(query) else {
+                    continue;
+                };
+                // A counter that went down was reset (server restart): count from zero
+                let increase = if last >= first { last - first } else { last };
+                increase / seconds.max(1) as f64
+            } else {
+                last
+            };
+            values.push((series.label.clone(), value));
+        }
+        values
+    }
+}
+
+/// Format a metric value compactly, e.g. 1.25, 512.0M, 3.4k
+pub fn format_metric_value(value: f64) -> String {
+    let abs = value.abs();
+    if abs >= 1e9 {
+        format!("{:.1}G", value / 1e9)
+    } else if abs >= 1e6 {
+        format!("{:.1}M", value / 1e6)
+    } else if abs >= 1e4 {
+        format!("{:.1}k", value / 1e3)
+    } else if abs >= 100.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
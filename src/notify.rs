@@ -0,0 +1,333 @@
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use console::style;
+use serde::Serialize;
+use serde_json::json;
+use std::io::Write;
+use std::process::Stdio;
+use std::time::Duration;
+
+use crate::analysis::{BenchmarkSummary, BreakReason};
+use crate::hooks::UrlSummary;
+use crate::output::UrlBenchmarkResults;
+use crate::runner::{shell_command, BenchmarkResult};
+use crate::secret;
+
+/// Timeout for delivering a notification to a webhook
+const SEND_TIMEOUT_SECONDS: u64 = 10;
+
+/// Moments of a run that send a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotifyEvent {
+    /// The run finished (with every URL's summary)
+    Complete,
+    /// The first step that ended a ramp
+    Break,
+    /// The run or a URL's ramp failed with an error
+    Failure,
+}
+
+impl std::fmt::Display for NotifyEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.to_possible_value() {
+            Some(value) => write!(f, "{}", value.get_name()),
+            None => write!(f, "{:?}", self),
+        }
+    }
+}
+
+/// Where notifications are delivered
+#[derive(Debug, Clone)]
+pub enum Sink {
+    /// POST of the notification as JSON
+    Webhook(String),
+    /// Slack incoming webhook
+    Slack(String),
+    /// Microsoft Teams incoming webhook (message card)
+    Teams(String),
+    /// Local command, given the notification as JSON on stdin
+    Command(String),
+}
+
+impl Sink {
+    fn name(&self) -> &'static str {
+        match self {
+            Sink::Webhook(_) => "webhook",
+            Sink::Slack(_) => "slack",
+            Sink::Teams(_) => "teams",
+            Sink::Command(_) => "command",
+        }
+    }
+}
+
+/// Notification sinks and the events they're sent for
+#[derive(Debug, Clone, Default)]
+pub struct NotifyConfig {
+    pub sinks: Vec<Sink>,
+    /// Events to notify on (empty means all of them)
+    pub events: Vec<NotifyEvent>,
+}
+
+impl NotifyConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.sinks.is_empty()
+    }
+
+    /// Events that send a notification
+    pub fn events(&self) -> Vec<NotifyEvent> {
+        if self.events.is_empty() {
+            vec![
+                NotifyEvent::Complete,
+                NotifyEvent::Break,
+                NotifyEvent::Failure,
+            ]
+        } else {
+            self.events.clone()
+        }
+    }
+
+    /// Describe the sinks (never their URLs, which usually embed a token) and events
+    pub fn describe(&self) -> String {
+        let mut sinks: Vec<&str> = Vec::new();
+        for sink in &self.sinks {
+            if !sinks.contains(&sink.name()) {
+                sinks.push(sink.name());
+            }
+        }
+        let events: Vec<String> = self.events().iter().map(|e| e.to_string()).collect();
+        format!("{} on {}", sinks.join(", "), events.join(", "))
+    }
+}
+
+/// A notification, sent as-is to webhooks and command sinks and as a chat message to Slack/Teams
+#[derive(Debug, Serialize)]
+pub struct Notification<'a> {
+    pub event: NotifyEvent,
+    /// One-line headline
+    pub title: String,
+    /// Key results, one line each (the report excerpt)
+    pub details: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<&'a str>,
+    /// Breaking step (break)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<&'a BenchmarkResult>,
+    /// Per-URL summaries (complete)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub summaries: Vec<UrlSummary<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Saved report and graph (complete)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph: Option<&'a str>,
+}
+
+impl<'a> Notification<'a> {
+    fn new(event: NotifyEvent, title: String) -> Self {
+        Self {
+            event,
+            title,
+            details: Vec::new(),
+            url: None,
+            result: None,
+            summaries: Vec::new(),
+            error: None,
+            report: None,
+            graph: None,
+        }
+    }
+
+    /// The run finished
+    pub fn complete(
+        url_results: &'a [UrlBenchmarkResults],
+        report: Option<&'a str>,
+        graph: Option<&'a str>,
+    ) -> Self {
+        let title = match url_results {
+            [single] => format!("ohabench finished {}", single.url),
+            _ => format!("ohabench finished {} URLs", url_results.len()),
+        };
+        Self {
+            details: url_results
+                .iter()
+                .map(|r| describe_summary(&r.url, &r.summary))
+                .collect(),
+            summaries: url_results
+                .iter()
+                .map(|r| UrlSummary {
+                    url: &r.url,
+                    summary: &r.summary,
+                })
+                .collect(),
+            report,
+            graph,
+            ..Self::new(NotifyEvent::Complete, title)
+        }
+    }
+
+    /// A step ended the ramp of a URL
+    pub fn breaking(url: &'a str, result: &'a BenchmarkResult, reason: &BreakReason) -> Self {
+        Self {
+            details: vec![
+                reason.to_string(),
+                format!(
+                    "{:.1} req/s achieved, {:.2}% errors, p99 {:.0}ms",
+                    result.actual_rate, result.error_rate, result.p99_latency_ms
+                ),
+            ],
+            url: Some(url),
+            result: Some(result),
+            ..Self::new(
+                NotifyEvent::Break,
+                format!("{} broke at {} req/s", url, result.target_rate),
+            )
+        }
+    }
+
+    /// The run, or the ramp of `url`, failed
+    pub fn failure(url: Option<&'a str>, error: &anyhow::Error) -> Self {
+        let error = secret::redact(&format!("{:#}", error));
+        let title = match url {
+            Some(url) => format!("ohabench failed on {}", url),
+            None => "ohabench run failed".to_string(),
+        };
+        Self {
+            details: vec![error.clone()],
+            url,
+            error: Some(error),
+            ..Self::new(NotifyEvent::Failure, title)
+        }
+    }
+
+    /// Details plus the saved files, for chat messages
+    fn lines(&self) -> Vec<String> {
+        let mut lines = self.details.clone();
+        if let Some(report) = self.report {
+            lines.push(format!("Report: {}", report));
+        }
+        if let Some(graph) = self.graph {
+            lines.push(format!("Graph: {}", graph));
+        }
+        lines
+    }
+}
+
+/// One line per URL, e.g. "https://api: broke at 300 req/s (...); last stable 200, ..."
+fn describe_summary(url: &str, summary: &BenchmarkSummary) -> String {
+    let outcome = match summary.breaking_point_rate {
+        Some(rate) => format!("broke at {} req/s ({})", rate, summary.break_reason),
+        None => "no breaking point reached".to_string(),
+    };
+    let stable = match (summary.last_stable_rate, summary.recommended_rate) {
+        (Some(stable), Some(recommended)) => format!(
+            "; last stable {}, recommended {} req/s",
+            stable, recommended
+        ),
+        (Some(stable), None) => format!("; last stable {} req/s", stable),
+        _ => "; no stable rate".to_string(),
+    };
+    format!("{}: {}{}", url, outcome, stable)
+}
+
+/// Send a notification to every sink, if its event is enabled
+/// Delivery failures are reported and never stop the run
+pub fn send(config: &NotifyConfig, notification: &Notification) {
+    if !config.is_enabled() || !config.events().contains(&notification.event) {
+        return;
+    }
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(SEND_TIMEOUT_SECONDS))
+        .build();
+    for sink in &config.sinks {
+        if let Err(e) = deliver(&agent, sink, notification) {
+            eprintln!(
+                "{} {} notification failed: {}",
+                style("✗").red(),
+                sink.name(),
+                secret::redact(&format!("{:#}", e))
+            );
+        }
+    }
+}
+
+fn deliver(agent: &ureq::Agent, sink: &Sink, notification: &Notification) -> Result<()> {
+    let (url, payload) = match sink {
+        Sink::Webhook(url) => (url, serde_json::to_value(notification)?),
+        Sink::Slack(url) => (url, slack_message(notification)),
+        Sink::Teams(url) => (url, teams_message(notification)),
+        Sink::Command(command) => return run_command(command, notification),
+    };
+    match agent
+        .post(url)
+        .set("Content-Type", "application/json")
+        .send_string(&payload.to_string())
+    {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(code, _)) => bail!("HTTP {}", code),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Slack incoming webhook message (mrkdwn text)
+fn slack_message(notification: &Notification) -> serde_json::Value {
+    let mut text = format!("*{}*", notification.title);
+    for line in notification.lines() {
+        text.push('\n');
+        text.push_str(&line);
+    }
+    json!({ "text": text })
+}
+
+/// Microsoft Teams incoming webhook message card
+fn teams_message(notification: &Notification) -> serde_json::Value {
+    let color = match notification.event {
+        NotifyEvent::Complete => "2E7D32",
+        NotifyEvent::Break => "F9A825",
+        NotifyEvent::Failure => "C62828",
+    };
+    json!({
+        "@type": "MessageCard",
+        "@context": "https://schema.org/extensions",
+        "summary": notification.title,
+        "themeColor": color,
+        "title": notification.title,
+        // Teams markdown needs a blank line for a line break
+        "text": notification.lines().join("\n\n"),
+    })
+}
+
+/// Run a command sink with the notification as JSON on stdin
+fn run_command(command: &str, notification: &Notification) -> Result<()> {
+    let mut cmd = shell_command(command);
+    cmd.env("OHABENCH_NOTIFY_EVENT", notification.event.to_string());
+    cmd.env("OHABENCH_MESSAGE", &notification.title);
+    if let Some(report) = notification.report {
+        cmd.env("OHABENCH_REPORT", report);
+    }
+    if let Some(graph) = notification.graph {
+        cmd.env("OHABENCH_GRAPH", graph);
+    }
+    cmd.stdin(Stdio::piped());
+    cmd.stdout(Stdio::null());
+    cmd.stderr(Stdio::piped());
+
+    let mut child = cmd
+        .spawn()
+        .with_context(|| format!("Failed to run '{}'", command))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let json = serde_json::to_string(notification)?;
+        stdin.write_all(json.as_bytes()).ok();
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        match stderr.lines().rev().find(|l| !l.trim().is_empty()) {
+            Some(line) => bail!("'{}' failed: {}", command, line.trim()),
+            None => bail!("'{}' failed ({})", command, output.status),
+        }
+    }
+    Ok(())
+}
@@ -2,9 +2,19 @@ use console::style;
 use std::fmt::Write as FmtWrite;
 use std::io::Write;
 
-use crate::analysis::{AnalysisResult, BenchmarkSummary, StepStatus};
-use crate::config::BenchmarkConfig;
+use crate::analysis::{limited_share, AnalysisResult, BenchmarkSummary, BreakReason, StepStatus};
+use crate::cli::AuthType;
+use crate::config::{AbortConfig, BenchmarkConfig, RampingConfig};
+use crate::gate::Verdict;
+use crate::history::{common_tested_rate, group_runs, RunRecord};
+use crate::hooks::HookConfig;
+use crate::login::LoginConfig;
+use crate::metrics::{format_metric_value, MetricsConfig};
 use crate::runner::BenchmarkResult;
+use crate::secret::{redact, redact_header};
+use crate::status::{ErrorKind, StatusClasses};
+use crate::tls::TlsConfig;
+use crate::validate::ValidationConfig;
 
 const SEPARATOR: &str =
     "═══════════════════════════════════════════════════════════════════════════════";
@@ -34,6 +44,64 @@ pub fn print_config_summary(config: &BenchmarkConfig) {
         }
     }
     println!("{:<14} {}", style("Method:").cyan(), config.method);
+    if let Some(body) = describe_body(config) {
+        println!("{:<14} {}", style("Body:").cyan(), body);
+    }
+    if let Some(auth) = describe_auth(config) {
+        println!("{:<14} {}", style("Auth:").cyan(), auth);
+    }
+    if let Some(ref login) = config.login {
+        println!("{:<14} {}", style("Login:").cyan(), describe_login(login));
+    }
+    if !config.tls.is_empty() {
+        println!("{:<14} {}", style("TLS:").cyan(), describe_tls(&config.tls));
+    }
+    for (i, header) in config.headers.iter().enumerate() {
+        let label = if i == 0 { "Headers:" } else { "" };
+        println!("{:<14} {}", style(label).cyan(), redact_header(header));
+    }
+    if !config.validation.is_empty() {
+        println!(
+            "{:<14} {}",
+            style("Checks:").cyan(),
+            describe_validation(&config.validation)
+        );
+    }
+    if !config.status_classes.is_default() {
+        println!(
+            "{:<14} {}",
+            style("Status:").cyan(),
+            describe_status_classes(&config.status_classes)
+        );
+    }
+    if !config.workers.is_empty() {
+        println!(
+            "{:<14} {}",
+            style("Workers:").cyan(),
+            config.workers.join(", ")
+        );
+    }
+    if config.metrics.is_enabled() {
+        println!(
+            "{:<14} {}",
+            style("Metrics:").cyan(),
+            describe_metrics(&config.metrics)
+        );
+    }
+    if !config.hooks.is_empty() {
+        println!(
+            "{:<14} {}",
+            style("Hooks:").cyan(),
+            describe_hooks(&config.hooks)
+        );
+    }
+    if config.notify.is_enabled() {
+        println!(
+            "{:<14} {}",
+            style("Notify:").cyan(),
+            config.notify.describe()
+        );
+    }
     println!(
         "{:<14} {} ramping",
         style("Mode:").cyan(),
@@ -46,9 +114,9 @@ pub fn print_config_summary(config: &BenchmarkConfig) {
         config.ramping.max_rate
     );
     println!(
-        "{:<14} {}s per step",
+        "{:<14} {}",
         style("Duration:").cyan(),
-        config.ramping.duration_seconds
+        describe_duration(&config.ramping)
     );
     println!(
         "{:<14} Error rate > {}% OR p99 > {}ms",
@@ -56,9 +124,349 @@ pub fn print_config_summary(config: &BenchmarkConfig) {
         config.thresholds.max_error_rate,
         config.thresholds.max_p99_ms
     );
+    if config.abort.is_enabled() {
+        println!(
+            "{:<14} {}",
+            style("Abort:").cyan(),
+            describe_abort(&config.abort)
+        );
+    }
     println!("{}", style(SEPARATOR).dim());
 }
 
+/// Describe the configured request body source for summaries
+fn describe_body(config: &BenchmarkConfig) -> Option<String> {
+    let source = match (&config.body, &config.body_file) {
+        (_, Some(path)) => format!("file {}", path),
+        (Some(body), None) => format!("{} bytes inline", body.len()),
+        (None, None) => return None,
+    };
+    let mut parts = vec![source];
+    if config.body.as_deref().is_some_and(|b| b.contains("{{")) {
+        parts.push(format!(
+            "templated, {} variants per step",
+            config.body_variants
+        ));
+    }
+    if let Some(ref content_type) = config.content_type {
+        parts.push(content_type.clone());
+    }
+    if let Some(ref data_file) = config.data_file {
+        parts.push(format!("data from {}", data_file));
+    }
+    Some(parts.join(", "))
+}
+
+/// Describe the configured auth without revealing any secret
+pub fn describe_auth(config: &BenchmarkConfig) -> Option<String> {
+    let auth = &config.auth;
+    let detail = match auth.auth_type {
+        AuthType::None => return None,
+        AuthType::Basic => auth.username.clone().map(|u| format!("user {}", u)),
+        AuthType::Bearer => None,
+        AuthType::Header => auth.custom_header.as_deref().map(redact_header),
+        AuthType::OAuth2 => auth.token_url.clone(),
+        AuthType::Hmac => auth.signature_header.clone(),
+        AuthType::SigV4 => match (&auth.region, &auth.service) {
+            (Some(region), Some(service)) => Some(format!("{}/{}", region, service)),
+            _ => None,
+        },
+        AuthType::Jwt => auth.algorithm.clone(),
+    };
+    Some(match detail {
+        Some(detail) => format!("{} ({})", auth.auth_type, detail),
+        None => auth.auth_type.to_string(),
+    })
+}
+
+/// Describe the login pre-step without revealing the credentials
+pub fn describe_login(login: &LoginConfig) -> String {
+    match login.token_path {
+        Some(ref path) => format!("{} (token from {})", login.url, path),
+        None => format!("{} (session cookies)", login.url),
+    }
+}
+
+/// Describe the TLS options (certificate paths only, never key contents)
+pub fn describe_tls(tls: &TlsConfig) -> String {
+    let mut parts = Vec::new();
+    if let Some(ref cert) = tls.cert {
+        parts.push(format!("client cert {}", cert));
+    }
+    if !tls.ca_certs.is_empty() {
+        parts.push(format!("CA {}", tls.ca_certs.join(", ")));
+    }
+    if let Some(ref sni) = tls.sni {
+        parts.push(format!("SNI {}", sni));
+    }
+    if tls.insecure {
+        parts.push("certificate verification OFF".to_string());
+    }
+    parts.join("; ")
+}
+
+/// Describe the response assertions and the sampling rate
+pub fn describe_validation(validation: &ValidationConfig) -> String {
+    let mut parts = Vec::new();
+    if !validation.status.is_empty() {
+        let codes: Vec<String> = validation.status.iter().map(|c| c.to_string()).collect();
+        parts.push(format!("status {}", codes.join(",")));
+    }
+    for text in &validation.body_contains {
+        parts.push(format!("contains \"{}\"", text));
+    }
+    for pattern in &validation.body_regex {
+        parts.push(format!("matches /{}/", pattern));
+    }
+    for (path, expected) in &validation.json_equals {
+        parts.push(format!("{} == {}", path, expected));
+    }
+    if let Some(ref schema) = validation.schema {
+        parts.push(format!("schema {}", schema));
+    }
+    if let Some(bytes) = validation.max_size {
+        parts.push(format!("size <= {} B", bytes));
+    }
+    format!(
+        "{} ({} samples/s)",
+        parts.join("; "),
+        validation.sample_rate
+    )
+}
+
+/// Describe the status code rules that override the default classification
+pub fn describe_status_classes(classes: &StatusClasses) -> String {
+    classes
+        .rules
+        .iter()
+        .map(|rule| rule.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Describe the metrics source and the recorded series for summaries
+pub fn describe_metrics(metrics: &MetricsConfig) -> String {
+    let source = match (&metrics.url, &metrics.command) {
+        (Some(url), _) => url.clone(),
+        (None, Some(command)) => format!("`{}`", command),
+        (None, None) => "none".to_string(),
+    };
+    let series: Vec<String> = metrics.series().iter().map(|s| s.to_string()).collect();
+    format!("{} ({})", source, series.join(", "))
+}
+
+/// Describe the configured hooks, e.g. "before-step, after-step (after-step can stop the ramp)"
+pub fn describe_hooks(hooks: &HookConfig) -> String {
+    let names = hooks.names().join(", ");
+    if hooks.veto {
+        format!("{} (after-step can stop the ramp)", names)
+    } else {
+        names
+    }
+}
+
+/// Server metrics of a step, e.g. "cpu 1.20, memory 512.0M"
+fn describe_server_metrics(metrics: &[(String, f64)]) -> String {
+    metrics
+        .iter()
+        .map(|(label, value)| format!("{} {}", label, format_metric_value(*value)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Write the per-step server metrics of a URL as a table, if any were recorded
+fn write_server_metrics(report: &mut String, results: &[BenchmarkResult]) {
+    let mut labels: Vec<&str> = Vec::new();
+    for (label, _) in results.iter().flat_map(|r| &r.server_metrics) {
+        if !labels.contains(&label.as_str()) {
+            labels.push(label);
+        }
+    }
+    if labels.is_empty() {
+        return;
+    }
+
+    writeln!(report).unwrap();
+    writeln!(report, "SERVER METRICS:").unwrap();
+    let mut header = format!("{:>7}", "Target");
+    let mut rule = format!("{:>7}", "-------");
+    for label in &labels {
+        let width = label.len().max(9);
+        header.push_str(&format!(" {:>width$}", label));
+        rule.push_str(&format!(" {}", "-".repeat(width)));
+    }
+    writeln!(report, "{}", header).unwrap();
+    writeln!(report, "{}", rule).unwrap();
+    for result in results {
+        let mut row = format!("{:>7}", result.target_rate);
+        for label in &labels {
+            let width = label.len().max(9);
+            let value = result
+                .server_metrics
+                .iter()
+                .find(|(l, _)| l == label)
+                .map_or("-".to_string(), |(_, v)| format_metric_value(*v));
+            row.push_str(&format!(" {:>width$}", value));
+        }
+        writeln!(report, "{}", row).unwrap();
+    }
+}
+
+/// Describe where the load generator itself ran out of resources, if it did
+fn describe_client_limit(summary: &BenchmarkSummary) -> Option<String> {
+    let rate = summary.client_limited_rate?;
+    let limit = summary.client_limit.as_ref()?;
+    Some(format!(
+        "saturated at {} req/s ({}); from there on results may reflect the client, not the server",
+        rate, limit
+    ))
+}
+
+/// Label for the breaking point: what stopped the ramp, or the capacity limit when the ramp
+/// went on past the limiter
+fn breaking_point_label(summary: &BenchmarkSummary) -> &'static str {
+    if summary.probed_past_limit {
+        return "Capacity limit:";
+    }
+    match summary.break_reason {
+        BreakReason::RateLimited(_) => "Rate limited at:",
+        BreakReason::Blocked(_) => "Blocked at:",
+        BreakReason::ClientLimited(_) => "Client limit:",
+        _ => "Breaking point:",
+    }
+}
+
+/// The first limited rate when the ramp went on past the limiter, e.g.
+/// "100 req/s (rate limited, 42.0% of requests)"
+fn describe_policy_limit(summary: &BenchmarkSummary) -> Option<String> {
+    if !summary.probed_past_limit {
+        return None;
+    }
+    let rate = summary.policy_limit_rate?;
+    Some(match summary.policy_limit_reason {
+        BreakReason::Blocked(share) => {
+            format!("{} req/s (blocked, {:.1}% of requests)", rate, share)
+        }
+        BreakReason::RateLimited(share) => {
+            format!("{} req/s (rate limited, {:.1}% of requests)", rate, share)
+        }
+        _ => format!("{} req/s", rate),
+    })
+}
+
+/// What the rate-limit headers advertise next to the rate the server actually let through,
+/// e.g. "limited at ~48 req/s, server advertises 100 per 60s (~1.7 req/s); Retry-After 30s"
+/// None without headers: the breaking point line already says the server rejected requests
+fn describe_rate_limit(summary: &BenchmarkSummary) -> Option<String> {
+    let info = summary.rate_limit.as_ref()?;
+    let mut parts = Vec::new();
+    if let Some(rate) = summary.rate_limited_accepted_rate {
+        parts.push(format!("limited at ~{:.0} req/s", rate));
+    }
+    match (info.limit, info.window_seconds, info.advertised_rate()) {
+        (Some(limit), Some(window), Some(rate)) => parts.push(format!(
+            "server advertises {} per {}s (~{:.1} req/s)",
+            limit, window, rate
+        )),
+        (Some(limit), _, _) => parts.push(format!("server advertises {} per window", limit)),
+        _ => {}
+    }
+
+    let mut details = Vec::new();
+    if !parts.is_empty() {
+        details.push(parts.join(", "));
+    }
+    if let Some(seconds) = info.retry_after_seconds {
+        details.push(format!("Retry-After {}s", seconds));
+    }
+    if let Some(remaining) = info.remaining {
+        details.push(format!("{} remaining at lowest", remaining));
+    }
+    if details.is_empty() {
+        details.push(format!("headers {}", info.headers.join(", ")));
+    }
+    Some(details.join("; "))
+}
+
+/// Failures without a response by kind, e.g. "timeout (12), connection refused (3)"
+fn describe_error_kinds(kinds: &[(ErrorKind, u64)]) -> String {
+    kinds
+        .iter()
+        .map(|(kind, count)| format!("{} ({})", kind, format_number(*count)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Failed assertions with their share of the sampled responses
+fn describe_assertion_failures(summary: &BenchmarkSummary) -> String {
+    summary
+        .assertion_failures
+        .iter()
+        .map(|(name, count)| {
+            format!(
+                "{} ({}/{} sampled)",
+                name,
+                format_number(*count),
+                format_number(summary.validated)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Describe the early abort margin and the safety cutoff
+pub fn describe_abort(abort: &AbortConfig) -> String {
+    let mut parts = Vec::new();
+    if let Some(margin) = abort.margin_percent {
+        parts.push(format!(
+            "step when {}% past the thresholds for {}s",
+            margin, abort.window_seconds
+        ));
+    }
+    let mut cutoff = Vec::new();
+    if let Some(rate) = abort.cutoff_error_rate {
+        cutoff.push(format!("error rate >= {}%", rate));
+    }
+    if let Some(ms) = abort.cutoff_p99_ms {
+        cutoff.push(format!("p99 >= {}ms", ms));
+    }
+    if !cutoff.is_empty() {
+        parts.push(format!("run when {}", cutoff.join(" OR ")));
+    }
+    if parts.is_empty() {
+        return "Off".to_string();
+    }
+    parts.join("; ")
+}
+
+/// Describe the step duration: fixed, or the adaptive bounds and tolerance
+pub fn describe_duration(ramping: &RampingConfig) -> String {
+    match ramping.adaptive {
+        Some(ref adaptive) => format!(
+            "adaptive, {}-{}s per step until p50/p99/throughput settle within {}%",
+            adaptive.min_seconds, adaptive.max_seconds, adaptive.tolerance_percent
+        ),
+        None => format!("{}s per step", ramping.duration_seconds),
+    }
+}
+
+/// Note shown after the status of a step that was cut short
+fn step_note(result: &BenchmarkResult) -> Option<&'static str> {
+    if result.safety_cutoff {
+        Some("(cutoff)")
+    } else if result.aborted_early {
+        Some("(aborted)")
+    } else if result
+        .client
+        .as_ref()
+        .is_some_and(|c| c.saturation().is_some())
+    {
+        Some("(client-limited)")
+    } else {
+        None
+    }
+}
+
 /// Print URL header for multi-URL runs
 pub fn print_url_header(url: &str, index: usize, total: usize) {
     println!();
@@ -95,7 +503,7 @@ pub fn print_table_header() {
 }
 
 /// Format latency value with appropriate unit
-fn format_latency(ms: f64) -> String {
+pub fn format_latency(ms: f64) -> String {
     if ms == 0.0 {
         "-".to_string()
     } else if ms < 1.0 {
@@ -108,7 +516,8 @@ fn format_latency(ms: f64) -> String {
 }
 
 /// Print a single result row
-pub fn print_result_row(result: &BenchmarkResult, analysis: &AnalysisResult) {
+/// With adaptive durations the seconds the step ran are shown after the status
+pub fn print_result_row(result: &BenchmarkResult, analysis: &AnalysisResult, adaptive: bool) {
     // Build status string (no error codes in table - they go in summary)
     let status_text = match analysis.status {
         StepStatus::Ok => "OK",
@@ -118,13 +527,14 @@ pub fn print_result_row(result: &BenchmarkResult, analysis: &AnalysisResult) {
         StepStatus::Blocked => "BLOCK",
         StepStatus::Hung => "HANG",
         StepStatus::Gone => "GONE",
+        StepStatus::Limited => "LIMIT",
     };
 
     let status_padded = format!("{:>6}", status_text);
 
     let status_str = match analysis.status {
         StepStatus::Ok => style(status_padded).green().to_string(),
-        StepStatus::Warning => style(status_padded).yellow().to_string(),
+        StepStatus::Warning | StepStatus::Limited => style(status_padded).yellow().to_string(),
         StepStatus::Break
         | StepStatus::RateLimited
         | StepStatus::Blocked
@@ -139,8 +549,31 @@ pub fn print_result_row(result: &BenchmarkResult, analysis: &AnalysisResult) {
         error_rate_padded
     };
 
+    let mut note = String::new();
+    if adaptive {
+        note.push_str(&format!(
+            " {}",
+            style(format!("{}s", result.duration_seconds)).dim()
+        ));
+    }
+    if result.limited > 0 {
+        note.push_str(&format!(
+            " {}",
+            style(format!("{:.1}% limited", limited_share(result))).yellow()
+        ));
+    }
+    if let Some(cut) = step_note(result) {
+        note.push_str(&format!(" {}", style(cut).yellow()));
+    }
+    if !result.server_metrics.is_empty() {
+        note.push_str(&format!(
+            " {}",
+            style(describe_server_metrics(&result.server_metrics)).dim()
+        ));
+    }
+
     println!(
-        "{:>7} {:>9.1} {:>9} {:>8} {:>8} {:>8} {:>8} {} {}",
+        "{:>7} {:>9.1} {:>9} {:>8} {:>8} {:>8} {:>8} {} {}{}",
         result.target_rate,
         result.actual_rate,
         format_latency(result.avg_latency_ms),
@@ -149,7 +582,8 @@ pub fn print_result_row(result: &BenchmarkResult, analysis: &AnalysisResult) {
         format_latency(result.p99_latency_ms),
         format_latency(result.max_latency_ms),
         error_rate_str,
-        status_str
+        status_str,
+        note
     );
 }
 
@@ -160,30 +594,57 @@ pub fn print_summary(summary: &BenchmarkSummary) {
     println!("{}", style("RESULTS").bold());
     println!("{}", style(SEPARATOR).dim());
 
+    if let Some(policy) = describe_policy_limit(summary) {
+        println!(
+            "{:<22} {}",
+            style("Policy limit:").cyan(),
+            style(policy).yellow()
+        );
+    }
+
     if let Some(rate) = summary.breaking_point_rate {
         // Choose appropriate label based on what caused the stop
-        let label = if summary.was_rate_limited {
-            "Rate limited at:"
-        } else if summary.was_blocked {
-            "Blocked at:"
-        } else {
-            "Breaking point:"
-        };
+        let label = breaking_point_label(summary);
 
         println!(
-            "{:<22} {} req/s ({})",
+            "{:<22} {} req/s ({}){}",
             style(label).cyan(),
             style(rate).red(),
-            summary.break_reason
+            summary.break_reason,
+            if summary.aborted_early {
+                " - step aborted early"
+            } else {
+                ""
+            }
         );
     } else {
         println!(
             "{:<22} {}",
-            style("Breaking point:").cyan(),
+            style(breaking_point_label(summary)).cyan(),
             style("Not reached (consider increasing max rate)").green()
         );
     }
 
+    if let Some(policy) = describe_rate_limit(summary) {
+        println!("{:<22} {}", style("Rate-limit policy:").cyan(), policy);
+    }
+
+    if let Some(client) = describe_client_limit(summary) {
+        println!(
+            "{:<22} {}",
+            style("Load generator:").cyan(),
+            style(client).yellow()
+        );
+    }
+
+    if let Some(rate) = summary.safety_cutoff_rate {
+        println!(
+            "{:<22} {}",
+            style("Safety cutoff:").cyan(),
+            style(format!("run stopped at {} req/s", rate)).red().bold()
+        );
+    }
+
     // Display aggregated HTTP error codes if any
     if !summary.aggregated_error_codes.is_empty() {
         let error_codes_str: Vec<String> = summary
@@ -198,6 +659,30 @@ pub fn print_summary(summary: &BenchmarkSummary) {
         );
     }
 
+    if !summary.aggregated_error_kinds.is_empty() {
+        println!(
+            "{:<22} {}",
+            style("Connection errors:").cyan(),
+            describe_error_kinds(&summary.aggregated_error_kinds)
+        );
+    }
+
+    if summary.ignored > 0 {
+        println!(
+            "{:<22} {}",
+            style("Ignored responses:").cyan(),
+            format_number(summary.ignored)
+        );
+    }
+
+    if !summary.assertion_failures.is_empty() {
+        println!(
+            "{:<22} {}",
+            style("Failed checks:").cyan(),
+            describe_assertion_failures(summary)
+        );
+    }
+
     if let Some(rate) = summary.last_stable_rate {
         println!(
             "{:<22} {} req/s",
@@ -227,6 +712,37 @@ pub fn print_summary(summary: &BenchmarkSummary) {
     );
 }
 
+/// Print gate results followed by the machine-readable verdict line
+pub fn print_verdict(verdict: &Verdict) {
+    println!();
+    println!("{}", style(SEPARATOR).dim());
+    if verdict.failures.is_empty() {
+        println!(
+            "{} {}",
+            style("GATES").bold(),
+            style("PASSED").green().bold()
+        );
+    } else {
+        println!(
+            "{} {} (exit code {})",
+            style("GATES").bold(),
+            style("FAILED").red().bold(),
+            verdict.exit_code
+        );
+        for failure in &verdict.failures {
+            println!(
+                "  {} {:<22} {} ({})",
+                style("✗").red(),
+                failure.gate,
+                failure.message,
+                failure.url
+            );
+        }
+    }
+    println!("{}", style(SEPARATOR).dim());
+    println!("{}", verdict.to_line());
+}
+
 /// Print the legend
 pub fn print_legend() {
     println!();
@@ -268,7 +784,16 @@ pub fn print_legend() {
         style("BLOCK").red(),
         style("GONE").red()
     );
-    println!("  {}  = server hung (timed out)", style("HANG").red());
+    println!(
+        "  {}  = server hung (timed out)   {} = limited, ramp continues (--probe-past-limit)",
+        style("HANG").red(),
+        style("LIMIT").yellow()
+    );
+    println!(
+        "  {} / {} = step cut short by early abort / the safety cutoff",
+        style("(aborted)").yellow(),
+        style("(cutoff)").yellow()
+    );
     println!();
     println!(
         "{}",
@@ -294,7 +819,7 @@ pub fn print_legend() {
 }
 
 /// Format a large number with commas
-fn format_number(n: u64) -> String {
+pub fn format_number(n: u64) -> String {
     let s = n.to_string();
     let mut result = String::new();
     for (i, c) in s.chars().rev().enumerate() {
@@ -321,6 +846,134 @@ fn format_duration(seconds: u64) -> String {
     }
 }
 
+/// Format an optional rate for table display
+fn format_optional_rate(rate: Option<u32>) -> String {
+    rate.map(|r| r.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Print the list of recorded runs (most recent last)
+pub fn print_history(runs: &[RunRecord]) {
+    print_header();
+
+    if runs.is_empty() {
+        println!();
+        println!("{}", style("No recorded runs found.").yellow());
+        return;
+    }
+
+    println!();
+    println!(
+        "{:<16} {:<24} {:>7} {:>7} {:>7} {:>9}  {:<3}",
+        "Date", "Scenario", "Break", "Stable", "Recomm", "Requests", "URL"
+    );
+    println!(
+        "{:<16} {:<24} {:>7} {:>7} {:>7} {:>9}  {:<3}",
+        "────────────────",
+        "────────────────────────",
+        "───────",
+        "───────",
+        "───────",
+        "─────────",
+        "───"
+    );
+
+    for run in runs {
+        let scenario: String = run.scenario.chars().take(24).collect();
+        let break_str = format!("{:>7}", format_optional_rate(run.breaking_point_rate));
+        let break_str = if run.breaking_point_rate.is_some() {
+            style(break_str).red().to_string()
+        } else {
+            style(break_str).green().to_string()
+        };
+
+        println!(
+            "{:<16} {:<24} {} {:>7} {:>7} {:>9}  {}",
+            run.formatted_time(),
+            scenario,
+            break_str,
+            format_optional_rate(run.last_stable_rate),
+            format_optional_rate(run.recommended_rate),
+            format_number(run.total_requests),
+            run.url
+        );
+    }
+
+    println!();
+    println!(
+        "{} run(s). Break/Stable/Recomm are in req/s; \"-\" means not reached.",
+        runs.len()
+    );
+}
+
+/// Print breaking point, recommended rate and p99-at-rate trends, grouped by scenario and URL
+/// p99 is compared at `at_rate`, or at the highest rate tested in every run of the group
+pub fn print_trend(runs: &[RunRecord], at_rate: Option<u32>) {
+    print_header();
+
+    if runs.is_empty() {
+        println!();
+        println!("{}", style("No recorded runs found.").yellow());
+        return;
+    }
+
+    for ((scenario, url), group) in group_runs(runs) {
+        let rate = at_rate.or_else(|| common_tested_rate(&group));
+
+        println!();
+        println!(
+            "{} {}",
+            style("TREND").cyan().bold(),
+            style(&scenario).bold()
+        );
+        println!("{:<14} {}", style("URL:").cyan(), url);
+        println!("{:<14} {}", style("Runs:").cyan(), group.len());
+        println!();
+
+        let p99_header = match rate {
+            Some(r) => format!("p99 @ {}", r),
+            None => "p99 @ -".to_string(),
+        };
+        println!(
+            "{:<16} {:>7} {:>7} {:>12}  {:<6}",
+            "Date", "Break", "Recomm", p99_header, "Change"
+        );
+        println!(
+            "{:<16} {:>7} {:>7} {:>12}  {:<6}",
+            "────────────────", "───────", "───────", "────────────", "──────"
+        );
+
+        let mut previous: Option<&RunRecord> = None;
+        for run in &group {
+            let p99 = rate.and_then(|r| run.p99_at_rate(r));
+
+            // Describe how the breaking point moved relative to the previous run
+            let change = match (
+                previous.and_then(|p| p.breaking_point_rate),
+                run.breaking_point_rate,
+            ) {
+                (Some(prev), Some(cur)) if cur > prev => {
+                    style(format!("▲ +{}", cur - prev)).green().to_string()
+                }
+                (Some(prev), Some(cur)) if cur < prev => {
+                    style(format!("▼ -{}", prev - cur)).red().to_string()
+                }
+                _ => String::new(),
+            };
+
+            println!(
+                "{:<16} {:>7} {:>7} {:>12}  {}",
+                run.formatted_time(),
+                format_optional_rate(run.breaking_point_rate),
+                format_optional_rate(run.recommended_rate),
+                p99.map(format_latency).unwrap_or_else(|| "-".to_string()),
+                change
+            );
+            previous = Some(run);
+        }
+    }
+}
+
 /// Results for a single URL benchmark
 pub struct UrlBenchmarkResults {
     pub url: String,
@@ -352,6 +1005,55 @@ pub fn generate_report_text(
         }
     }
     writeln!(report, "Method:       {}", config.method).unwrap();
+    if let Some(body) = describe_body(config) {
+        writeln!(report, "Body:         {}", body).unwrap();
+    }
+    if let Some(auth) = describe_auth(config) {
+        writeln!(report, "Auth:         {}", auth).unwrap();
+    }
+    if let Some(ref login) = config.login {
+        writeln!(report, "Login:        {}", describe_login(login)).unwrap();
+    }
+    if !config.tls.is_empty() {
+        writeln!(report, "TLS:          {}", describe_tls(&config.tls)).unwrap();
+    }
+    for (i, header) in config.headers.iter().enumerate() {
+        let label = if i == 0 { "Headers:" } else { "" };
+        writeln!(report, "{:<14}{}", label, redact_header(header)).unwrap();
+    }
+    if !config.validation.is_empty() {
+        writeln!(
+            report,
+            "Checks:       {}",
+            describe_validation(&config.validation)
+        )
+        .unwrap();
+    }
+    if !config.status_classes.is_default() {
+        writeln!(
+            report,
+            "Status:       {}",
+            describe_status_classes(&config.status_classes)
+        )
+        .unwrap();
+    }
+    if !config.workers.is_empty() {
+        writeln!(report, "Workers:      {}", config.workers.join(", ")).unwrap();
+    }
+    if config.metrics.is_enabled() {
+        writeln!(
+            report,
+            "Metrics:      {}",
+            describe_metrics(&config.metrics)
+        )
+        .unwrap();
+    }
+    if !config.hooks.is_empty() {
+        writeln!(report, "Hooks:        {}", describe_hooks(&config.hooks)).unwrap();
+    }
+    if config.notify.is_enabled() {
+        writeln!(report, "Notify:       {}", config.notify.describe()).unwrap();
+    }
     writeln!(report, "Mode:         {} ramping", config.ramping.mode).unwrap();
     writeln!(
         report,
@@ -361,8 +1063,8 @@ pub fn generate_report_text(
     .unwrap();
     writeln!(
         report,
-        "Duration:     {}s per step",
-        config.ramping.duration_seconds
+        "Duration:     {}",
+        describe_duration(&config.ramping)
     )
     .unwrap();
     writeln!(
@@ -371,6 +1073,9 @@ pub fn generate_report_text(
         config.thresholds.max_error_rate, config.thresholds.max_p99_ms
     )
     .unwrap();
+    if config.abort.is_enabled() {
+        writeln!(report, "Abort:        {}", describe_abort(&config.abort)).unwrap();
+    }
     writeln!(report, "{}", SEPARATOR).unwrap();
 
     // Results for each URL
@@ -422,11 +1127,23 @@ pub fn generate_report_text(
                 StepStatus::Blocked => "BLOCK",
                 StepStatus::Hung => "HANG",
                 StepStatus::Gone => "GONE",
+                StepStatus::Limited => "LIMIT",
             };
 
+            let mut note = String::new();
+            if config.ramping.adaptive.is_some() {
+                note.push_str(&format!(" {}s", result.duration_seconds));
+            }
+            if result.limited > 0 {
+                note.push_str(&format!(" {:.1}% limited", limited_share(result)));
+            }
+            if let Some(cut) = step_note(result) {
+                note.push_str(&format!(" {}", cut));
+            }
+
             writeln!(
                 report,
-                "{:>7} {:>9.1} {:>9} {:>8} {:>8} {:>8} {:>8} {:>8.2}% {:>6}",
+                "{:>7} {:>9.1} {:>9} {:>8} {:>8} {:>8} {:>8} {:>8.2}% {:>6}{}",
                 result.target_rate,
                 result.actual_rate,
                 format_latency(result.avg_latency_ms),
@@ -435,33 +1152,59 @@ pub fn generate_report_text(
                 format_latency(result.p99_latency_ms),
                 format_latency(result.max_latency_ms),
                 result.error_rate,
-                status_str
+                status_str,
+                note
             )
             .unwrap();
         }
 
+        write_server_metrics(&mut report, &url_result.results);
+
         // Summary for this URL
         writeln!(report).unwrap();
         writeln!(report, "RESULTS:").unwrap();
 
+        if let Some(policy) = describe_policy_limit(&url_result.summary) {
+            writeln!(report, "  Policy limit:       {}", policy).unwrap();
+        }
+
         if let Some(rate) = url_result.summary.breaking_point_rate {
-            let label = if url_result.summary.was_rate_limited {
-                "Rate limited at:"
-            } else if url_result.summary.was_blocked {
-                "Blocked at:"
-            } else {
-                "Breaking point:"
-            };
+            let label = breaking_point_label(&url_result.summary);
             writeln!(
                 report,
-                "  {:<18} {} req/s ({})",
-                label, rate, url_result.summary.break_reason
+                "  {:<19} {} req/s ({}){}",
+                label,
+                rate,
+                url_result.summary.break_reason,
+                if url_result.summary.aborted_early {
+                    " - step aborted early"
+                } else {
+                    ""
+                }
             )
             .unwrap();
         } else {
             writeln!(
                 report,
-                "  Breaking point:     Not reached (consider increasing max rate)"
+                "  {:<19} Not reached (consider increasing max rate)",
+                breaking_point_label(&url_result.summary)
+            )
+            .unwrap();
+        }
+
+        if let Some(policy) = describe_rate_limit(&url_result.summary) {
+            writeln!(report, "  Rate-limit policy:  {}", policy).unwrap();
+        }
+
+        if let Some(client) = describe_client_limit(&url_result.summary) {
+            writeln!(report, "  Load generator:     {}", client).unwrap();
+        }
+
+        if let Some(rate) = url_result.summary.safety_cutoff_rate {
+            writeln!(
+                report,
+                "  Safety cutoff:      run stopped at {} req/s",
+                rate
             )
             .unwrap();
         }
@@ -482,6 +1225,33 @@ pub fn generate_report_text(
             .unwrap();
         }
 
+        if !url_result.summary.aggregated_error_kinds.is_empty() {
+            writeln!(
+                report,
+                "  Connection errors:  {}",
+                describe_error_kinds(&url_result.summary.aggregated_error_kinds)
+            )
+            .unwrap();
+        }
+
+        if url_result.summary.ignored > 0 {
+            writeln!(
+                report,
+                "  Ignored responses:  {}",
+                format_number(url_result.summary.ignored)
+            )
+            .unwrap();
+        }
+
+        if !url_result.summary.assertion_failures.is_empty() {
+            writeln!(
+                report,
+                "  Failed checks:      {}",
+                describe_assertion_failures(&url_result.summary)
+            )
+            .unwrap();
+        }
+
         if let Some(rate) = url_result.summary.last_stable_rate {
             writeln!(report, "  Last stable rate:   {} req/s", rate).unwrap();
         }
@@ -550,7 +1320,16 @@ pub fn generate_report_text(
         "  BLOCK = blocked (403)        GONE  = no responses received"
     )
     .unwrap();
-    writeln!(report, "  HANG  = server hung (timed out)").unwrap();
+    writeln!(
+        report,
+        "  HANG  = server hung (timed out)   LIMIT = limited, ramp continues (--probe-past-limit)"
+    )
+    .unwrap();
+    writeln!(
+        report,
+        "  (aborted) / (cutoff) = step cut short by early abort / the safety cutoff"
+    )
+    .unwrap();
     writeln!(report).unwrap();
     writeln!(report, "Expected Error Rates by Service Type:").unwrap();
     writeln!(report, "  Payment/Checkout:         < 0.1%").unwrap();
@@ -565,7 +1344,8 @@ pub fn generate_report_text(
     .unwrap();
     writeln!(report, "{}", SEPARATOR).unwrap();
 
-    report
+    // Last line of defence: no registered secret ends up in a saved report
+    redact(&report)
 }
 
 /// Save report to file
@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Reset values above this are Unix timestamps rather than seconds from now
+const EPOCH_THRESHOLD: f64 = 1_000_000_000.0;
+
+/// Rate-limit policy advertised by the server in sampled response headers
+/// (`RateLimit-*`, `X-RateLimit-*`, the combined `RateLimit` field and `Retry-After`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitInfo {
+    /// Requests allowed per window
+    pub limit: Option<u64>,
+    /// Window length in seconds, from a policy (`w=`) or the largest reset seen
+    pub window_seconds: Option<u64>,
+    /// Lowest remaining quota seen
+    pub remaining: Option<u64>,
+    /// Longest `Retry-After` seen (seconds)
+    pub retry_after_seconds: Option<u64>,
+    /// Rate-limit header names seen (lowercase), sorted
+    pub headers: Vec<String>,
+}
+
+impl RateLimitInfo {
+    /// Read the rate-limit headers of a response, if it has any
+    pub fn from_response(response: &ureq::Response) -> Option<Self> {
+        let mut info = RateLimitInfo::default();
+        let mut reset: Option<f64> = None;
+        let mut names = response.headers_names();
+        names.sort();
+        names.dedup();
+
+        for name in names {
+            let Some(value) = response.header(&name) else {
+                continue;
+            };
+            let key = name
+                .trim_start_matches("x-")
+                .replace("rate-limit", "ratelimit");
+            let fields = Fields::parse(value);
+            match key.as_str() {
+                "ratelimit-limit" => {
+                    info.limit = info.limit.or(fields.bare.map(|n| n as u64));
+                    info.window_seconds = info.window_seconds.or(fields.get("w"));
+                }
+                "ratelimit-remaining" => info.remaining = fields.bare.map(|n| n as u64),
+                "ratelimit-reset" => reset = fields.bare,
+                "ratelimit-policy" => {
+                    // "100;w=60" or the structured form "\"default\";q=100;w=60"
+                    info.limit = fields.bare.map(|n| n as u64).or(fields.get("q"));
+                    info.window_seconds = fields.get("w");
+                }
+                "ratelimit" => {
+                    // "limit=100, remaining=50, reset=30" or "\"default\";r=50;t=30"
+                    info.limit = info.limit.or(fields.get("limit"));
+                    info.remaining = fields.get("remaining").or(fields.get("r"));
+                    reset = fields.value("reset").or(fields.value("t"));
+                }
+                "retry-after" => info.retry_after_seconds = fields.bare.map(|n| n as u64),
+                _ => continue,
+            }
+            info.headers.push(name);
+        }
+
+        if info.headers.is_empty() {
+            return None;
+        }
+        if let Some(reset) = reset {
+            let seconds = if reset > EPOCH_THRESHOLD {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0.0, |d| d.as_secs_f64());
+                (reset - now).max(0.0)
+            } else {
+                reset
+            };
+            // Without a policy, the longest reset seen approximates the window
+            if info.window_seconds.is_none() && seconds > 0.0 {
+                info.window_seconds = Some(seconds.ceil() as u64);
+            }
+        }
+        Some(info)
+    }
+
+    /// Fold another sample in: the tightest remaining quota, the longest window and
+    /// Retry-After, the latest limit
+    pub fn merge(&mut self, other: &RateLimitInfo) {
+        self.limit = other.limit.or(self.limit);
+        self.window_seconds = max_option(self.window_seconds, other.window_seconds);
+        self.remaining = match (self.remaining, other.remaining) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.retry_after_seconds = max_option(self.retry_after_seconds, other.retry_after_seconds);
+        for name in &other.headers {
+            if !self.headers.contains(name) {
+                self.headers.push(name.clone());
+            }
+        }
+        self.headers.sort();
+    }
+
+    /// Advertised requests per second (limit / window), when both are known
+    pub fn advertised_rate(&self) -> Option<f64> {
+        match (self.limit, self.window_seconds) {
+            (Some(limit), Some(window)) if window > 0 => Some(limit as f64 / window as f64),
+            _ => None,
+        }
+    }
+}
+
+/// Fold an optional rate-limit sample into another
+pub fn merge_info(target: &mut Option<RateLimitInfo>, other: Option<&RateLimitInfo>) {
+    if let Some(other) = other {
+        match target {
+            Some(info) => info.merge(other),
+            None => *target = Some(other.clone()),
+        }
+    }
+}
+
+fn max_option(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Numbers in a rate-limit header value: the first bare number and `key=value` parameters
+/// (the first occurrence of each wins, so "100;w=60, 1000;w=3600" reads as 100 per 60s)
+struct Fields {
+    bare: Option<f64>,
+    params: Vec<(String, f64)>,
+}
+
+impl Fields {
+    fn parse(value: &str) -> Self {
+        let mut fields = Fields {
+            bare: None,
+            params: Vec::new(),
+        };
+        for part in value.split([',', ';']) {
+            let part = part.trim();
+            match part.split_once('=') {
+                Some((key, number)) => {
+                    let key = key.trim().to_lowercase();
+                    if let Ok(number) = number.trim().trim_matches('"').parse::<f64>() {
+                        if !fields.params.iter().any(|(k, _)| *k == key) {
+                            fields.params.push((key, number));
+                        }
+                    }
+                }
+                None => {
+                    if fields.bare.is_none() {
+                        fields.bare = part.parse::<f64>().ok();
+                    }
+                }
+            }
+        }
+        fields
+    }
+
+    fn value(&self, key: &str) -> Option<f64> {
+        self.params.iter().find(|(k, _)| k == key).map(|(_, v)| *v)
+    }
+
+    fn get(&self, key: &str) -> Option<u64> {
+        self.value(key).map(|v| v as u64)
+    }
+}
@@ -0,0 +1,483 @@
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::auth::AuthSession;
+use crate::cli::HttpMethod;
+use crate::config::BenchmarkConfig;
+use crate::login::LoginSession;
+use crate::menu::ensure_protocol;
+use crate::metrics::MetricsScraper;
+use crate::runner::BenchmarkResult;
+use crate::signing::SigningRequest;
+use crate::template::{DataSet, Template};
+use crate::tls::{self, TlsConfig};
+use crate::validate::Validator;
+
+/// Default Content-Type for literal `--body` values
+const DEFAULT_CONTENT_TYPE: &str = "application/json";
+
+/// How a request body is handed to oha
+#[derive(Debug, Clone)]
+pub enum BodyArg {
+    /// Passed inline with `-d`
+    Inline(String),
+    /// Passed as a file with `-D` (supports binary content)
+    File(PathBuf),
+}
+
+/// How the target URL(s) are handed to oha
+#[derive(Debug, Clone)]
+pub enum UrlArg {
+    /// A single fixed URL
+    Single(String),
+    /// A generated list passed with `--urls-from-file` (oha picks one at random per request)
+    File(PathBuf),
+}
+
+/// Source of URLs for one target entry
+#[derive(Debug)]
+enum TargetSource {
+    /// Plain URL, sent as-is
+    Static,
+    /// URL with placeholders, rendered into `url_variants` URLs per step
+    Template(Template),
+    /// Lines loaded from a targets file (`@path`), each possibly templated
+    List(Vec<Template>),
+}
+
+#[derive(Debug)]
+struct Target {
+    source: TargetSource,
+    counter: u64,
+    /// Position in the config, used to name the generated URL list
+    index: usize,
+    /// oha `--connect-to` route for the current URLs when the SNI name is overridden
+    connect_to: Option<String>,
+}
+
+#[derive(Debug)]
+enum BodySource {
+    None,
+    Literal(String),
+    File(PathBuf),
+    Template(Template),
+}
+
+/// Per-run request state shared across steps: prepared bodies, data rows, counters and auth
+#[derive(Debug)]
+pub struct RequestPlan {
+    body: BodySource,
+    auth: AuthSession,
+    login: Option<LoginSession>,
+    content_type: Option<String>,
+    data: Option<DataSet>,
+    variants: usize,
+    counter: u64,
+    targets: HashMap<String, Target>,
+    url_variants: usize,
+    tls: TlsConfig,
+    /// The CA file handed to oha (several `--cacert` bundles are concatenated)
+    ca_bundle: Option<PathBuf>,
+    /// Response assertions and rate-limit headers checked on sampled requests, if enabled
+    validator: Option<Arc<Validator>>,
+    /// Server metrics source, if configured
+    metrics: Option<MetricsScraper>,
+    temp_dir: PathBuf,
+}
+
+impl RequestPlan {
+    /// Build the request plan from config, loading and validating body and data files
+    pub fn new(config: &BenchmarkConfig) -> Result<Self> {
+        let data = match config.data_file {
+            Some(ref path) => Some(DataSet::load(path)?),
+            None => None,
+        };
+
+        let (body_text, file_path) = match (&config.body, &config.body_file) {
+            (Some(_), Some(_)) => bail!("--body and --body-file can't be used together"),
+            (Some(body), None) => (Some(body.clone()), None),
+            (None, Some(path)) => {
+                let bytes = std::fs::read(path)
+                    .with_context(|| format!("Failed to read body file {}", path))?;
+                // Binary files are sent as-is; text files may contain template placeholders
+                (String::from_utf8(bytes).ok(), Some(PathBuf::from(path)))
+            }
+            (None, None) => (None, None),
+        };
+
+        let template = match body_text {
+            Some(ref text) => Some(Template::parse(text)?),
+            None => None,
+        };
+
+        let body = match (template, file_path, body_text) {
+            (Some(t), _, _) if t.is_dynamic() => {
+                if let Some(ref data) = data {
+                    for column in t.data_columns() {
+                        if !data.has_column(column) {
+                            bail!("Data file has no column '{}'", column);
+                        }
+                    }
+                } else if !t.data_columns().is_empty() {
+                    bail!("Body references {{{{data.*}}}} but no --data-file was given");
+                }
+                BodySource::Template(t)
+            }
+            (_, Some(path), _) => BodySource::File(path),
+            (_, None, Some(text)) => BodySource::Literal(text),
+            _ => BodySource::None,
+        };
+
+        let content_type = config.content_type.clone().or_else(|| match body {
+            BodySource::None => None,
+            BodySource::File(ref path) => Some(infer_content_type(path).to_string()),
+            BodySource::Template(_) => Some(
+                config
+                    .body_file
+                    .as_deref()
+                    .map(|p| infer_content_type(Path::new(p)))
+                    .unwrap_or(DEFAULT_CONTENT_TYPE)
+                    .to_string(),
+            ),
+            BodySource::Literal(_) => Some(DEFAULT_CONTENT_TYPE.to_string()),
+        });
+
+        let mut targets = HashMap::new();
+        for (index, url) in config.urls.iter().enumerate() {
+            let source = load_target(url, data.as_ref())?;
+            targets.insert(
+                url.clone(),
+                Target {
+                    source,
+                    counter: 0,
+                    index,
+                    connect_to: None,
+                },
+            );
+        }
+
+        let temp_dir = std::env::temp_dir().join(format!("ohabench-{}", std::process::id()));
+
+        config.tls.validate()?;
+        let ca_bundle = match config.tls.ca_certs.as_slice() {
+            [] => None,
+            [path] => Some(PathBuf::from(path)),
+            paths => {
+                let mut bundle = Vec::new();
+                for path in paths {
+                    let pem = std::fs::read(path)
+                        .with_context(|| format!("Failed to read CA bundle {}", path))?;
+                    bundle.extend_from_slice(&pem);
+                    bundle.push(b'\n');
+                }
+                std::fs::create_dir_all(&temp_dir)
+                    .with_context(|| format!("Failed to create temp dir {}", temp_dir.display()))?;
+                let path = temp_dir.join("ca-bundle.pem");
+                std::fs::write(&path, bundle)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                Some(path)
+            }
+        };
+
+        // Token-based auth is fetched up front so a bad token endpoint fails before the ramp
+        let auth = AuthSession::new(&config.auth, &config.tls)?;
+        if auth.signs_request()
+            && targets
+                .values()
+                .any(|t: &Target| !matches!(t.source, TargetSource::Static))
+        {
+            bail!(
+                "{} signs the request URL, so URL templates and targets files can't be used",
+                config.auth.auth_type
+            );
+        }
+
+        // Compile the assertions up front so a bad regex or schema fails before the ramp
+        // (redirects are only left unfollowed with a login, as for oha)
+        let validator = if config.validation.is_empty() && !config.rate_limit_headers {
+            None
+        } else {
+            Some(Arc::new(Validator::new(
+                &config.validation,
+                &config.tls,
+                config.login.is_none(),
+                &config.status_classes,
+                config.rate_limit_headers,
+            )?))
+        };
+
+        // Scrape once up front so an unreachable metrics source fails before the ramp
+        let metrics = if config.metrics.is_enabled() {
+            Some(MetricsScraper::new(&config.metrics, &config.tls)?)
+        } else {
+            None
+        };
+
+        // Log in once up front; the session is renewed if it expires mid-ramp
+        let login = match config.login {
+            Some(ref login) => Some(LoginSession::new(login, &config.tls)?),
+            None => None,
+        };
+
+        Ok(Self {
+            body,
+            auth,
+            login,
+            content_type,
+            data,
+            variants: config.body_variants.max(1) as usize,
+            counter: 0,
+            targets,
+            url_variants: config.url_variants.max(1) as usize,
+            tls: config.tls.clone(),
+            ca_bundle,
+            validator,
+            metrics,
+            temp_dir,
+        })
+    }
+
+    /// Prepare the URL argument for the next step against a target
+    /// Templates and targets files are rendered into a fresh URL list every step
+    /// With an SNI override the URLs carry the SNI name and are routed to the original host
+    pub fn next_url(&mut self, url: &str) -> Result<UrlArg> {
+        let Some(target) = self.targets.get_mut(url) else {
+            return Ok(UrlArg::Single(url.to_string()));
+        };
+
+        let mut urls: Vec<String> = match target.source {
+            TargetSource::Static => vec![url.to_string()],
+            TargetSource::Template(ref template) => (0..self.url_variants)
+                .map(|_| {
+                    let rendered = template.render(target.counter, self.data.as_ref());
+                    target.counter += 1;
+                    rendered
+                })
+                .collect(),
+            TargetSource::List(ref lines) => {
+                // Every line shares the step's counter so each line advances once per step
+                let urls = lines
+                    .iter()
+                    .map(|line| ensure_protocol(&line.render(target.counter, self.data.as_ref())))
+                    .collect();
+                target.counter += 1;
+                urls
+            }
+        };
+
+        if let Some(ref sni) = self.tls.sni {
+            let mut routes: Vec<String> = Vec::new();
+            for u in urls.iter_mut() {
+                let (rewritten, route) = tls::sni_route(u, sni)?;
+                *u = rewritten;
+                if !routes.contains(&route) {
+                    routes.push(route);
+                }
+            }
+            if routes.len() > 1 {
+                bail!(
+                    "--sni routes every request to one host, but {} spans several hosts",
+                    url
+                );
+            }
+            target.connect_to = routes.pop();
+        }
+
+        if let TargetSource::Static = target.source {
+            return Ok(UrlArg::Single(urls.remove(0)));
+        }
+
+        std::fs::create_dir_all(&self.temp_dir)
+            .with_context(|| format!("Failed to create temp dir {}", self.temp_dir.display()))?;
+
+        let path = self.temp_dir.join(format!("urls-{}.txt", target.index));
+        std::fs::write(&path, urls.join("\n"))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        Ok(UrlArg::File(path))
+    }
+
+    /// Auth headers for one oha process sending `body` to `url` for the next `seconds`
+    /// Tokens are refreshed if they would expire and signatures are computed for this request
+    pub fn auth_headers(
+        &mut self,
+        method: HttpMethod,
+        url: &UrlArg,
+        body: Option<&BodyArg>,
+        seconds: u32,
+    ) -> Result<Vec<String>> {
+        let body = match body {
+            Some(BodyArg::Inline(text)) => text.as_bytes().to_vec(),
+            Some(BodyArg::File(path)) => std::fs::read(path)
+                .with_context(|| format!("Failed to read body file {}", path.display()))?,
+            None => Vec::new(),
+        };
+        // URL lists are rejected up front for auth types that sign the URL
+        let url = match url {
+            UrlArg::Single(url) => url.as_str(),
+            UrlArg::File(_) => "",
+        };
+
+        let method = method.to_string();
+        let mut headers = self.auth.headers_for(
+            &SigningRequest {
+                method: &method,
+                url,
+                body: &body,
+            },
+            seconds,
+        )?;
+        if let Some(ref login) = self.login {
+            headers.extend(login.headers().iter().cloned());
+        }
+        Ok(headers)
+    }
+
+    /// TLS arguments for oha processes sending to `url` (call after `next_url`)
+    pub fn tls_args(&self, url: &str) -> Vec<String> {
+        let mut args = tls::oha_args(&self.tls, self.ca_bundle.as_deref());
+        if let Some(route) = self.targets.get(url).and_then(|t| t.connect_to.as_ref()) {
+            args.push("--connect-to".to_string());
+            args.push(route.clone());
+        }
+        args
+    }
+
+    /// Log in again if a step's responses show the session expired
+    /// Returns whether the session was renewed (the step should be retried)
+    pub fn renew_session(&mut self, result: &BenchmarkResult) -> Result<bool> {
+        match self.login {
+            Some(ref mut login) if LoginSession::expired(result) => {
+                login.relogin()?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Longest time one set of auth headers may be used for, if limited
+    pub fn max_segment_seconds(&self) -> Option<u32> {
+        self.auth.max_segment_seconds()
+    }
+
+    /// Validator for the response assertions, if any are configured
+    pub fn validator(&self) -> Option<&Arc<Validator>> {
+        self.validator.as_ref()
+    }
+
+    /// Server metrics scraper, if a metrics source is configured
+    pub fn metrics(&self) -> Option<&MetricsScraper> {
+        self.metrics.as_ref()
+    }
+
+    /// Content-Type to send with the body, if any
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Prepare bodies for the next step
+    /// Returns one entry per oha process: templated bodies are rendered into `variants`
+    /// distinct payloads and the step's rate is split evenly across them
+    pub fn next_bodies(&mut self) -> Result<Vec<Option<BodyArg>>> {
+        match self.body {
+            BodySource::None => Ok(vec![None]),
+            BodySource::Literal(ref s) => Ok(vec![Some(BodyArg::Inline(s.clone()))]),
+            BodySource::File(ref p) => Ok(vec![Some(BodyArg::File(p.clone()))]),
+            BodySource::Template(ref template) => {
+                std::fs::create_dir_all(&self.temp_dir).with_context(|| {
+                    format!("Failed to create temp dir {}", self.temp_dir.display())
+                })?;
+
+                let mut bodies = Vec::with_capacity(self.variants);
+                for i in 0..self.variants {
+                    let rendered = template.render(self.counter, self.data.as_ref());
+                    self.counter += 1;
+
+                    let path = self.temp_dir.join(format!("body-{}.txt", i));
+                    std::fs::write(&path, rendered)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    bodies.push(Some(BodyArg::File(path)));
+                }
+                Ok(bodies)
+            }
+        }
+    }
+}
+
+impl Drop for RequestPlan {
+    fn drop(&mut self) {
+        if self.temp_dir.exists() {
+            std::fs::remove_dir_all(&self.temp_dir).ok();
+        }
+    }
+}
+
+/// Classify a target entry: plain URL, URL template or `@path` targets file
+fn load_target(url: &str, data: Option<&DataSet>) -> Result<TargetSource> {
+    let templates = if let Some(path) = url.strip_prefix('@') {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read targets file {}", path))?;
+        let lines: Vec<Template> = contents
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(Template::parse)
+            .collect::<Result<_>>()?;
+        if lines.is_empty() {
+            bail!("Targets file {} has no URLs", path);
+        }
+        lines
+    } else {
+        let template = Template::parse(url)?;
+        if !template.is_dynamic() {
+            return Ok(TargetSource::Static);
+        }
+        vec![template]
+    };
+
+    for template in &templates {
+        for column in template.data_columns() {
+            match data {
+                Some(d) if d.has_column(column) => {}
+                Some(_) => bail!("Data file has no column '{}' (used in {})", column, url),
+                None => bail!(
+                    "{} references {{{{data.*}}}} but no --data-file was given",
+                    url
+                ),
+            }
+        }
+    }
+
+    if url.starts_with('@') {
+        Ok(TargetSource::List(templates))
+    } else {
+        Ok(TargetSource::Template(
+            templates.into_iter().next().unwrap(),
+        ))
+    }
+}
+
+/// Infer a Content-Type from a file extension
+fn infer_content_type(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+
+    match ext.as_str() {
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "yaml" | "yml" => "application/yaml",
+        "txt" => "text/plain",
+        "html" | "htm" => "text/html",
+        "csv" => "text/csv",
+        "form" | "urlencoded" => "application/x-www-form-urlencoded",
+        "graphql" | "gql" => "application/graphql",
+        "pb" | "protobuf" => "application/x-protobuf",
+        "msgpack" => "application/msgpack",
+        _ => "application/octet-stream",
+    }
+}
@@ -1,18 +1,39 @@
 use anyhow::{bail, Context, Result};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::io::Read;
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 use wait_timeout::ChildExt;
 
-use crate::auth::generate_auth_header;
+use crate::analysis::{exceeds_thresholds, hits_cutoff};
+use crate::cli::HttpMethod;
+use crate::client::{self, merge_usage, ClientUsage};
 use crate::config::BenchmarkConfig;
+use crate::dashboard::{Dashboard, ROLLING_SLICES};
+use crate::metrics::Scrape;
+use crate::ratelimit::{merge_info, RateLimitInfo};
+use crate::request::{BodyArg, RequestPlan, UrlArg};
+use crate::status::{ErrorKind, StatusClass, StatusClasses};
+use crate::validate::SampleRequest;
+use crate::worker::{self, RemoteRun};
 
 /// Grace period added to benchmark duration before considering it hung (in seconds)
 const HANG_TIMEOUT_GRACE_SECONDS: u64 = 30;
 
+/// Slice length for adaptive steps: stability is checked after each slice
+const ADAPTIVE_SLICE_SECONDS: u32 = 5;
+
+/// How often the live dashboard redraws and checks for key presses while oha runs
+const LIVE_REFRESH: Duration = Duration::from_millis(250);
+
+/// How often a wait for oha checks whether it was cancelled
+const CANCEL_POLL: Duration = Duration::from_millis(100);
+
 /// Results from a single benchmark run
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BenchmarkResult {
     pub target_rate: u32,
     pub actual_rate: f64,
@@ -28,8 +49,45 @@ pub struct BenchmarkResult {
     /// HTTP status codes that caused errors, sorted by count (descending)
     /// Each tuple is (status_code, count)
     pub error_status_codes: Vec<(u32, u64)>,
+    /// Requests that got no response, by kind of failure, sorted by count (descending)
+    pub error_kinds: Vec<(ErrorKind, u64)>,
     /// Whether the benchmark timed out (server hung)
     pub hung: bool,
+    /// 3xx responses (only seen when redirects aren't followed, i.e. with a login configured)
+    pub redirects: u64,
+    /// The step was ended early because it stayed well past the break thresholds
+    pub aborted_early: bool,
+    /// The safety cutoff was reached during this step, stopping the whole run
+    pub safety_cutoff: bool,
+    /// Seconds the step actually ran (differs from the configured duration when adaptive,
+    /// aborted, skipped or extended)
+    pub duration_seconds: u32,
+    /// Sampled 2xx/3xx responses checked against the response assertions
+    pub validated: u64,
+    /// Checked samples failing at least one assertion
+    pub validation_failed: u64,
+    /// Failed assertions by name with their sampled counts, sorted by count (descending)
+    pub assertion_failures: Vec<(String, u64)>,
+    /// Rate-limit and block responses kept out of the request and error counts when probing
+    /// past the limiter (`--probe-past-limit`)
+    pub limited: u64,
+    /// Status codes of the `limited` responses, sorted by count (descending)
+    pub limited_status_codes: Vec<(u32, u64)>,
+    /// Rate-limit policy advertised in sampled response headers
+    pub rate_limit: Option<RateLimitInfo>,
+    /// Responses left out of the counts by an `ignore` status class
+    pub ignored: u64,
+    /// Errors added for failed assertions (the failing share of samples applied to the
+    /// requests oha counted as successful), included in `errors`
+    pub assertion_errors: u64,
+    /// Selected server metrics over the step, by label (`--metrics-url`/`--metrics-command`)
+    pub server_metrics: Vec<(String, f64)>,
+    /// Peak resource use of the machine running oha during the step
+    pub client: Option<ClientUsage>,
+    /// Latency distribution as (quantile, latency ms) points from fastest (0) to slowest (1),
+    /// used to merge the percentiles of concurrent processes
+    #[serde(skip)]
+    pub latency_distribution: Vec<(f64, f64)>,
 }
 
 /// Check if oha is installed
@@ -68,74 +126,578 @@ For more info, see: https://github.com/hatoo/oha"#
         .to_string()
 }
 
+/// Arguments for one oha process within a step
+/// `rate` and `connections` are this process's share when a step is split across processes
+struct OhaProcess<'a> {
+    url: &'a UrlArg,
+    rate: f64,
+    connections: u32,
+    seconds: u32,
+    body: Option<&'a BodyArg>,
+    content_type: Option<&'a str>,
+    auth_headers: &'a [String],
+    /// Client identity header (`--identity`) this process sends as
+    identity: Option<&'a str>,
+    tls_args: &'a [String],
+}
+
 /// Build the oha command for a benchmark run
-fn build_oha_command(config: &BenchmarkConfig, url: &str, rate: u32) -> Command {
+fn build_oha_command(config: &BenchmarkConfig, process: &OhaProcess) -> Command {
     let mut cmd = Command::new("oha");
 
     // Basic options
-    cmd.arg("-c").arg(config.ramping.connections.to_string());
-    cmd.arg("-z")
-        .arg(format!("{}s", config.ramping.duration_seconds));
-    cmd.arg("-q").arg(rate.to_string());
+    cmd.arg("-c").arg(process.connections.to_string());
+    cmd.arg("-z").arg(format!("{}s", process.seconds));
+    cmd.arg("-q").arg(
+        format!("{:.3}", process.rate)
+            .trim_end_matches('0')
+            .trim_end_matches('.'),
+    );
     cmd.arg("--latency-correction"); // Fix coordinated omission
     cmd.arg("-w"); // Wait for ongoing requests after deadline (prevents false errors)
     cmd.arg("--no-tui"); // Disable TUI for scripting
+    if config.login.is_some() {
+        cmd.arg("--redirect").arg("0"); // Surface redirects to the login page
+    }
+
+    // TLS: CA bundle, client certificate, insecure mode and SNI routing
+    cmd.args(process.tls_args);
 
     // HTTP method
     cmd.arg("-m").arg(config.method.to_string().to_uppercase());
 
     // Request body
-    if let Some(ref body) = config.body {
-        cmd.arg("-d").arg(body);
-        // Add Content-Type if not already specified
+    if let Some(body) = process.body {
+        match body {
+            BodyArg::Inline(text) => cmd.arg("-d").arg(text),
+            BodyArg::File(path) => cmd.arg("-D").arg(path),
+        };
+    }
+
+    for header in request_headers(config, process) {
+        cmd.arg("-H").arg(header);
+    }
+
+    // URL (a generated list is read by oha, which picks one per request)
+    add_url_arg(&mut cmd, process.url);
+
+    cmd
+}
+
+/// Headers sent with every request of an oha process
+fn request_headers(config: &BenchmarkConfig, process: &OhaProcess) -> Vec<String> {
+    let mut headers = Vec::new();
+
+    // Add Content-Type if not already specified
+    if let (Some(_), Some(content_type)) = (process.body, process.content_type) {
         if !config
             .headers
             .iter()
             .any(|h| h.to_lowercase().starts_with("content-type:"))
         {
-            cmd.arg("-H").arg("Content-Type: application/json");
+            headers.push(format!("Content-Type: {}", content_type));
         }
     }
 
     // User-Agent header
-    cmd.arg("-H")
-        .arg(format!("User-Agent: {}", config.user_agent));
+    headers.push(format!("User-Agent: {}", config.user_agent));
 
-    // Auth header
-    if let Some(auth_header) = generate_auth_header(&config.auth) {
-        cmd.arg("-H").arg(auth_header);
-    }
+    // Auth headers (static, token or per-process signature)
+    headers.extend(process.auth_headers.iter().cloned());
 
     // Additional headers
-    for header in &config.headers {
-        cmd.arg("-H").arg(header);
+    headers.extend(config.headers.iter().cloned());
+
+    // Client identity, so each process counts against its own rate limit
+    if let Some(identity) = process.identity {
+        headers.push(identity.to_string());
     }
 
-    // URL
-    cmd.arg(url);
+    headers
+}
 
-    cmd
+/// The request an oha process sends, for the response sampler
+fn sample_request(config: &BenchmarkConfig, process: &OhaProcess) -> Result<SampleRequest> {
+    let urls = match process.url {
+        UrlArg::Single(url) => vec![url.clone()],
+        UrlArg::File(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+            .lines()
+            .map(str::to_string)
+            .collect(),
+    };
+    let body = match process.body {
+        Some(BodyArg::Inline(text)) => Some(text.as_bytes().to_vec()),
+        Some(BodyArg::File(path)) => Some(
+            std::fs::read(path)
+                .with_context(|| format!("Failed to read body file {}", path.display()))?,
+        ),
+        None => None,
+    };
+    let connect_to = process
+        .tls_args
+        .iter()
+        .position(|a| a == "--connect-to")
+        .and_then(|i| process.tls_args.get(i + 1))
+        .cloned();
+    Ok(SampleRequest {
+        method: config.method.to_string().to_uppercase(),
+        urls,
+        body,
+        headers: request_headers(config, process),
+        connect_to,
+    })
+}
+
+/// Append the target URL or URL list to an oha command
+fn add_url_arg(cmd: &mut Command, url: &UrlArg) {
+    match url {
+        UrlArg::Single(url) => {
+            cmd.arg(url);
+        }
+        UrlArg::File(path) => {
+            cmd.arg("--urls-from-file").arg(path);
+        }
+    }
 }
 
 /// Run a single benchmark at the specified rate
-pub fn run_benchmark(config: &BenchmarkConfig, url: &str, rate: u32) -> Result<BenchmarkResult> {
-    let mut cmd = build_oha_command(config, url, rate);
+/// When the auth headers expire (signatures, minted JWTs), a step longer than their lifetime
+/// is run as consecutive parts, each signed afresh, and the parts are merged
+/// With a live dashboard the step runs in short slices so it can show in-step metrics and
+/// react to key presses (skip, stop, extend); with early abort or a safety cutoff it runs in
+/// slices of the abort window, each checked against the thresholds; adaptive steps run in
+/// slices until their figures settle
+pub fn run_benchmark(
+    config: &BenchmarkConfig,
+    url: &str,
+    rate: u32,
+    plan: &mut RequestPlan,
+    mut live: Option<&mut Dashboard>,
+) -> Result<BenchmarkResult> {
+    let mut start = scrape_metrics(plan);
+    let mut result = run_step(config, url, rate, plan, live.as_deref_mut())?;
+
+    // An expired login session shows up as 401s/redirects: log in again and retry the step once
+    if plan.renew_session(&result)? {
+        start = scrape_metrics(plan);
+        result = run_step(config, url, rate, plan, live)?;
+    }
+
+    // A failed scrape mid-ramp only leaves the step without server metrics
+    if let (Some(metrics), Some(start)) = (plan.metrics(), start) {
+        if let Ok(end) = metrics.scrape() {
+            result.server_metrics = metrics.step_values(&start, &end, result.duration_seconds);
+        }
+    }
+
+    Ok(result)
+}
+
+fn scrape_metrics(plan: &RequestPlan) -> Option<Scrape> {
+    plan.metrics().and_then(|metrics| metrics.scrape().ok())
+}
+
+/// Build a command running `command` through the platform shell
+pub fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
+/// Run one full step, split into parts when auth headers expire within it (or for live and
+/// abort-check slices)
+fn run_step(
+    config: &BenchmarkConfig,
+    url: &str,
+    rate: u32,
+    plan: &mut RequestPlan,
+    mut live: Option<&mut Dashboard>,
+) -> Result<BenchmarkResult> {
+    let adaptive = config.ramping.adaptive.as_ref();
+    let mut duration = config.ramping.max_step_seconds();
+    let mut segment = plan
+        .max_segment_seconds()
+        .map_or(duration, |s| s.min(duration))
+        .max(1);
+    if live.is_some() {
+        segment = segment.min(config.live_interval.max(1));
+    }
+    let checked = config.abort.is_enabled();
+    if checked {
+        segment = segment.min(config.abort.window_seconds.max(1));
+    }
+    if adaptive.is_some() {
+        segment = segment.min(ADAPTIVE_SLICE_SECONDS);
+    }
+
+    if segment >= duration && live.is_none() && !checked && adaptive.is_none() {
+        let mut result = run_step_part(config, url, rate, duration, plan, None)?;
+        result.duration_seconds = duration;
+        return Ok(result);
+    }
+
+    let mut parts = Vec::new();
+    let mut durations = Vec::new();
+    let mut elapsed = 0;
+    let mut exceeded_seconds = 0;
+    let mut aborted_early = false;
+    let mut safety_cutoff = false;
+    while elapsed < duration {
+        let seconds = (duration - elapsed).min(segment);
+        let part = run_step_part(config, url, rate, seconds, plan, live.as_deref_mut())?;
+        let hung = part.hung;
+
+        // Consecutive slices well past the thresholds add up to the abort window
+        if let Some(margin) = config.abort.margin_percent {
+            if exceeds_thresholds(&part, &config.thresholds, margin) {
+                exceeded_seconds += seconds;
+            } else {
+                exceeded_seconds = 0;
+            }
+        }
+        safety_cutoff = hits_cutoff(&part, &config.abort);
+
+        parts.push(part);
+        durations.push(seconds);
+        elapsed += seconds;
+        if hung || safety_cutoff {
+            break;
+        }
+        if exceeded_seconds >= config.abort.window_seconds && elapsed < duration {
+            aborted_early = true;
+            break;
+        }
+
+        if let Some(dashboard) = live.as_deref_mut() {
+            let recent = parts.len().saturating_sub(ROLLING_SLICES);
+            dashboard.slice_done(
+                merge_sequential(&parts[recent..], &durations[recent..], rate),
+                merge_sequential(&parts, &durations, rate),
+            );
+            duration += dashboard.take_extension();
+            if dashboard.ends_step() {
+                break;
+            }
+        }
+
+        if let Some(adaptive) = adaptive {
+            let window = (adaptive.min_seconds / (2 * segment)).max(1) as usize;
+            if elapsed >= adaptive.min_seconds
+                && is_stable(&parts, &durations, rate, window, adaptive.tolerance_percent)
+            {
+                break;
+            }
+        }
+    }
+
+    let mut result = merge_sequential(&parts, &durations, rate);
+    result.aborted_early = aborted_early;
+    result.safety_cutoff = safety_cutoff;
+    result.duration_seconds = elapsed;
+    Ok(result)
+}
+
+/// Whether the latest `window` slices agree with the `window` slices before them on p50,
+/// p99 and throughput within `tolerance_percent`
+fn is_stable(
+    parts: &[BenchmarkResult],
+    durations: &[u32],
+    rate: u32,
+    window: usize,
+    tolerance_percent: f64,
+) -> bool {
+    if parts.len() < 2 * window {
+        return false;
+    }
+    let split = parts.len() - window;
+    let start = split - window;
+    let before = merge_sequential(&parts[start..split], &durations[start..split], rate);
+    let latest = merge_sequential(&parts[split..], &durations[split..], rate);
+
+    let within = |a: f64, b: f64| {
+        let base = a.abs().max(b.abs());
+        base == 0.0 || (a - b).abs() / base * 100.0 <= tolerance_percent
+    };
+    within(before.p50_latency_ms, latest.p50_latency_ms)
+        && within(before.p99_latency_ms, latest.p99_latency_ms)
+        && within(before.actual_rate, latest.actual_rate)
+}
+
+/// Run `seconds` of a step at the specified rate
+/// When the plan has several body variants, the rate and connections are split across
+/// one oha process per variant and the results are merged
+/// With workers, every process is run once per worker, each with its share of the rate
+fn run_step_part(
+    config: &BenchmarkConfig,
+    url: &str,
+    rate: u32,
+    seconds: u32,
+    plan: &mut RequestPlan,
+    live: Option<&mut Dashboard>,
+) -> Result<BenchmarkResult> {
+    let url_arg = plan.next_url(url)?;
+    let bodies = plan.next_bodies()?;
+    // One process per body variant, or per client identity when there are more of those
+    let slots = bodies.len().max(config.identities.len());
+    let processes = (slots * config.workers.len().max(1)) as u32;
+    let process_rate = rate as f64 / processes as f64;
+    let process_connections = (config.ramping.connections / processes).max(1);
+    let tls_args = plan.tls_args(url);
+
+    let mut commands = Vec::with_capacity(processes as usize);
+    let mut sampler = None;
+    for index in 0..processes as usize {
+        let slot = index % slots;
+        let body = &bodies[slot % bodies.len()];
+        let identity = match config.identities.len() {
+            0 => None,
+            n => Some(config.identities[slot % n].as_str()),
+        };
+        let auth_headers = plan.auth_headers(config.method, &url_arg, body.as_ref(), seconds)?;
+        let process = OhaProcess {
+            url: &url_arg,
+            rate: process_rate,
+            connections: process_connections,
+            seconds,
+            body: body.as_ref(),
+            content_type: plan.content_type(),
+            auth_headers: &auth_headers,
+            identity,
+            tls_args: &tls_args,
+        };
+
+        // Responses are sampled from the first process's requests
+        if sampler.is_none() {
+            if let Some(validator) = plan.validator() {
+                sampler = Some((validator.clone(), sample_request(config, &process)?));
+            }
+        }
+        commands.push(build_oha_command(config, &process));
+    }
+
+    // Calculate timeout: benchmark duration + grace period (shared by all processes)
+    let timeout = Duration::from_secs(seconds as u64 + HANG_TIMEOUT_GRACE_SECONDS);
+
+    // Workers get their processes ready first, so they all start on the same signal
+    let prepared = if config.workers.is_empty() {
+        None
+    } else {
+        let mut runs = Vec::with_capacity(commands.len());
+        for (index, cmd) in commands.iter().enumerate() {
+            let worker = &config.workers[index / slots];
+            runs.push(RemoteRun::prepare(
+                worker,
+                config.worker_token.as_deref(),
+                cmd,
+                seconds,
+                timeout,
+            )?);
+        }
+        Some(runs)
+    };
+
+    // oha doesn't check bodies or headers: sample requests alongside it for the response
+    // assertions and rate-limit headers
+    let sampling = match sampler {
+        Some((validator, request)) => Some(validator.start(request, seconds)?),
+        None => None,
+    };
+
+    let mut parts = match prepared {
+        None => run_local(config, commands, rate, seconds, timeout, live)?,
+        Some(runs) => run_remote(config, runs, rate, seconds, timeout, live)?,
+    };
+
+    let mut result = if parts.len() == 1 {
+        parts.remove(0)
+    } else {
+        merge_results(&parts, rate)
+    };
+    if let Some(handle) = sampling {
+        if let Ok(report) = handle.join() {
+            report.apply(&mut result);
+        }
+    }
+    if config.probe_past_limit {
+        separate_limited(&mut result, &config.status_classes);
+    }
+    Ok(result)
+}
+
+/// Run the oha commands of a step part on this machine
+fn run_local(
+    config: &BenchmarkConfig,
+    commands: Vec<Command>,
+    rate: u32,
+    seconds: u32,
+    timeout: Duration,
+    live: Option<&mut Dashboard>,
+) -> Result<Vec<BenchmarkResult>> {
+    let mut children = Vec::with_capacity(commands.len());
+    for mut cmd in commands {
+        cmd.stdin(Stdio::null());
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+
+        match cmd.spawn() {
+            Ok(child) => children.push(child),
+            Err(e) => {
+                // Don't leave already-started processes running
+                for mut child in children {
+                    child.kill().ok();
+                    child.wait().ok();
+                }
+                return Err(e).context("Failed to spawn oha process");
+            }
+        }
+    }
+
+    // Watch the load generator itself, so its own limits aren't blamed on the server
+    let monitor = client::start_monitor(children.iter().map(|c| c.id()).collect(), seconds);
+
+    let deadline = std::time::Instant::now() + timeout;
+
+    // Keep the dashboard responsive while the processes run
+    if let Some(dashboard) = live {
+        while std::time::Instant::now() < deadline
+            && children
+                .iter_mut()
+                .any(|c| matches!(c.try_wait(), Ok(None)))
+        {
+            dashboard.wait(LIVE_REFRESH);
+        }
+    }
+
+    let mut parts = Vec::with_capacity(children.len());
+    for child in children {
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        parts.push(wait_for_oha(
+            child,
+            remaining,
+            rate,
+            seconds,
+            &config.status_classes,
+        )?);
+    }
+
+    // The usage covers all processes; merging keeps it from the first part
+    if let (Some(handle), Some(first)) = (monitor, parts.first_mut()) {
+        first.client = handle.join().ok().flatten();
+    }
+    Ok(parts)
+}
 
-    cmd.stdout(Stdio::piped());
-    cmd.stderr(Stdio::piped());
+/// Start prepared runs on their workers together and collect their results
+fn run_remote(
+    config: &BenchmarkConfig,
+    mut runs: Vec<RemoteRun>,
+    rate: u32,
+    seconds: u32,
+    timeout: Duration,
+    live: Option<&mut Dashboard>,
+) -> Result<Vec<BenchmarkResult>> {
+    worker::start_all(&mut runs)?;
+    let handles: Vec<_> = runs
+        .into_iter()
+        .map(|run| std::thread::spawn(move || run.wait(timeout)))
+        .collect();
+
+    // Keep the dashboard responsive while the workers run
+    if let Some(dashboard) = live {
+        while handles.iter().any(|h| !h.is_finished()) {
+            dashboard.wait(LIVE_REFRESH);
+        }
+    }
+
+    let mut parts = Vec::with_capacity(handles.len());
+    for handle in handles {
+        let outcome = handle
+            .join()
+            .map_err(|_| anyhow::anyhow!("Worker thread panicked"))??;
+        let mut part = match outcome.output {
+            Some(ref output) => parse_oha_output(output, rate, seconds, &config.status_classes)?,
+            None => hung_result(rate),
+        };
+        part.client = outcome.client;
+        parts.push(part);
+    }
+    Ok(parts)
+}
 
-    let mut child = cmd.spawn().context("Failed to spawn oha process")?;
+/// Move rate-limit and block responses out of the errors into their own series, so the error
+/// rate only measures the requests the limiter let through to the backend
+fn separate_limited(result: &mut BenchmarkResult, classes: &StatusClasses) {
+    let (limited, errors): (Vec<_>, Vec<_>) =
+        result.error_status_codes.iter().partition(|(code, _)| {
+            matches!(
+                classes.classify(*code),
+                StatusClass::RateLimit | StatusClass::Block
+            )
+        });
+    let count: u64 = limited.iter().map(|(_, c)| c).sum();
+    if count == 0 {
+        return;
+    }
+    result.limited = count;
+    result.limited_status_codes = limited;
+    result.error_status_codes = errors;
+    result.errors = result.errors.saturating_sub(count);
+    result.total_requests = result.total_requests.saturating_sub(count);
+    result.error_rate = if result.total_requests > 0 {
+        (result.errors as f64 / result.total_requests as f64) * 100.0
+    } else {
+        0.0
+    };
+}
 
-    // Calculate timeout: benchmark duration + grace period
-    let timeout_secs = config.ramping.duration_seconds as u64 + HANG_TIMEOUT_GRACE_SECONDS;
-    let timeout = Duration::from_secs(timeout_secs);
+/// Wait for an oha process to finish and parse its output, or mark it hung on timeout
+fn wait_for_oha(
+    child: std::process::Child,
+    timeout: Duration,
+    rate: u32,
+    duration_seconds: u32,
+    classes: &StatusClasses,
+) -> Result<BenchmarkResult> {
+    let cancel = AtomicBool::new(false);
+    match collect_oha_output(child, timeout, &cancel)? {
+        Some((output, _)) => parse_oha_output(&output, rate, duration_seconds, classes),
+        None => Ok(hung_result(rate)),
+    }
+}
 
-    // Wait with timeout
-    match child
-        .wait_timeout(timeout)
-        .context("Failed to wait for oha")?
-    {
-        Some(_status) => {
+/// Wait for an oha process to finish and return its combined output and whether it succeeded
+/// Returns None when it timed out (server hung); the process is killed, as it is (with an
+/// error) when `cancel` is set meanwhile
+pub fn collect_oha_output(
+    mut child: std::process::Child,
+    timeout: Duration,
+    cancel: &AtomicBool,
+) -> Result<Option<(String, bool)>> {
+    // Wait with timeout, checking for cancellation in between
+    let deadline = std::time::Instant::now() + timeout;
+    let status = loop {
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        let status = child
+            .wait_timeout(remaining.min(CANCEL_POLL))
+            .context("Failed to wait for oha")?;
+        if status.is_some() || remaining <= CANCEL_POLL {
+            break status;
+        }
+        if cancel.load(Ordering::Relaxed) {
+            child.kill().ok();
+            child.wait().ok();
+            bail!("Cancelled");
+        }
+    };
+    match status {
+        Some(status) => {
             // Process completed within timeout
             let mut stdout = String::new();
             let mut stderr = String::new();
@@ -148,28 +710,246 @@ pub fn run_benchmark(config: &BenchmarkConfig, url: &str, rate: u32) -> Result<B
             }
 
             // Combine stdout and stderr for parsing
-            let full_output = format!("{}\n{}", stdout, stderr);
-
-            parse_oha_output(&full_output, rate, config.ramping.duration_seconds)
+            Ok(Some((format!("{}\n{}", stdout, stderr), status.success())))
         }
         None => {
             // Timeout - process hung, kill it
             child.kill().ok();
             child.wait().ok(); // Reap the zombie
+            Ok(None)
+        }
+    }
+}
+
+/// Result of a step whose oha process hung
+fn hung_result(rate: u32) -> BenchmarkResult {
+    BenchmarkResult {
+        target_rate: rate,
+        hung: true,
+        error_rate: 100.0,
+        ..Default::default()
+    }
+}
+
+/// Quantiles kept in a merged latency distribution (those oha reports, plus fastest and slowest)
+const DISTRIBUTION_QUANTILES: [f64; 11] = [
+    0.0, 0.1, 0.25, 0.5, 0.75, 0.9, 0.95, 0.99, 0.999, 0.9999, 1.0,
+];
 
-            // Return a hung result
-            Ok(BenchmarkResult {
-                target_rate: rate,
-                hung: true,
-                error_rate: 100.0,
-                ..Default::default()
-            })
+/// Share of a part's requests at or below `latency`, interpolated between its distribution points
+fn distribution_share(points: &[(f64, f64)], latency: f64) -> f64 {
+    let Some(&(_, fastest)) = points.first() else {
+        return 0.0;
+    };
+    if latency < fastest {
+        return 0.0;
+    }
+    for pair in points.windows(2) {
+        let ((q0, l0), (q1, l1)) = (pair[0], pair[1]);
+        if latency < l1 {
+            return q0 + (q1 - q0) * (latency - l0) / (l1 - l0);
+        }
+    }
+    1.0
+}
+
+/// Latency at quantile `q` of the combined distribution of several parts, weighted by share
+fn merged_quantile(parts: &[(&[(f64, f64)], f64)], q: f64) -> f64 {
+    let mut low = parts
+        .iter()
+        .filter_map(|(points, _)| points.first().map(|p| p.1))
+        .fold(f64::MAX, f64::min);
+    let mut high = parts
+        .iter()
+        .filter_map(|(points, _)| points.last().map(|p| p.1))
+        .fold(0.0, f64::max);
+    if low >= high {
+        return high;
+    }
+    // The combined share at or below a latency only grows with it, so bisect
+    for _ in 0..64 {
+        let mid = (low + high) / 2.0;
+        let share: f64 = parts
+            .iter()
+            .map(|(points, weight)| distribution_share(points, mid) * weight)
+            .sum();
+        if share >= q {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    high
+}
+
+/// Merge results from oha processes that ran concurrently against the same target
+/// Rates and counts are summed; latency percentiles are read from the combined latency
+/// distribution (averaged weighted by request count only when a part has no distribution)
+pub fn merge_results(parts: &[BenchmarkResult], target_rate: u32) -> BenchmarkResult {
+    let mut merged = BenchmarkResult {
+        target_rate,
+        ..Default::default()
+    };
+
+    let total_weight: f64 = parts.iter().map(|p| p.total_requests as f64).sum();
+    let weight = |p: &BenchmarkResult| {
+        if total_weight > 0.0 {
+            p.total_requests as f64 / total_weight
+        } else {
+            1.0 / parts.len() as f64
+        }
+    };
+
+    let mut status_counts: std::collections::HashMap<u32, u64> = std::collections::HashMap::new();
+    let mut limited_counts: std::collections::HashMap<u32, u64> = std::collections::HashMap::new();
+    let mut kind_counts: std::collections::HashMap<ErrorKind, u64> =
+        std::collections::HashMap::new();
+    let mut assertion_counts: std::collections::HashMap<String, u64> =
+        std::collections::HashMap::new();
+    let mut transfer_bytes = 0.0;
+
+    for part in parts {
+        let w = weight(part);
+        merged.actual_rate += part.actual_rate;
+        merged.avg_latency_ms += part.avg_latency_ms * w;
+        merged.p50_latency_ms += part.p50_latency_ms * w;
+        merged.p90_latency_ms += part.p90_latency_ms * w;
+        merged.p99_latency_ms += part.p99_latency_ms * w;
+        merged.max_latency_ms = merged.max_latency_ms.max(part.max_latency_ms);
+        merged.total_requests += part.total_requests;
+        merged.errors += part.errors;
+        merged.hung |= part.hung;
+        merged.redirects += part.redirects;
+        merged.ignored += part.ignored;
+        merged.limited += part.limited;
+        merged.validated += part.validated;
+        merged.validation_failed += part.validation_failed;
+        merged.assertion_errors += part.assertion_errors;
+        transfer_bytes += parse_size_to_bytes(&part.transfer_rate);
+
+        for (code, count) in &part.error_status_codes {
+            *status_counts.entry(*code).or_insert(0) += count;
         }
+        merge_info(&mut merged.rate_limit, part.rate_limit.as_ref());
+        merge_usage(&mut merged.client, part.client.as_ref());
+        for (code, count) in &part.limited_status_codes {
+            *limited_counts.entry(*code).or_insert(0) += count;
+        }
+        for (kind, count) in &part.error_kinds {
+            *kind_counts.entry(*kind).or_insert(0) += count;
+        }
+        for (name, count) in &part.assertion_failures {
+            *assertion_counts.entry(name.clone()).or_insert(0) += count;
+        }
+    }
+
+    merged.assertion_failures = assertion_counts.into_iter().collect();
+    merged
+        .assertion_failures
+        .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    merged.error_status_codes = status_counts.into_iter().collect();
+    merged
+        .error_status_codes
+        .sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+
+    merged.limited_status_codes = limited_counts.into_iter().collect();
+    merged
+        .limited_status_codes
+        .sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+
+    merged.error_kinds = kind_counts.into_iter().collect();
+    merged
+        .error_kinds
+        .sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+
+    merged.error_rate = if merged.total_requests > 0 {
+        (merged.errors as f64 / merged.total_requests as f64) * 100.0
+    } else {
+        parts.iter().map(|p| p.error_rate).fold(0.0, f64::max)
+    };
+
+    let distributions: Vec<(&[(f64, f64)], f64)> = parts
+        .iter()
+        .filter(|p| weight(p) > 0.0)
+        .map(|p| (p.latency_distribution.as_slice(), weight(p)))
+        .collect();
+    if !distributions.is_empty() && distributions.iter().all(|(points, _)| points.len() >= 2) {
+        merged.latency_distribution = DISTRIBUTION_QUANTILES
+            .iter()
+            .map(|&q| (q, merged_quantile(&distributions, q)))
+            .collect();
+        merged.p50_latency_ms = merged_quantile(&distributions, 0.5);
+        merged.p90_latency_ms = merged_quantile(&distributions, 0.9);
+        merged.p99_latency_ms = merged_quantile(&distributions, 0.99);
+    }
+
+    if transfer_bytes > 0.0 {
+        merged.transfer_rate = format_bytes(transfer_bytes);
+    }
+
+    merged
+}
+
+/// Merge results from consecutive parts of one step
+/// Counts are summed; rates are averaged weighted by each part's duration
+fn merge_sequential(
+    parts: &[BenchmarkResult],
+    durations: &[u32],
+    target_rate: u32,
+) -> BenchmarkResult {
+    let mut merged = merge_results(parts, target_rate);
+
+    let total: f64 = durations.iter().map(|&d| d as f64).sum();
+    let per_second = |value: &dyn Fn(&BenchmarkResult) -> f64| {
+        parts
+            .iter()
+            .zip(durations)
+            .map(|(p, &d)| value(p) * d as f64)
+            .sum::<f64>()
+            / total
+    };
+
+    merged.actual_rate = per_second(&|p| p.actual_rate);
+    let transfer_bytes = per_second(&|p| parse_size_to_bytes(&p.transfer_rate));
+    if transfer_bytes > 0.0 {
+        merged.transfer_rate = format_bytes(transfer_bytes);
+    }
+
+    merged
+}
+
+/// Parse an oha size string such as "12.34 KiB" into bytes
+fn parse_size_to_bytes(value: &str) -> f64 {
+    let mut parts = value.split_whitespace();
+    let number: f64 = parts.next().and_then(|n| n.parse().ok()).unwrap_or(0.0);
+    let multiplier = match parts.next().unwrap_or("B") {
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "KB" => 1000.0,
+        "MB" => 1000.0 * 1000.0,
+        "GB" => 1000.0 * 1000.0 * 1000.0,
+        _ => 1.0,
+    };
+    number * multiplier
+}
+
+/// Format bytes in the same style as oha
+fn format_bytes(bytes: f64) -> String {
+    if bytes >= 1024.0 * 1024.0 * 1024.0 {
+        format!("{:.2} GiB", bytes / (1024.0 * 1024.0 * 1024.0))
+    } else if bytes >= 1024.0 * 1024.0 {
+        format!("{:.2} MiB", bytes / (1024.0 * 1024.0))
+    } else if bytes >= 1024.0 {
+        format!("{:.2} KiB", bytes / 1024.0)
+    } else {
+        format!("{:.0} B", bytes)
     }
 }
 
 /// Run a warmup period
-pub fn run_warmup(config: &BenchmarkConfig, url: &str) -> Result<()> {
+pub fn run_warmup(config: &BenchmarkConfig, url: &str, plan: &mut RequestPlan) -> Result<()> {
     if config.warmup_seconds == 0 {
         return Ok(());
     }
@@ -183,11 +963,32 @@ pub fn run_warmup(config: &BenchmarkConfig, url: &str) -> Result<()> {
     // Add headers
     cmd.arg("-H")
         .arg(format!("User-Agent: {}", config.user_agent));
-    if let Some(auth_header) = generate_auth_header(&config.auth) {
-        cmd.arg("-H").arg(auth_header);
+    // Warmup requests are plain GETs without a body
+    let url_arg = plan.next_url(url)?;
+    for header in plan.auth_headers(HttpMethod::Get, &url_arg, None, config.warmup_seconds)? {
+        cmd.arg("-H").arg(header);
     }
 
-    cmd.arg(url);
+    cmd.args(plan.tls_args(url));
+    add_url_arg(&mut cmd, &url_arg);
+
+    // With workers, the first one warms the target up
+    if let Some(worker) = config.workers.first() {
+        let timeout =
+            Duration::from_secs(config.warmup_seconds as u64 + HANG_TIMEOUT_GRACE_SECONDS);
+        let mut run = RemoteRun::prepare(
+            worker,
+            config.worker_token.as_deref(),
+            &cmd,
+            config.warmup_seconds,
+            timeout,
+        )?;
+        run.start()?;
+        if !run.wait(timeout)?.success {
+            bail!("Warmup failed on worker {}", worker);
+        }
+        return Ok(());
+    }
 
     cmd.stdout(Stdio::null());
     cmd.stderr(Stdio::null());
@@ -231,6 +1032,7 @@ fn parse_oha_output(
     output: &str,
     target_rate: u32,
     duration_seconds: u32,
+    classes: &StatusClasses,
 ) -> Result<BenchmarkResult> {
     let mut result = BenchmarkResult {
         target_rate,
@@ -276,6 +1078,7 @@ fn parse_oha_output(
     if let Some(caps) = p99_re.captures(output) {
         result.p99_latency_ms = parse_time_to_ms(&caps[1], &caps[2]);
     }
+    result.latency_distribution = parse_latency_distribution(output, &result)?;
 
     // Parse status code responses to get total requests
     // Matches: [200] 28 responses, [404] 5 responses, etc.
@@ -287,38 +1090,58 @@ fn parse_oha_output(
     for caps in status_re.captures_iter(output) {
         let status_code: u32 = caps[1].parse().unwrap_or(0);
         let count: u64 = caps[2].parse().unwrap_or(0);
+        if (300..400).contains(&status_code) {
+            result.redirects += count;
+        }
+
+        let class = classes.classify(status_code);
+        if class == StatusClass::Ignore {
+            result.ignored += count;
+            continue;
+        }
         total_requests += count;
 
-        // Count non-2xx/3xx as errors
-        if !(200..400).contains(&status_code) {
+        // Count error classes (by default anything but 2xx/3xx) as errors
+        if class.is_error() {
             error_responses += count;
             error_status_codes.push((status_code, count));
         }
     }
 
     // Sort by count descending to get most common errors first
-    error_status_codes.sort_by(|a, b| b.1.cmp(&a.1));
+    error_status_codes.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
 
     // Parse error distribution for additional errors (connection errors, timeouts, etc.)
     // Matches: [2] aborted due to deadline, [1] connection refused, etc.
     let error_re = Regex::new(r"Error distribution:\s*\n((?:\s+\[\d+\][^\n]+\n?)+)")?;
+    let mut error_kinds: Vec<(ErrorKind, u64)> = Vec::new();
     if let Some(caps) = error_re.captures(output) {
         let error_section = &caps[1];
-        let error_count_re = Regex::new(r"\[(\d+)\]")?;
+        let error_count_re = Regex::new(r"\[(\d+)\]\s*([^\n]*)")?;
         for err_caps in error_count_re.captures_iter(error_section) {
             let count: u64 = err_caps[1].parse().unwrap_or(0);
             result.errors += count;
             // Connection errors are attempted requests that never got a response
             total_requests += count;
+
+            // Several messages can normalize to the same kind
+            let kind = ErrorKind::from_message(&err_caps[2]);
+            match error_kinds.iter_mut().find(|(k, _)| *k == kind) {
+                Some((_, total)) => *total += count,
+                None => error_kinds.push((kind, count)),
+            }
         }
     }
+    error_kinds.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    result.error_kinds = error_kinds;
 
     result.errors += error_responses;
     result.total_requests = total_requests;
     result.error_status_codes = error_status_codes;
 
-    // Recalculate error rate if we have total requests
-    if result.total_requests > 0 && result.errors > 0 {
+    // Recalculate error rate if we have total requests (oha's success rate doesn't know
+    // about the status classes)
+    if result.total_requests > 0 {
         result.error_rate = (result.errors as f64 / result.total_requests as f64) * 100.0;
     }
 
@@ -336,6 +1159,33 @@ fn parse_oha_output(
     Ok(result)
 }
 
+/// Read every percentile of the "Response time distribution" section, bounded by the fastest
+/// and slowest response, as (quantile, latency ms) points
+fn parse_latency_distribution(output: &str, result: &BenchmarkResult) -> Result<Vec<(f64, f64)>> {
+    let fastest_re = Regex::new(r"Fastest:\s+([\d.]+)\s*(us|ms|s|m)")?;
+    let (Some(fastest), true) = (fastest_re.captures(output), result.max_latency_ms > 0.0) else {
+        return Ok(Vec::new());
+    };
+    let mut points = vec![(0.0, parse_time_to_ms(&fastest[1], &fastest[2]))];
+
+    let percentile_re = Regex::new(r"([\d.]+)%\s+in\s+([\d.]+)\s*(us|ms|s|m)")?;
+    for caps in percentile_re.captures_iter(output) {
+        let quantile = caps[1].parse::<f64>().unwrap_or(0.0) / 100.0;
+        let latency = parse_time_to_ms(&caps[2], &caps[3]);
+        points.push((quantile, latency));
+    }
+    points.push((1.0, result.max_latency_ms));
+
+    // Keep the points increasing in both quantile and latency
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut latency = 0.0;
+    for point in &mut points {
+        latency = point.1.max(latency);
+        point.1 = latency;
+    }
+    Ok(points)
+}
+
 /// Convert time value to milliseconds
 fn parse_time_to_ms(value: &str, unit: &str) -> f64 {
     let v: f64 = value.parse().unwrap_or(0.0);
@@ -347,3 +1197,101 @@ fn parse_time_to_ms(value: &str, unit: &str) -> f64 {
         _ => v,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// oha output with every request at `latency` ms, as one process reports it
+    fn oha_output(latency: f64, requests: u64) -> String {
+        let line = |pct: &str| format!("  {}% in {:.4} secs\n", pct, latency / 1000.0);
+        let mut output = format!(
+            "Summary:\n  Success rate:\t100.00%\n  Total:\t10.0 secs\n  Slowest:\t{s:.4} secs\n  \
+             Fastest:\t{s:.4} secs\n  Average:\t{s:.4} secs\n  Requests/sec:\t{rps:.4}\n\n\
+             Response time distribution:\n",
+            s = latency / 1000.0,
+            rps = requests as f64 / 10.0
+        );
+        for pct in [
+            "10.00", "25.00", "50.00", "75.00", "90.00", "95.00", "99.00", "99.90",
+        ] {
+            output.push_str(&line(pct));
+        }
+        output.push_str(&format!(
+            "\nStatus code distribution:\n  [200] {} responses\n",
+            requests
+        ));
+        output
+    }
+
+    fn parse(output: &str) -> BenchmarkResult {
+        parse_oha_output(output, 100, 10, &StatusClasses::default()).unwrap()
+    }
+
+    #[test]
+    fn parses_the_latency_distribution() {
+        let output = "Summary:\n  Slowest:\t0.7763 secs\n  Fastest:\t0.1427 secs\n\n\
+            Response time distribution:\n  10.00% in 0.1580 secs\n  50.00% in 0.1960 secs\n  \
+            99.00% in 0.7763 secs\n";
+        let result = parse(output);
+        let points: Vec<(f64, f64)> = result
+            .latency_distribution
+            .iter()
+            .map(|&(q, ms)| (q, (ms * 10.0).round() / 10.0))
+            .collect();
+        assert_eq!(
+            points,
+            vec![
+                (0.0, 142.7),
+                (0.1, 158.0),
+                (0.5, 196.0),
+                (0.99, 776.3),
+                (1.0, 776.3)
+            ]
+        );
+    }
+
+    #[test]
+    fn merges_percentiles_from_the_combined_distribution() {
+        // Two workers, one fast and one slow: a weighted average would put every percentile
+        // at 55ms, which no request took
+        let fast = parse(&oha_output(10.0, 9000));
+        let slow = parse(&oha_output(100.0, 1000));
+        let merged = merge_results(&[fast, slow], 1000);
+
+        assert_eq!(merged.total_requests, 10_000);
+        assert!((merged.p50_latency_ms - 10.0).abs() < 0.01);
+        assert!((merged.p90_latency_ms - 10.0).abs() < 0.01);
+        assert!((merged.p99_latency_ms - 100.0).abs() < 0.01);
+        assert!((merged.avg_latency_ms - 19.0).abs() < 0.01);
+        assert_eq!(merged.max_latency_ms, 100.0);
+    }
+
+    #[test]
+    fn merges_overlapping_distributions() {
+        let mut a = BenchmarkResult {
+            total_requests: 100,
+            latency_distribution: vec![(0.0, 0.0), (1.0, 100.0)],
+            ..Default::default()
+        };
+        let b = BenchmarkResult {
+            total_requests: 100,
+            latency_distribution: vec![(0.0, 100.0), (1.0, 200.0)],
+            ..Default::default()
+        };
+        // Uniform over 0-200ms together
+        let merged = merge_results(&[a.clone(), b], 100);
+        assert!((merged.p50_latency_ms - 100.0).abs() < 0.01);
+        assert!((merged.p90_latency_ms - 180.0).abs() < 0.01);
+
+        // A part without a distribution falls back to the weighted average
+        a.latency_distribution.clear();
+        a.p50_latency_ms = 50.0;
+        let other = BenchmarkResult {
+            total_requests: 100,
+            p50_latency_ms: 150.0,
+            ..Default::default()
+        };
+        assert!((merge_results(&[a, other], 100).p50_latency_ms - 100.0).abs() < 0.01);
+    }
+}
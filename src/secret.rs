@@ -0,0 +1,139 @@
+use anyhow::{bail, Context, Result};
+use std::io::Read;
+use std::sync::Mutex;
+
+use crate::auth::generate_auth_header;
+use crate::config::BenchmarkConfig;
+
+/// Replacement shown in place of redacted values
+const REDACTED: &str = "****";
+
+/// Secrets shorter than this are not scrubbed from free text (they would match everywhere)
+const MIN_REDACT_LEN: usize = 4;
+
+/// Header names whose values are always redacted
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+];
+
+/// Fragments that mark a header name as sensitive (e.g. X-Auth-Token, X-Client-Secret)
+const SENSITIVE_HEADER_FRAGMENTS: &[&str] = &["token", "secret", "api-key", "apikey", "password"];
+
+/// Every secret value seen this session, scrubbed from output and error messages
+static SECRETS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Resolve a secret value given on the command line or in the menu
+/// `env:NAME` reads an environment variable, `file:PATH` reads a file (trailing newline
+/// removed) and `-` reads stdin; anything else is used literally
+pub fn resolve_secret(value: &str) -> Result<String> {
+    let secret = if let Some(name) = value.strip_prefix("env:") {
+        std::env::var(name).with_context(|| format!("Environment variable {} is not set", name))?
+    } else if let Some(path) = value.strip_prefix("file:") {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read secret file {}", path))?
+            .trim_end_matches(['\r', '\n'])
+            .to_string()
+    } else if value == "-" {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .context("Failed to read secret from stdin")?;
+        let input = input.trim_end_matches(['\r', '\n']).to_string();
+        if input.is_empty() {
+            bail!("No secret on stdin (stdin can only supply one secret)");
+        }
+        input
+    } else {
+        value.to_string()
+    };
+
+    register(&secret);
+    Ok(secret)
+}
+
+/// Resolve an optional secret value
+pub fn resolve_optional(value: Option<&str>) -> Result<Option<String>> {
+    value.map(resolve_secret).transpose()
+}
+
+/// Remember a secret so it is scrubbed by `redact`
+pub fn register(secret: &str) {
+    if secret.len() < MIN_REDACT_LEN {
+        return;
+    }
+    let mut secrets = SECRETS.lock().unwrap_or_else(|e| e.into_inner());
+    if !secrets.iter().any(|s| s == secret) {
+        secrets.push(secret.to_string());
+        // Longest first so a secret containing another is replaced whole
+        secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+    }
+}
+
+/// Register the auth secrets and sensitive header values of a config
+pub fn register_config(config: &BenchmarkConfig) {
+    let auth = &config.auth;
+    for secret in [
+        &auth.password,
+        &auth.token,
+        &auth.client_secret,
+        &auth.signing_key,
+        &auth.session_token,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        register(secret);
+    }
+    if let Some(ref login) = config.login {
+        register(&login.body);
+    }
+
+    // The encoded/full header values (e.g. the base64 Basic credentials) leak the same secrets
+    let headers = generate_auth_header(auth)
+        .into_iter()
+        .chain(auth.custom_header.clone())
+        .chain(config.headers.iter().cloned());
+    for header in headers {
+        if let Some((name, value)) = header.split_once(':') {
+            if is_sensitive_header(name) {
+                let value = value.trim();
+                register(value);
+                // "Bearer abc" / "Basic abc": the credential alone may show up too
+                if let Some((_, credential)) = value.split_once(' ') {
+                    register(credential);
+                }
+            }
+        }
+    }
+}
+
+/// Whether a header's value should never be shown
+pub fn is_sensitive_header(name: &str) -> bool {
+    let name = name.trim().to_lowercase();
+    SENSITIVE_HEADERS.contains(&name.as_str())
+        || SENSITIVE_HEADER_FRAGMENTS.iter().any(|f| name.contains(f))
+}
+
+/// Show a `Name: value` header with the value hidden if the header is sensitive
+pub fn redact_header(header: &str) -> String {
+    match header.split_once(':') {
+        Some((name, _)) if is_sensitive_header(name) => format!("{}: {}", name.trim(), REDACTED),
+        _ => redact(header),
+    }
+}
+
+/// Replace every registered secret in `text`
+pub fn redact(text: &str) -> String {
+    let secrets = SECRETS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut out = text.to_string();
+    for secret in secrets.iter() {
+        if out.contains(secret.as_str()) {
+            out = out.replace(secret.as_str(), REDACTED);
+        }
+    }
+    out
+}
@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::menu::SessionState;
+
+/// File holding the interactive settings from the last launch
+const SESSION_FILE: &str = "session.json";
+
+/// File holding the named presets
+const PRESETS_FILE: &str = "presets.json";
+
+/// Named presets of interactive settings, sorted by name
+pub type Presets = BTreeMap<String, SessionState>;
+
+/// Get the config directory (platform config dir, e.g. ~/.config/ohabench)
+pub fn default_config_dir() -> PathBuf {
+    dirs::config_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("ohabench")
+}
+
+/// Load the settings saved by the last launch, or the defaults if there are none
+/// An unreadable file is reported and ignored rather than blocking the menu
+pub fn load_session() -> SessionState {
+    let path = default_config_dir().join(SESSION_FILE);
+    if !path.exists() {
+        return SessionState::default();
+    }
+    match read_json::<SessionState>(&path) {
+        Ok(mut state) => {
+            state.sanitize();
+            state
+        }
+        Err(e) => {
+            eprintln!("Warning: ignoring saved settings: {:#}", e);
+            SessionState::default()
+        }
+    }
+}
+
+/// Save the settings so the next launch starts from them
+pub fn save_session(state: &SessionState) -> Result<()> {
+    write_json(&default_config_dir().join(SESSION_FILE), state)
+}
+
+/// Load the named presets (none if the file doesn't exist yet)
+pub fn load_presets() -> Result<Presets> {
+    let path = default_config_dir().join(PRESETS_FILE);
+    if !path.exists() {
+        return Ok(Presets::new());
+    }
+    let mut presets: Presets = read_json(&path)?;
+    for state in presets.values_mut() {
+        state.sanitize();
+    }
+    Ok(presets)
+}
+
+/// Save `state` as the preset `name`, replacing any preset with that name
+pub fn save_preset(name: &str, state: &SessionState) -> Result<()> {
+    let mut presets = load_presets()?;
+    presets.insert(name.to_string(), state.clone());
+    write_json(&default_config_dir().join(PRESETS_FILE), &presets)
+}
+
+/// Delete the preset `name`; returns whether it existed
+pub fn delete_preset(name: &str) -> Result<bool> {
+    let mut presets = load_presets()?;
+    if presets.remove(name).is_none() {
+        return Ok(false);
+    }
+    write_json(&default_config_dir().join(PRESETS_FILE), &presets)?;
+    Ok(true)
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let json = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&json).with_context(|| format!("Invalid settings file {}", path.display()))
+}
+
+/// Write via a temp file and rename so an interrupted write can't corrupt the settings
+fn write_json<T: serde::Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create config directory {}", dir.display()))?;
+    }
+    let json = serde_json::to_string_pretty(value)?;
+    let temp = path.with_extension("json.tmp");
+    std::fs::write(&temp, json).with_context(|| format!("Failed to write {}", temp.display()))?;
+    std::fs::rename(&temp, path).with_context(|| format!("Failed to write {}", path.display()))
+}
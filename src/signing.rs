@@ -0,0 +1,270 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::config::AuthConfig;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Default header carrying the HMAC signature
+pub const DEFAULT_SIGNATURE_HEADER: &str = "X-Signature";
+
+/// Header carrying the timestamp covered by the HMAC signature
+const TIMESTAMP_HEADER: &str = "X-Timestamp";
+
+/// Header naming the key used for the HMAC signature
+const KEY_ID_HEADER: &str = "X-Key-Id";
+
+/// Default JWT signing algorithm
+pub const DEFAULT_JWT_ALGORITHM: &str = "HS256";
+
+/// The parts of a request covered by a signature
+#[derive(Debug, Clone, Copy)]
+pub struct SigningRequest<'a> {
+    pub method: &'a str,
+    pub url: &'a str,
+    pub body: &'a [u8],
+}
+
+/// HMAC-SHA256 signature headers
+/// String to sign: `METHOD\nPATH?QUERY\nTIMESTAMP\nhex(sha256(body))`, signature in lowercase hex
+pub fn hmac_headers(
+    auth: &AuthConfig,
+    request: &SigningRequest,
+    now: DateTime<Utc>,
+) -> Result<Vec<String>> {
+    let secret = auth
+        .signing_key
+        .as_deref()
+        .context("HMAC auth requires --signing-key")?;
+    let url =
+        url::Url::parse(request.url).with_context(|| format!("Invalid URL {}", request.url))?;
+
+    let mut path = url.path().to_string();
+    if let Some(query) = url.query() {
+        path.push('?');
+        path.push_str(query);
+    }
+
+    let timestamp = now.timestamp().to_string();
+    let string_to_sign = format!(
+        "{}\n{}\n{}\n{}",
+        request.method,
+        path,
+        timestamp,
+        hex(&Sha256::digest(request.body))
+    );
+    let signature = hex(&hmac_sha256(secret.as_bytes(), string_to_sign.as_bytes()));
+
+    let mut headers = vec![
+        format!("{}: {}", TIMESTAMP_HEADER, timestamp),
+        format!(
+            "{}: {}",
+            auth.signature_header
+                .as_deref()
+                .unwrap_or(DEFAULT_SIGNATURE_HEADER),
+            signature
+        ),
+    ];
+    if let Some(ref key_id) = auth.key_id {
+        headers.push(format!("{}: {}", KEY_ID_HEADER, key_id));
+    }
+    Ok(headers)
+}
+
+/// AWS Signature Version 4 headers (Authorization, X-Amz-Date and friends)
+/// Signs `host` and the `x-amz-*` headers it adds; S3 also gets `x-amz-content-sha256`
+pub fn sigv4_headers(
+    auth: &AuthConfig,
+    request: &SigningRequest,
+    now: DateTime<Utc>,
+) -> Result<Vec<String>> {
+    let access_key = auth
+        .key_id
+        .as_deref()
+        .context("SigV4 auth requires --key-id (the access key ID)")?;
+    let secret_key = auth
+        .signing_key
+        .as_deref()
+        .context("SigV4 auth requires --signing-key (the secret access key)")?;
+    let region = auth
+        .region
+        .as_deref()
+        .context("SigV4 auth requires --aws-region")?;
+    let service = auth
+        .service
+        .as_deref()
+        .context("SigV4 auth requires --aws-service")?;
+
+    let url =
+        url::Url::parse(request.url).with_context(|| format!("Invalid URL {}", request.url))?;
+    let Some(host) = url.host_str() else {
+        bail!("URL {} has no host", request.url);
+    };
+    let host = match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    };
+
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let payload_hash = hex(&Sha256::digest(request.body));
+
+    // Canonical headers, sorted by name
+    let mut signed: Vec<(&str, String)> = vec![("host", host), ("x-amz-date", amz_date.clone())];
+    if service == "s3" {
+        signed.push(("x-amz-content-sha256", payload_hash.clone()));
+    }
+    if let Some(ref token) = auth.session_token {
+        signed.push(("x-amz-security-token", token.clone()));
+    }
+    signed.sort_by(|a, b| a.0.cmp(b.0));
+
+    let canonical_headers: String = signed
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_headers = signed
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        request.method,
+        canonical_uri(url.path(), service),
+        canonical_query(&url),
+        canonical_headers,
+        signed_headers,
+        payload_hash
+    );
+
+    let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex(&Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let k_date = hmac_sha256(format!("AWS4{}", secret_key).as_bytes(), date.as_bytes());
+    let k_region = hmac_sha256(&k_date, region.as_bytes());
+    let k_service = hmac_sha256(&k_region, service.as_bytes());
+    let k_signing = hmac_sha256(&k_service, b"aws4_request");
+    let signature = hex(&hmac_sha256(&k_signing, string_to_sign.as_bytes()));
+
+    // Host is sent by oha itself; the rest are added as headers
+    let mut headers = vec![format!(
+        "Authorization: AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        access_key, scope, signed_headers, signature
+    )];
+    for (name, value) in &signed {
+        if *name != "host" {
+            headers.push(format!("{}: {}", name, value));
+        }
+    }
+    Ok(headers)
+}
+
+/// Mint a JWT valid for `ttl_seconds` from now, signed with the configured key
+/// HS* algorithms use the key as a shared secret; RS*/PS*/ES*/EdDSA expect a PEM key
+pub fn mint_jwt(auth: &AuthConfig, now: DateTime<Utc>, ttl_seconds: u32) -> Result<String> {
+    use jsonwebtoken::{Algorithm, EncodingKey, Header};
+
+    let key = auth
+        .signing_key
+        .as_deref()
+        .context("JWT auth requires --signing-key (use file:PATH for a PEM key)")?;
+    let algorithm_name = auth.algorithm.as_deref().unwrap_or(DEFAULT_JWT_ALGORITHM);
+    let algorithm: Algorithm = algorithm_name
+        .to_uppercase()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Unsupported JWT algorithm {}", algorithm_name))?;
+
+    let encoding_key = match algorithm {
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+            EncodingKey::from_secret(key.as_bytes())
+        }
+        Algorithm::RS256
+        | Algorithm::RS384
+        | Algorithm::RS512
+        | Algorithm::PS256
+        | Algorithm::PS384
+        | Algorithm::PS512 => EncodingKey::from_rsa_pem(key.as_bytes())?,
+        Algorithm::ES256 | Algorithm::ES384 => EncodingKey::from_ec_pem(key.as_bytes())?,
+        Algorithm::EdDSA => EncodingKey::from_ed_pem(key.as_bytes())?,
+    };
+
+    let mut claims = match auth.claims {
+        Some(ref json) => match serde_json::from_str(json)
+            .with_context(|| format!("Invalid --jwt-claims JSON: {}", json))?
+        {
+            serde_json::Value::Object(map) => map,
+            _ => bail!("--jwt-claims must be a JSON object"),
+        },
+        None => serde_json::Map::new(),
+    };
+    let iat = now.timestamp();
+    claims.insert("iat".to_string(), iat.into());
+    claims.insert("nbf".to_string(), iat.into());
+    claims.insert("exp".to_string(), (iat + ttl_seconds as i64).into());
+
+    let mut header = Header::new(algorithm);
+    header.kid = auth.key_id.clone();
+
+    jsonwebtoken::encode(&header, &claims, &encoding_key).context("Failed to sign JWT")
+}
+
+/// URI-encode a path for SigV4 (S3 paths are encoded once, other services twice)
+fn canonical_uri(path: &str, service: &str) -> String {
+    if path.is_empty() {
+        return "/".to_string();
+    }
+    if service == "s3" {
+        return path.to_string();
+    }
+    path.split('/')
+        .map(aws_encode)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Sorted, encoded query string for SigV4
+fn canonical_query(url: &url::Url) -> String {
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| (aws_encode(&k), aws_encode(&v)))
+        .collect();
+    pairs.sort();
+    pairs
+        .into_iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Percent-encode everything except unreserved characters (RFC 3986), as SigV4 requires
+fn aws_encode(value: &str) -> String {
+    let mut out = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
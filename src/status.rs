@@ -0,0 +1,195 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// How responses with a status code are counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatusClass {
+    /// Counts as a successful request
+    Success,
+    /// Counts as an error
+    Error,
+    /// Counts as an error; a step dominated by it is rate limited
+    RateLimit,
+    /// Counts as an error; a step dominated by it is blocked
+    Block,
+    /// Left out of the request and error counts entirely
+    Ignore,
+}
+
+impl std::fmt::Display for StatusClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.to_possible_value() {
+            Some(value) => write!(f, "{}", value.get_name()),
+            None => write!(f, "{:?}", self),
+        }
+    }
+}
+
+impl StatusClass {
+    /// Whether responses of this class count as errors
+    pub fn is_error(self) -> bool {
+        matches!(
+            self,
+            StatusClass::Error | StatusClass::RateLimit | StatusClass::Block
+        )
+    }
+}
+
+/// A range of status codes mapped to a class
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusRule {
+    pub from: u32,
+    pub to: u32,
+    pub class: StatusClass,
+}
+
+impl std::fmt::Display for StatusRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.from == self.to {
+            write!(f, "{}={}", self.from, self.class)
+        } else if self.from.is_multiple_of(100) && self.to == self.from + 99 {
+            write!(f, "{}xx={}", self.from / 100, self.class)
+        } else {
+            write!(f, "{}-{}={}", self.from, self.to, self.class)
+        }
+    }
+}
+
+/// Parse `CODES=CLASS`, where CODES is a code (404), a range (300-399) or a class (5xx)
+pub fn parse_status_rule(value: &str) -> Result<StatusRule, String> {
+    let Some((codes, class)) = value.split_once('=') else {
+        return Err(format!("expected CODES=CLASS, got '{}'", value));
+    };
+    let class = StatusClass::from_str(class.trim(), true).map_err(|_| {
+        format!(
+            "unknown class '{}' (success, error, rate-limit, block, ignore)",
+            class.trim()
+        )
+    })?;
+
+    let codes = codes.trim().to_lowercase();
+    let parse = |code: &str| {
+        code.trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|c| (100..=599).contains(c))
+            .ok_or_else(|| format!("invalid status code '{}'", code.trim()))
+    };
+    let (from, to) = if let Some(hundreds) = codes.strip_suffix("xx") {
+        let from = parse(&format!("{}00", hundreds))?;
+        (from, from + 99)
+    } else if let Some((from, to)) = codes.split_once('-') {
+        (parse(from)?, parse(to)?)
+    } else {
+        let code = parse(&codes)?;
+        (code, code)
+    };
+    if from > to {
+        return Err(format!("empty status range '{}'", codes));
+    }
+    Ok(StatusRule { from, to, class })
+}
+
+/// Status code classification: 2xx/3xx succeed, 429 is rate limiting, 403 is blocking and
+/// everything else is an error, unless overridden by rules (later rules win)
+#[derive(Debug, Clone, Default)]
+pub struct StatusClasses {
+    pub rules: Vec<StatusRule>,
+}
+
+impl StatusClasses {
+    /// Class of responses with `code`
+    pub fn classify(&self, code: u32) -> StatusClass {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| (rule.from..=rule.to).contains(&code))
+            .map(|rule| rule.class)
+            .unwrap_or(match code {
+                200..=399 => StatusClass::Success,
+                429 => StatusClass::RateLimit,
+                403 => StatusClass::Block,
+                _ => StatusClass::Error,
+            })
+    }
+
+    /// Whether the default classification is in use
+    pub fn is_default(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+/// Kind of failure for requests that never got a response, normalized from oha's
+/// error distribution messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorKind {
+    ConnectionRefused,
+    ConnectionReset,
+    Timeout,
+    Tls,
+    Dns,
+    Other,
+}
+
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorKind::ConnectionRefused => write!(f, "connection refused"),
+            ErrorKind::ConnectionReset => write!(f, "connection reset"),
+            ErrorKind::Timeout => write!(f, "timeout"),
+            ErrorKind::Tls => write!(f, "TLS"),
+            ErrorKind::Dns => write!(f, "DNS"),
+            ErrorKind::Other => write!(f, "other"),
+        }
+    }
+}
+
+impl ErrorKind {
+    /// Categorize an oha error message, e.g. "aborted due to deadline" or
+    /// "error trying to connect: tcp connect error: Connection refused (os error 111)"
+    pub fn from_message(message: &str) -> Self {
+        let message = message.to_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|n| message.contains(n));
+        // DNS and TLS first: their messages often also mention connecting or timeouts
+        if has(&[
+            "dns",
+            "failed to lookup",
+            "name or service not known",
+            "no such host",
+            "resolve",
+        ]) {
+            ErrorKind::Dns
+        } else if has(&["tls", "ssl", "certificate", "handshake", "rustls"]) {
+            ErrorKind::Tls
+        } else if has(&["refused"]) {
+            ErrorKind::ConnectionRefused
+        } else if has(&[
+            "reset",
+            "broken pipe",
+            "connection closed",
+            "incomplete message",
+            "incompletemessage",
+            "unexpected eof",
+        ]) {
+            ErrorKind::ConnectionReset
+        } else if has(&["deadline", "timed out", "timeout", "elapsed"]) {
+            ErrorKind::Timeout
+        } else {
+            ErrorKind::Other
+        }
+    }
+
+    /// What a step dominated by this kind of failure broke on
+    pub fn break_description(self) -> &'static str {
+        match self {
+            ErrorKind::ConnectionRefused => "Connections refused",
+            ErrorKind::ConnectionReset => "Connections reset",
+            ErrorKind::Timeout => "Requests timed out",
+            ErrorKind::Tls => "TLS handshakes failed",
+            ErrorKind::Dns => "DNS resolution failed",
+            ErrorKind::Other => "Connection errors",
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A single template variable, written as `{{name}}` or `{{name:args}}`
+#[derive(Debug, Clone)]
+enum Var {
+    /// `{{uuid}}` - random UUID v4
+    Uuid,
+    /// `{{random_int}}` or `{{random_int:MIN:MAX}}` (inclusive)
+    RandomInt(i64, i64),
+    /// `{{random_string:N}}` - N random alphanumeric characters
+    RandomString(usize),
+    /// `{{counter}}` - incrementing value, unique per rendered instance
+    Counter,
+    /// `{{range:MIN:MAX}}` - cycles through MIN..=MAX in order, driven by the counter
+    Range(i64, i64),
+    /// `{{timestamp}}` - unix seconds
+    Timestamp,
+    /// `{{timestamp_ms}}` - unix milliseconds
+    TimestampMs,
+    /// `{{now}}` - RFC 3339 UTC time
+    Now,
+    /// `{{data.COLUMN}}` - value from the current data file row
+    Data(String),
+}
+
+#[derive(Debug, Clone)]
+enum Part {
+    Literal(String),
+    Var(Var),
+}
+
+/// A parsed text template with `{{...}}` placeholders
+#[derive(Debug, Clone)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    /// Parse a template string
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut rest = input;
+
+        while let Some(start) = rest.find("{{") {
+            let Some(len) = rest[start + 2..].find("}}") else {
+                break;
+            };
+            if start > 0 {
+                parts.push(Part::Literal(rest[..start].to_string()));
+            }
+            let expr = rest[start + 2..start + 2 + len].trim();
+            parts.push(Part::Var(parse_var(expr)?));
+            rest = &rest[start + 2 + len + 2..];
+        }
+
+        if !rest.is_empty() {
+            parts.push(Part::Literal(rest.to_string()));
+        }
+
+        Ok(Self { parts })
+    }
+
+    /// Whether the template contains any placeholders
+    pub fn is_dynamic(&self) -> bool {
+        self.parts.iter().any(|p| matches!(p, Part::Var(_)))
+    }
+
+    /// Data file columns referenced by the template
+    pub fn data_columns(&self) -> Vec<&str> {
+        self.parts
+            .iter()
+            .filter_map(|p| match p {
+                Part::Var(Var::Data(col)) => Some(col.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Render one instance of the template
+    /// `counter` feeds `{{counter}}` and selects the data row (cycling through the file)
+    pub fn render(&self, counter: u64, data: Option<&DataSet>) -> String {
+        let row = data.and_then(|d| d.row(counter));
+        let mut out = String::new();
+
+        for part in &self.parts {
+            match part {
+                Part::Literal(s) => out.push_str(s),
+                Part::Var(var) => out.push_str(&render_var(var, counter, row)),
+            }
+        }
+
+        out
+    }
+}
+
+fn parse_var(expr: &str) -> Result<Var> {
+    if let Some(column) = expr.strip_prefix("data.") {
+        return Ok(Var::Data(column.to_string()));
+    }
+
+    let mut args = expr.split(':');
+    let name = args.next().unwrap_or_default();
+    let args: Vec<&str> = args.collect();
+
+    let var = match (name, args.as_slice()) {
+        ("uuid", []) => Var::Uuid,
+        ("random_int", []) => Var::RandomInt(0, i32::MAX as i64),
+        ("random_int", [min, max]) => {
+            let (min, max) = parse_bounds(expr, min, max)?;
+            Var::RandomInt(min, max)
+        }
+        ("random_string", []) => Var::RandomString(16),
+        ("random_string", [len]) => Var::RandomString(
+            len.parse()
+                .with_context(|| format!("Invalid length in {{{{{}}}}}", expr))?,
+        ),
+        ("counter", []) => Var::Counter,
+        ("range", [min, max]) => {
+            let (min, max) = parse_bounds(expr, min, max)?;
+            Var::Range(min, max)
+        }
+        ("timestamp", []) => Var::Timestamp,
+        ("timestamp_ms", []) => Var::TimestampMs,
+        ("now", []) => Var::Now,
+        _ => bail!("Unknown template variable {{{{{}}}}}", expr),
+    };
+
+    Ok(var)
+}
+
+/// Parse inclusive MIN:MAX bounds of a variable
+fn parse_bounds(expr: &str, min: &str, max: &str) -> Result<(i64, i64)> {
+    let min: i64 = min
+        .parse()
+        .with_context(|| format!("Invalid min in {{{{{}}}}}", expr))?;
+    let max: i64 = max
+        .parse()
+        .with_context(|| format!("Invalid max in {{{{{}}}}}", expr))?;
+    if min > max {
+        bail!("min > max in {{{{{}}}}}", expr);
+    }
+    Ok((min, max))
+}
+
+fn render_var(var: &Var, counter: u64, row: Option<&HashMap<String, String>>) -> String {
+    match var {
+        Var::Uuid => random_uuid(),
+        Var::RandomInt(min, max) => fastrand::i64(*min..=*max).to_string(),
+        Var::RandomString(len) => std::iter::repeat_with(fastrand::alphanumeric)
+            .take(*len)
+            .collect(),
+        Var::Counter => counter.to_string(),
+        Var::Range(min, max) => {
+            let span = (max - min) as u64 + 1;
+            (min + (counter % span) as i64).to_string()
+        }
+        Var::Timestamp => chrono::Utc::now().timestamp().to_string(),
+        Var::TimestampMs => chrono::Utc::now().timestamp_millis().to_string(),
+        Var::Now => chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        Var::Data(column) => row.and_then(|r| r.get(column)).cloned().unwrap_or_default(),
+    }
+}
+
+/// Generate a random (version 4) UUID string
+fn random_uuid() -> String {
+    let mut bytes = fastrand::u128(..).to_be_bytes();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Rows loaded from a CSV (with header row) or JSONL data file
+#[derive(Debug, Clone)]
+pub struct DataSet {
+    rows: Vec<HashMap<String, String>>,
+}
+
+impl DataSet {
+    /// Load a data file; `.jsonl`/`.ndjson` are read as one JSON object per line, anything else as CSV
+    pub fn load(path: &str) -> Result<Self> {
+        let is_jsonl = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("jsonl") || e.eq_ignore_ascii_case("ndjson"));
+
+        let rows = if is_jsonl {
+            load_jsonl(path)?
+        } else {
+            load_csv(path)?
+        };
+
+        if rows.is_empty() {
+            bail!("Data file {} has no rows", path);
+        }
+
+        Ok(Self { rows })
+    }
+
+    /// Get the row for a counter value, cycling through the file
+    pub fn row(&self, counter: u64) -> Option<&HashMap<String, String>> {
+        self.rows.get((counter % self.rows.len() as u64) as usize)
+    }
+
+    /// Whether a column exists in the data file
+    pub fn has_column(&self, column: &str) -> bool {
+        self.rows.iter().any(|r| r.contains_key(column))
+    }
+}
+
+fn load_csv(path: &str) -> Result<Vec<HashMap<String, String>>> {
+    let mut reader = csv::Reader::from_path(path)
+        .with_context(|| format!("Failed to open data file {}", path))?;
+    let headers = reader.headers()?.clone();
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.with_context(|| format!("Invalid CSV in {}", path))?;
+        rows.push(
+            headers
+                .iter()
+                .zip(record.iter())
+                .map(|(h, v)| (h.to_string(), v.to_string()))
+                .collect(),
+        );
+    }
+    Ok(rows)
+}
+
+fn load_jsonl(path: &str) -> Result<Vec<HashMap<String, String>>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read data file {}", path))?;
+
+    let mut rows = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let value: serde_json::Value = serde_json::from_str(line)
+            .with_context(|| format!("Invalid JSON on line {} of {}", i + 1, path))?;
+        let serde_json::Value::Object(map) = value else {
+            bail!("Line {} of {} is not a JSON object", i + 1, path);
+        };
+        rows.push(
+            map.into_iter()
+                .map(|(k, v)| {
+                    // Strings are inserted raw; other values keep their JSON form
+                    let v = match v {
+                        serde_json::Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    (k, v)
+                })
+                .collect(),
+        );
+    }
+    Ok(rows)
+}
@@ -0,0 +1,202 @@
+use anyhow::{bail, Context, Result};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, SignatureScheme};
+use std::path::Path;
+use std::sync::Arc;
+
+/// TLS settings for the benchmark target (and the token/login requests)
+#[derive(Debug, Clone, Default)]
+pub struct TlsConfig {
+    /// Extra CA bundles (PEM) trusted on top of the system roots
+    pub ca_certs: Vec<String>,
+    /// Client certificate (PEM) for mutual TLS
+    pub cert: Option<String>,
+    /// Private key (PEM) for the client certificate
+    pub key: Option<String>,
+    /// Server name sent in the TLS handshake instead of the URL host
+    pub sni: Option<String>,
+    /// Skip server certificate verification
+    pub insecure: bool,
+}
+
+impl TlsConfig {
+    /// Whether any TLS option is set
+    pub fn is_empty(&self) -> bool {
+        self.ca_certs.is_empty()
+            && self.cert.is_none()
+            && self.key.is_none()
+            && self.sni.is_none()
+            && !self.insecure
+    }
+
+    /// Check the options fit together and the files exist
+    pub fn validate(&self) -> Result<()> {
+        match (&self.cert, &self.key) {
+            (Some(_), None) => bail!("--cert requires --key"),
+            (None, Some(_)) => bail!("--key requires --cert"),
+            _ => {}
+        }
+        for path in self.ca_certs.iter().chain(&self.cert).chain(&self.key) {
+            if !Path::new(path).is_file() {
+                bail!("TLS file {} does not exist", path);
+            }
+        }
+        if let Some(ref sni) = self.sni {
+            if sni.is_empty() || sni.contains(['/', ':', ' ']) {
+                bail!("--sni must be a host name, got '{}'", sni);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Point `url` at the SNI name and route the connection back to the original host
+/// Returns the rewritten URL and the oha `--connect-to` value (`sni:port:host:port`)
+/// oha takes the TLS server name (and Host header) from the URL, so the URL carries the
+/// SNI name while the connection still goes to the original address
+pub fn sni_route(url: &str, sni: &str) -> Result<(String, String)> {
+    let mut parsed = url::Url::parse(url).with_context(|| format!("Invalid URL {}", url))?;
+    let Some(host) = parsed.host_str().map(str::to_string) else {
+        bail!("URL {} has no host", url);
+    };
+    let port = parsed
+        .port_or_known_default()
+        .with_context(|| format!("URL {} has no port", url))?;
+    parsed
+        .set_host(Some(sni))
+        .with_context(|| format!("Invalid --sni host {}", sni))?;
+
+    // connect-to wants IPv6 hosts without brackets
+    let target = host.trim_start_matches('[').trim_end_matches(']');
+    Ok((
+        parsed.to_string(),
+        format!("{}:{}:{}:{}", sni, port, target, port),
+    ))
+}
+
+/// oha arguments for the certificate options (`--connect-to` is added per URL)
+/// `ca_bundle` is the single CA file to pass (oha accepts one; several are concatenated)
+pub fn oha_args(tls: &TlsConfig, ca_bundle: Option<&Path>) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(path) = ca_bundle {
+        args.push("--cacert".to_string());
+        args.push(path.to_string_lossy().to_string());
+    }
+    if let (Some(cert), Some(key)) = (&tls.cert, &tls.key) {
+        args.push("--cert".to_string());
+        args.push(cert.clone());
+        args.push("--key".to_string());
+        args.push(key.clone());
+    }
+    if tls.insecure {
+        args.push("--insecure".to_string());
+    }
+    args
+}
+
+/// HTTP agent builder for ohabench's own requests (OAuth2 tokens, login) honouring the
+/// CA, client certificate and insecure options; the SNI override only applies to the target
+pub fn agent_builder(tls: &TlsConfig) -> Result<ureq::AgentBuilder> {
+    if tls.ca_certs.is_empty() && tls.cert.is_none() && !tls.insecure {
+        return Ok(ureq::AgentBuilder::new());
+    }
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = rustls::ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .context("Failed to set up TLS")?;
+
+    let builder = if tls.insecure {
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoVerification(provider)))
+    } else {
+        let mut roots = rustls::RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        for path in &tls.ca_certs {
+            for cert in read_certs(path)? {
+                roots
+                    .add(cert)
+                    .with_context(|| format!("Invalid CA certificate in {}", path))?;
+            }
+        }
+        builder.with_root_certificates(roots)
+    };
+
+    let config = match (&tls.cert, &tls.key) {
+        (Some(cert), Some(key)) => {
+            let key = PrivateKeyDer::from_pem_file(key)
+                .with_context(|| format!("Failed to read private key {}", key))?;
+            builder
+                .with_client_auth_cert(read_certs(cert)?, key)
+                .context("Client certificate and key don't match")?
+        }
+        _ => builder.with_no_client_auth(),
+    };
+
+    Ok(ureq::AgentBuilder::new().tls_config(Arc::new(config)))
+}
+
+/// Read every certificate in a PEM file
+fn read_certs(path: &str) -> Result<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .with_context(|| format!("Failed to read {}", path))?
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Invalid PEM in {}", path))?;
+    if certs.is_empty() {
+        bail!("No certificates found in {}", path);
+    }
+    Ok(certs)
+}
+
+/// Accepts any server certificate (`--insecure`); handshake signatures are still checked
+#[derive(Debug)]
+struct NoVerification(Arc<rustls::crypto::CryptoProvider>);
+
+impl ServerCertVerifier for NoVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
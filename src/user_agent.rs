@@ -66,12 +66,11 @@ pub fn resolve_user_agent(input: &str) -> String {
     let input_lower = input.to_lowercase();
 
     for preset in USER_AGENT_PRESETS {
-        if preset.name.to_lowercase().contains(&input_lower)
-            || input_lower.contains(&preset.name.to_lowercase().replace(" ", "-"))
+        if (preset.name.to_lowercase().contains(&input_lower)
+            || input_lower.contains(&preset.name.to_lowercase().replace(" ", "-")))
+            && !preset.value.is_empty()
         {
-            if !preset.value.is_empty() {
-                return preset.value.to_string();
-            }
+            return preset.value.to_string();
         }
     }
 
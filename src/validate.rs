@@ -0,0 +1,355 @@
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::net::ToSocketAddrs;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::ratelimit::{merge_info, RateLimitInfo};
+use crate::runner::BenchmarkResult;
+use crate::status::{StatusClass, StatusClasses};
+use crate::tls::{self, TlsConfig};
+
+/// Timeout for one sampled request
+const SAMPLE_TIMEOUT_SECONDS: u64 = 10;
+
+/// Largest sampled body read for the assertions
+const MAX_SAMPLE_BODY_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Response assertions, checked on requests sampled alongside each step
+#[derive(Debug, Clone, Default)]
+pub struct ValidationConfig {
+    /// Accepted status codes (any 2xx/3xx when empty)
+    pub status: Vec<u16>,
+    /// Text the body must contain
+    pub body_contains: Vec<String>,
+    /// Regexes the body must match
+    pub body_regex: Vec<String>,
+    /// (dot path, expected value) pairs checked in a JSON body
+    pub json_equals: Vec<(String, String)>,
+    /// JSON Schema file the body must match
+    pub schema: Option<String>,
+    /// Largest accepted body in bytes
+    pub max_size: Option<u64>,
+    /// Sampled requests per second
+    pub sample_rate: u32,
+}
+
+impl ValidationConfig {
+    /// Whether any assertion is configured
+    pub fn is_empty(&self) -> bool {
+        self.status.is_empty()
+            && self.body_contains.is_empty()
+            && self.body_regex.is_empty()
+            && self.json_equals.is_empty()
+            && self.schema.is_none()
+            && self.max_size.is_none()
+    }
+}
+
+/// Parse a `PATH=VALUE` JSON assertion
+pub fn parse_json_assertion(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((path, expected)) if !path.trim().is_empty() => {
+            Ok((path.trim().to_string(), expected.to_string()))
+        }
+        _ => Err(format!("expected PATH=VALUE, got '{}'", value)),
+    }
+}
+
+/// One response assertion
+#[derive(Debug)]
+enum Assertion {
+    Status(Vec<u16>),
+    Contains(String),
+    Regex(Regex),
+    JsonEquals {
+        path: String,
+        expected: String,
+    },
+    Schema {
+        file: String,
+        schema: Box<jsonschema::Validator>,
+    },
+    MaxSize(u64),
+}
+
+impl Assertion {
+    /// Name shown in the summary breakdown
+    fn name(&self) -> String {
+        match self {
+            Assertion::Status(codes) => format!(
+                "status in {}",
+                codes
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+            Assertion::Contains(text) => format!("body contains \"{}\"", text),
+            Assertion::Regex(re) => format!("body matches /{}/", re.as_str()),
+            Assertion::JsonEquals { path, expected } => format!("{} == {}", path, expected),
+            Assertion::Schema { file, .. } => format!("schema {}", file),
+            Assertion::MaxSize(bytes) => format!("size <= {} B", bytes),
+        }
+    }
+
+    fn check(&self, status: u16, body: &[u8], json: Option<&serde_json::Value>) -> bool {
+        match self {
+            Assertion::Status(codes) => codes.contains(&status),
+            Assertion::Contains(text) => String::from_utf8_lossy(body).contains(text.as_str()),
+            Assertion::Regex(re) => re.is_match(&String::from_utf8_lossy(body)),
+            Assertion::JsonEquals { path, expected } => json
+                .and_then(|j| crate::login::json_path(j, path))
+                .is_some_and(|value| value == *expected),
+            Assertion::Schema { schema, .. } => json.is_some_and(|j| schema.is_valid(j)),
+            Assertion::MaxSize(bytes) => body.len() as u64 <= *bytes,
+        }
+    }
+
+    fn needs_json(&self) -> bool {
+        matches!(
+            self,
+            Assertion::JsonEquals { .. } | Assertion::Schema { .. }
+        )
+    }
+}
+
+/// Outcome of sampling one step (or slice)
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    /// Sampled responses counted as successful (2xx/3xx by default), checked against the assertions
+    checked: u64,
+    /// Checked responses failing at least one assertion
+    failed: u64,
+    /// Failures by assertion name
+    failures: BTreeMap<String, u64>,
+    /// Rate-limit headers seen on any sampled response
+    rate_limit: Option<RateLimitInfo>,
+}
+
+impl ValidationReport {
+    /// Add the sample to a result: the share of failing samples is applied to the requests
+    /// oha counted as successful and those are added to the errors
+    pub fn apply(self, result: &mut BenchmarkResult) {
+        result.rate_limit = self.rate_limit;
+        if self.checked == 0 {
+            return;
+        }
+        let successes = result.total_requests.saturating_sub(result.errors);
+        let estimated = (successes as f64 * self.failed as f64 / self.checked as f64).round();
+        result.assertion_errors = estimated as u64;
+        result.errors += result.assertion_errors;
+        if result.total_requests > 0 {
+            result.error_rate = result.errors as f64 / result.total_requests as f64 * 100.0;
+        }
+        result.validated = self.checked;
+        result.validation_failed = self.failed;
+        result.assertion_failures = self.failures.into_iter().collect();
+        result
+            .assertion_failures
+            .sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    }
+}
+
+/// The request a step sends, as the sampler should repeat it
+pub struct SampleRequest {
+    pub method: String,
+    /// URLs the step picks from (one, or a generated list)
+    pub urls: Vec<String>,
+    pub body: Option<Vec<u8>>,
+    /// Headers as `Name: value`
+    pub headers: Vec<String>,
+    /// oha `--connect-to` route (`sni:port:host:port`) when the SNI name is overridden
+    pub connect_to: Option<String>,
+}
+
+/// Compiled assertions plus what's needed to send the sampled requests
+/// With no assertions the samples are only read for rate-limit headers
+#[derive(Debug)]
+pub struct Validator {
+    assertions: Vec<Assertion>,
+    rate_limit_headers: bool,
+    sample_rate: u32,
+    tls: TlsConfig,
+    follow_redirects: bool,
+    classes: StatusClasses,
+}
+
+impl Validator {
+    /// Compile the assertions, loading the schema file
+    /// Only samples `classes` counts as successful are checked (the rest are oha's errors);
+    /// with `rate_limit_headers` every sample is also read for the advertised rate limit
+    pub fn new(
+        config: &ValidationConfig,
+        tls: &TlsConfig,
+        follow_redirects: bool,
+        classes: &StatusClasses,
+        rate_limit_headers: bool,
+    ) -> Result<Self> {
+        let mut assertions = Vec::new();
+        if !config.status.is_empty() {
+            assertions.push(Assertion::Status(config.status.clone()));
+        }
+        for text in &config.body_contains {
+            assertions.push(Assertion::Contains(text.clone()));
+        }
+        for pattern in &config.body_regex {
+            let re = Regex::new(pattern)
+                .with_context(|| format!("Invalid --expect-body-regex '{}'", pattern))?;
+            assertions.push(Assertion::Regex(re));
+        }
+        for (path, expected) in &config.json_equals {
+            assertions.push(Assertion::JsonEquals {
+                path: path.clone(),
+                expected: expected.clone(),
+            });
+        }
+        if let Some(ref file) = config.schema {
+            let text = std::fs::read_to_string(file)
+                .with_context(|| format!("Failed to read schema {}", file))?;
+            let json: serde_json::Value = serde_json::from_str(&text)
+                .with_context(|| format!("Schema {} is not JSON", file))?;
+            let schema = jsonschema::validator_for(&json)
+                .map_err(|e| anyhow::anyhow!("Invalid JSON Schema {}: {}", file, e))?;
+            assertions.push(Assertion::Schema {
+                file: file.clone(),
+                schema: Box::new(schema),
+            });
+        }
+        if let Some(bytes) = config.max_size {
+            assertions.push(Assertion::MaxSize(bytes));
+        }
+        if assertions.is_empty() && !rate_limit_headers {
+            bail!("No response assertions configured");
+        }
+
+        Ok(Self {
+            assertions,
+            rate_limit_headers,
+            sample_rate: config.sample_rate.max(1),
+            tls: tls.clone(),
+            follow_redirects,
+            classes: classes.clone(),
+        })
+    }
+
+    /// Start sampling `request` for `seconds` on a background thread
+    pub fn start(
+        self: &Arc<Self>,
+        request: SampleRequest,
+        seconds: u32,
+    ) -> Result<JoinHandle<ValidationReport>> {
+        let mut builder = tls::agent_builder(&self.tls)?
+            .timeout(Duration::from_secs(SAMPLE_TIMEOUT_SECONDS))
+            .redirects(if self.follow_redirects { 5 } else { 0 });
+        if let Some(ref route) = request.connect_to {
+            // Same routing as oha's --connect-to: the SNI name resolves to the original host
+            let parts: Vec<&str> = route.splitn(4, ':').collect();
+            if let [sni, port, host, target_port] = parts[..] {
+                let from = format!("{}:{}", sni, port);
+                let to = format!("{}:{}", host, target_port);
+                builder = builder.resolver(move |netloc: &str| {
+                    let addr = if netloc == from { to.as_str() } else { netloc };
+                    addr.to_socket_addrs().map(|a| a.collect())
+                });
+            }
+        }
+        let agent = builder.build();
+
+        let validator = Arc::clone(self);
+        Ok(std::thread::spawn(move || {
+            validator.sample(&agent, &request, seconds)
+        }))
+    }
+
+    fn sample(
+        &self,
+        agent: &ureq::Agent,
+        request: &SampleRequest,
+        seconds: u32,
+    ) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        if request.urls.is_empty() {
+            return report;
+        }
+        let interval = Duration::from_secs_f64(1.0 / self.sample_rate as f64);
+        let deadline = Instant::now() + Duration::from_secs(seconds as u64);
+        let mut next = Instant::now();
+
+        while next < deadline {
+            let wait = next.saturating_duration_since(Instant::now());
+            std::thread::sleep(wait);
+            next += interval;
+
+            let url = &request.urls[fastrand::usize(..request.urls.len())];
+            let Some((status, body, rate_limit)) = send(agent, request, url) else {
+                // Transport errors are already counted by oha
+                continue;
+            };
+            if self.rate_limit_headers {
+                // Rejected responses (429s) usually carry the most telling headers
+                merge_info(&mut report.rate_limit, rate_limit.as_ref());
+            }
+            if self.assertions.is_empty()
+                || self.classes.classify(status as u32) != StatusClass::Success
+            {
+                // Errors are counted from oha's status codes already; ignored codes don't count
+                continue;
+            }
+
+            report.checked += 1;
+            let json = if self.assertions.iter().any(Assertion::needs_json) {
+                serde_json::from_slice(&body).ok()
+            } else {
+                None
+            };
+            let mut failed = false;
+            for assertion in &self.assertions {
+                if !assertion.check(status, &body, json.as_ref()) {
+                    *report.failures.entry(assertion.name()).or_insert(0) += 1;
+                    failed = true;
+                }
+            }
+            if failed {
+                report.failed += 1;
+            }
+        }
+        report
+    }
+}
+
+/// Send one sampled request, returning its status, body and rate-limit headers
+/// (None on transport errors)
+fn send(
+    agent: &ureq::Agent,
+    request: &SampleRequest,
+    url: &str,
+) -> Option<(u16, Vec<u8>, Option<RateLimitInfo>)> {
+    let mut req = agent.request(&request.method, url);
+    for header in &request.headers {
+        if let Some((name, value)) = header.split_once(':') {
+            req = req.set(name.trim(), value.trim());
+        }
+    }
+    let response = match request.body {
+        Some(ref body) => req.send_bytes(body),
+        None => req.call(),
+    };
+    let response = match response {
+        Ok(r) => r,
+        Err(ureq::Error::Status(_, r)) => r,
+        Err(_) => return None,
+    };
+    let status = response.status();
+    let rate_limit = RateLimitInfo::from_response(&response);
+    let mut body = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_SAMPLE_BODY_BYTES)
+        .read_to_end(&mut body)
+        .ok()?;
+    Some((status, body, rate_limit))
+}
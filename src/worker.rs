@@ -0,0 +1,531 @@
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use crate::client::{self, ClientUsage};
+use crate::runner::collect_oha_output;
+
+/// Address `ohabench worker` listens on by default (other machines need `--listen` and a token)
+pub const DEFAULT_LISTEN: &str = "127.0.0.1:7700";
+
+/// Timeout for connecting to a worker and for its replies while preparing
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a prepared worker waits for the coordinator's go signal
+const GO_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How often a running job checks whether the coordinator cancelled it
+const CANCEL_POLL: Duration = Duration::from_millis(200);
+
+/// oha options whose value is a local file; the file is sent along with the job
+const FILE_OPTIONS: &[&str] = &["-D", "--urls-from-file", "--cacert", "--cert", "--key"];
+
+/// Other oha options a job may use that take a value
+const VALUE_OPTIONS: &[&str] = &[
+    "-c",
+    "-z",
+    "-q",
+    "-m",
+    "-d",
+    "-H",
+    "--redirect",
+    "--connect-to",
+];
+
+/// oha flags a job may use
+const FLAG_OPTIONS: &[&str] = &["--latency-correction", "-w", "--no-tui", "--insecure"];
+
+/// Jobs prepared by this worker, naming their temporary directories
+static JOBS: AtomicUsize = AtomicUsize::new(0);
+
+/// Messages between the coordinator and a worker, one JSON object per line
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+enum Message {
+    /// Coordinator: prepare an oha run
+    Job(Job),
+    /// Coordinator: start the prepared run
+    Go,
+    /// Coordinator: stop the run (closing the connection does the same)
+    Cancel,
+    /// Worker: the run is prepared and waits for the go signal
+    Ready,
+    /// Worker: the run finished
+    Done(Outcome),
+    /// Worker: the job was rejected or failed
+    Error { message: String },
+}
+
+/// One oha process to run on a worker
+#[derive(Debug, Serialize, Deserialize)]
+struct Job {
+    token: Option<String>,
+    /// oha arguments (file arguments are replaced by the worker's copies)
+    args: Vec<String>,
+    files: Vec<JobFile>,
+    /// Step duration, for the worker's resource monitor
+    seconds: u32,
+    /// Time after which the run counts as hung
+    timeout_seconds: u64,
+}
+
+/// A local file an oha argument refers to
+#[derive(Debug, Serialize, Deserialize)]
+struct JobFile {
+    /// Index of the argument holding the path
+    arg: usize,
+    /// Base64 file content
+    content: String,
+}
+
+/// What a worker's oha run produced
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Outcome {
+    /// Combined oha output, None when the run hung
+    pub output: Option<String>,
+    /// oha exited successfully
+    pub success: bool,
+    /// Peak resource use of the worker machine during the run
+    pub client: Option<ClientUsage>,
+}
+
+/// A line-delimited JSON connection
+struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    fn new(stream: TcpStream) -> Result<Self> {
+        let writer = stream.try_clone().context("Failed to clone connection")?;
+        Ok(Self {
+            reader: BufReader::new(stream),
+            writer,
+        })
+    }
+
+    fn send(&mut self, message: &Message) -> Result<()> {
+        let mut line = serde_json::to_string(message)?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Read the next message, None when the peer closed the connection or `timeout` passed
+    fn receive(&mut self, timeout: Duration) -> Result<Option<Message>> {
+        self.reader.get_ref().set_read_timeout(Some(timeout))?;
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) => Ok(None),
+            Ok(_) => Ok(Some(
+                serde_json::from_str(&line).context("Invalid message")?,
+            )),
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// A run prepared on a worker, started with [`RemoteRun::start`]
+pub struct RemoteRun {
+    worker: String,
+    connection: Connection,
+}
+
+impl RemoteRun {
+    /// Send an oha command and the files it reads to a worker and wait until it's ready
+    pub fn prepare(
+        worker: &str,
+        token: Option<&str>,
+        cmd: &Command,
+        seconds: u32,
+        timeout: Duration,
+    ) -> Result<Self> {
+        let args: Vec<String> = cmd
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        let mut files = Vec::new();
+        for (i, pair) in args.windows(2).enumerate() {
+            if FILE_OPTIONS.contains(&pair[0].as_str()) {
+                let content = std::fs::read(&pair[1])
+                    .with_context(|| format!("Failed to read {}", pair[1]))?;
+                files.push(JobFile {
+                    arg: i + 1,
+                    content: STANDARD.encode(content),
+                });
+            }
+        }
+        let job = Job {
+            token: token.map(str::to_string),
+            args,
+            files,
+            seconds,
+            timeout_seconds: timeout.as_secs(),
+        };
+
+        let mut connection = connect(worker)?;
+        connection.send(&Message::Job(job))?;
+        match connection.receive(CONNECT_TIMEOUT)? {
+            Some(Message::Ready) => Ok(Self {
+                worker: worker.to_string(),
+                connection,
+            }),
+            Some(Message::Error { message }) => bail!("Worker {}: {}", worker, message),
+            _ => bail!("Worker {} didn't get ready", worker),
+        }
+    }
+
+    /// Send the go signal
+    pub fn start(&mut self) -> Result<()> {
+        self.connection
+            .send(&Message::Go)
+            .with_context(|| format!("Lost connection to worker {}", self.worker))
+    }
+
+    /// Stop the run on the worker, whether or not it was started
+    pub fn cancel(&mut self) {
+        self.connection.send(&Message::Cancel).ok();
+        self.connection.writer.shutdown(Shutdown::Both).ok();
+    }
+
+    /// Wait for the run's outcome; a worker silent past `timeout` counts as a hung run
+    pub fn wait(mut self, timeout: Duration) -> Result<Outcome> {
+        // The worker kills oha at the timeout itself, then still needs to reply
+        match self.connection.receive(timeout + CONNECT_TIMEOUT)? {
+            Some(Message::Done(outcome)) => Ok(outcome),
+            Some(Message::Error { message }) => bail!("Worker {}: {}", self.worker, message),
+            Some(_) => bail!("Worker {} sent an unexpected reply", self.worker),
+            None => Ok(Outcome::default()),
+        }
+    }
+}
+
+/// Start prepared runs together; if one fails to start, every run is cancelled
+pub fn start_all(runs: &mut [RemoteRun]) -> Result<()> {
+    for i in 0..runs.len() {
+        if let Err(e) = runs[i].start() {
+            for run in runs.iter_mut() {
+                run.cancel();
+            }
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+fn connect(worker: &str) -> Result<Connection> {
+    let addr = worker
+        .to_socket_addrs()
+        .with_context(|| format!("Invalid worker address {}", worker))?
+        .next()
+        .ok_or_else(|| anyhow!("Invalid worker address {}", worker))?;
+    let stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)
+        .with_context(|| format!("Failed to connect to worker {}", worker))?;
+    stream.set_nodelay(true).ok();
+    Connection::new(stream)
+}
+
+/// Run the worker: accept jobs from coordinators and run them with the local oha
+/// Without a token the worker only listens on loopback addresses
+pub fn serve(listen: &str, token: Option<&str>) -> Result<()> {
+    let addrs: Vec<_> = listen
+        .to_socket_addrs()
+        .with_context(|| format!("Invalid listen address {}", listen))?
+        .collect();
+    if token.is_none() && addrs.iter().any(|a| !a.ip().is_loopback()) {
+        bail!(
+            "Refusing to listen on {} without a token: set --token (or OHABENCH_WORKER_TOKEN)",
+            listen
+        );
+    }
+    let listener =
+        TcpListener::bind(&addrs[..]).with_context(|| format!("Failed to listen on {}", listen))?;
+    println!(
+        "{} Worker listening on {}{}",
+        style("✓").green(),
+        listener.local_addr()?,
+        if token.is_some() {
+            " (token required)"
+        } else {
+            ""
+        }
+    );
+
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let token = token.map(str::to_string);
+        std::thread::spawn(move || {
+            let peer = stream
+                .peer_addr()
+                .map_or("unknown".to_string(), |a| a.to_string());
+            if let Err(e) = handle_connection(stream, token.as_deref()) {
+                eprintln!("{} {}: {:#}", style("✗").red(), peer, e);
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(stream: TcpStream, token: Option<&str>) -> Result<()> {
+    stream.set_nodelay(true).ok();
+    let mut connection = Connection::new(stream)?;
+    let job = match connection.receive(CONNECT_TIMEOUT)? {
+        Some(Message::Job(job)) => job,
+        Some(_) => bail!("Expected a job"),
+        None => return Ok(()),
+    };
+    if !token_matches(token, job.token.as_deref()) {
+        connection.send(&Message::Error {
+            message: "invalid worker token".to_string(),
+        })?;
+        bail!("Rejected a job with an invalid token");
+    }
+
+    if let Err(e) = check_args(&job) {
+        connection.send(&Message::Error {
+            message: format!("{:#}", e),
+        })?;
+        return Err(e.context("Rejected a job"));
+    }
+
+    let dir = std::env::temp_dir().join(format!(
+        "ohabench-worker-{}-{}",
+        std::process::id(),
+        JOBS.fetch_add(1, Ordering::Relaxed)
+    ));
+    let result = run_job(&mut connection, &job, &dir);
+    std::fs::remove_dir_all(&dir).ok();
+    if let Err(ref e) = result {
+        connection
+            .send(&Message::Error {
+                message: format!("{:#}", e),
+            })
+            .ok();
+    }
+    result
+}
+
+/// Set `cancel` when the coordinator cancels the run or goes away, until `finished` is set
+fn watch_for_cancel(reader: &mut BufReader<TcpStream>, cancel: &AtomicBool, finished: &AtomicBool) {
+    if reader
+        .get_ref()
+        .set_read_timeout(Some(CANCEL_POLL))
+        .is_err()
+    {
+        return;
+    }
+    let mut line = String::new();
+    while !finished.load(Ordering::Relaxed) {
+        match reader.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) if line.ends_with('\n') => {
+                if matches!(serde_json::from_str(&line), Ok(Message::Cancel)) {
+                    break;
+                }
+                line.clear();
+            }
+            Ok(_) => {}
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => break,
+        }
+    }
+    if !finished.load(Ordering::Relaxed) {
+        cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Whether a job's token is the one the worker requires, compared in constant time
+fn token_matches(expected: Option<&str>, given: Option<&str>) -> bool {
+    let Some(expected) = expected else {
+        return true;
+    };
+    let given = given.unwrap_or("").as_bytes();
+    let expected = expected.as_bytes();
+    let mut diff = (given.len() != expected.len()) as u8;
+    for (i, byte) in expected.iter().enumerate() {
+        diff |= byte ^ given.get(i).copied().unwrap_or(0);
+    }
+    diff == 0
+}
+
+/// Check a job's oha arguments against the options ohabench itself uses
+/// File options must point at a file sent with the job, so a job can't read the worker's files
+fn check_args(job: &Job) -> Result<()> {
+    let mut url = None;
+    let mut i = 0;
+    while i < job.args.len() {
+        let arg = job.args[i].as_str();
+        if FILE_OPTIONS.contains(&arg) {
+            if !job.files.iter().any(|f| f.arg == i + 1) {
+                bail!("{} must refer to a file sent with the job", arg);
+            }
+            i += 2;
+        } else if VALUE_OPTIONS.contains(&arg) {
+            i += 2;
+        } else if FLAG_OPTIONS.contains(&arg) {
+            i += 1;
+        } else if arg.starts_with('-') {
+            bail!("oha option {} is not allowed", arg);
+        } else {
+            if url.replace(arg).is_some() {
+                bail!("Unexpected oha argument {}", arg);
+            }
+            if !(arg.starts_with("http://") || arg.starts_with("https://")) {
+                bail!("Invalid target URL {}", arg);
+            }
+            i += 1;
+        }
+    }
+    if i > job.args.len() {
+        bail!(
+            "Missing value for oha option {}",
+            job.args[job.args.len() - 1]
+        );
+    }
+    for file in &job.files {
+        let option = file.arg.checked_sub(1).and_then(|i| job.args.get(i));
+        if !option.is_some_and(|o| FILE_OPTIONS.contains(&o.as_str())) {
+            bail!("A job file must follow a file option");
+        }
+    }
+    Ok(())
+}
+
+fn run_job(connection: &mut Connection, job: &Job, dir: &Path) -> Result<()> {
+    let mut args = job.args.clone();
+    for (i, file) in job.files.iter().enumerate() {
+        let slot = args
+            .get_mut(file.arg)
+            .ok_or_else(|| anyhow!("File argument out of range"))?;
+        std::fs::create_dir_all(dir)?;
+        let path: PathBuf = dir.join(format!("file-{}", i));
+        let content = STANDARD
+            .decode(&file.content)
+            .context("Invalid file content")?;
+        std::fs::write(&path, content)?;
+        *slot = path.to_string_lossy().into_owned();
+    }
+
+    let mut cmd = Command::new("oha");
+    cmd.args(&args);
+    cmd.stdin(Stdio::null());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+
+    connection.send(&Message::Ready)?;
+    match connection.receive(GO_TIMEOUT)? {
+        Some(Message::Go) => {}
+        // The coordinator gave up on the step (another worker failed to get ready)
+        _ => return Ok(()),
+    }
+
+    let child = cmd.spawn().context("Failed to spawn oha process")?;
+    let monitor = client::start_monitor(vec![child.id()], job.seconds);
+
+    // The coordinator cancels the run by message or by closing the connection
+    let cancel = AtomicBool::new(false);
+    let finished = AtomicBool::new(false);
+    let output = std::thread::scope(|scope| {
+        scope.spawn(|| watch_for_cancel(&mut connection.reader, &cancel, &finished));
+        let output = collect_oha_output(child, Duration::from_secs(job.timeout_seconds), &cancel);
+        finished.store(true, Ordering::Relaxed);
+        output
+    });
+    let client = monitor.and_then(|handle| handle.join().ok().flatten());
+    if cancel.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    let output = output?;
+
+    let outcome = match output {
+        Some((output, success)) => Outcome {
+            output: Some(output),
+            success,
+            client,
+        },
+        None => Outcome {
+            client,
+            ..Outcome::default()
+        },
+    };
+    connection.send(&Message::Done(outcome))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(args: &[&str], files: &[usize]) -> Job {
+        Job {
+            token: None,
+            args: args.iter().map(|a| a.to_string()).collect(),
+            files: files
+                .iter()
+                .map(|&arg| JobFile {
+                    arg,
+                    content: String::new(),
+                })
+                .collect(),
+            seconds: 1,
+            timeout_seconds: 5,
+        }
+    }
+
+    #[test]
+    fn accepts_the_options_ohabench_sends() {
+        let args = [
+            "-c",
+            "10",
+            "-z",
+            "5s",
+            "-q",
+            "100",
+            "--latency-correction",
+            "-w",
+            "--no-tui",
+            "-m",
+            "POST",
+            "-D",
+            "/tmp/body",
+            "-H",
+            "User-Agent: x",
+            "https://example.com/",
+        ];
+        assert!(check_args(&job(&args, &[12])).is_ok());
+    }
+
+    #[test]
+    fn rejects_local_files_and_unknown_options() {
+        let shadow = job(&["-D", "/etc/shadow", "https://example.com/"], &[]);
+        assert!(check_args(&shadow).is_err());
+        let output = job(&["--output", "/tmp/x", "https://example.com/"], &[]);
+        assert!(check_args(&output).is_err());
+        let misplaced = job(&["-H", "X: y", "https://example.com/"], &[1]);
+        assert!(check_args(&misplaced).is_err());
+        let two_urls = job(&["https://a.example/", "https://b.example/"], &[]);
+        assert!(check_args(&two_urls).is_err());
+        let missing_value = job(&["https://example.com/", "-c"], &[]);
+        assert!(check_args(&missing_value).is_err());
+    }
+
+    #[test]
+    fn compares_tokens() {
+        assert!(token_matches(None, None));
+        assert!(token_matches(Some("secret"), Some("secret")));
+        assert!(!token_matches(Some("secret"), Some("secreT")));
+        assert!(!token_matches(Some("secret"), Some("secret2")));
+        assert!(!token_matches(Some("secret"), None));
+    }
+}